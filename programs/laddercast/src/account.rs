@@ -2,16 +2,22 @@ use core::mem::size_of;
 
 use anchor_lang::prelude::*;
 
-use crate::{GameTurnInfo, Tile, TileType};
-use crate::utils::ItemType;
+use crate::{GameRules, GameTurnInfo, Tile, TileType};
+use crate::error::ErrorCode;
+use crate::utils::{get_slot_hash, get_turn_entropy, INVENTORY_CAPACITY, ItemType, SlotHash, TILE_HISTORY_CAPACITY, TileChange};
 use crate::utils::Modifiers;
 use crate::utils::TurnCommit;
 
-/// Signer PDA with seeds ["game_signer", signer_bump]
+/// Signer PDA with seeds ["game_signer", game.key, signer_bump]
 #[account]
 pub struct Game {
     pub authority: Pubkey,
-    pub map: [[Option<Tile>; 3]; 30],
+    /// Number of levels of the map
+    pub levels: u8,
+    /// Number of columns per level of the map
+    pub columns: u8,
+    /// Indexed as map[level][column], sized levels x columns at init
+    pub map: Vec<Vec<Option<Tile>>>,
     pub turn_info: GameTurnInfo,
     pub last_turn_added: u32,
     pub signer_bump: u8,
@@ -27,23 +33,57 @@ pub struct Game {
 }
 
 impl Game {
-    pub const SIZE: usize =
-        8 + 32 + (90 + 1) * Tile::SIZE + 8 + 1 + 32 + 32 + 32 + 32 + 32 + 32 + 300;
+    pub fn size(levels: u8, columns: u8) -> usize {
+        8 + 32 + 1 + 1 + map_size(levels, columns, Tile::SIZE) + GameTurnInfo::SIZE + 4 + 1 + 32 + 32 + 32 + 32 + 32 + 300
+    }
 
-    pub fn get_map_as_tile_features_only(&self) -> [[Option<TileType>; 3]; 30] {
-        let mut result: [[Option<TileType>; 3]; 30] = [[None; 3]; 30];
+    pub fn get_map_as_tile_features_only(&self) -> Vec<Vec<Option<TileType>>> {
+        self.map
+            .iter()
+            .map(|level| {
+                level
+                    .iter()
+                    .map(|tile| match tile {
+                        None => None,
+                        Some(tile) => Some((*tile).tile_type)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
 
-        for (i0, v0) in self.map.iter().enumerate() {
-            for (i1, v1) in v0.iter().enumerate() {
-                result[i0][i1] = match v1 {
-                    None => None,
-                    Some(tile) => Some((*tile).tile_type)
-                }
-            }
-        }
+/// Borsh size of a levels x columns map (vec of vec of options)
+fn map_size(levels: u8, columns: u8, tile_size: usize) -> usize {
+    4 + levels as usize * (4 + columns as usize * (1 + tile_size))
+}
 
-        result
-    }
+/// Rules (economy / balancing) for a specific game
+/// PDA with seeds ["game_config", game.key]
+#[account]
+pub struct GameConfig {
+    pub bump: u8,
+    /// Game
+    pub game: Pubkey,
+    pub rules: GameRules,
+}
+
+impl GameConfig {
+    pub const SIZE: usize = 8 + 1 + 32 + GameRules::SIZE + 300;
+}
+
+/// Holds the key allowed to call the admin instructions
+/// PDA with seeds ["program_admin"]
+#[account]
+pub struct ProgramAdmin {
+    pub bump: u8,
+    pub admin: Pubkey,
+    /// Set by propose_admin, becomes admin once it calls accept_admin
+    pub pending_admin: Option<Pubkey>,
+}
+
+impl ProgramAdmin {
+    pub const SIZE: usize = 8 + 1 + 32 + 1 + 32 + 300;
 }
 
 /// Data about a specific turn
@@ -51,29 +91,103 @@ impl Game {
 #[account]
 pub struct TurnData {
     pub bump: u8,
+    /// Paid the rent, gets it back when the turn data is closed
+    pub payer: Pubkey,
     pub resource_1_burned: u64,
     pub resource_2_burned: u64,
     pub resource_3_burned: u64,
+    /// Minted back by salvaging items during the turn, indexed with FIRE_INDEX, WATER_INDEX and EARTH_INDEX
+    pub resources_salvaged: [u64; 3],
+    /// Casters that got their LADA share, past that the burned totals can't go down anymore
+    pub rewards_redeemed: u32,
+    /// Slot the crank ending the turn landed in, its hash becomes the entropy (see record_turn_entropy)
+    pub entropy_slot: u64,
+    /// Mixed with the casters' secrets when redeeming, zeroed until recorded
+    pub entropy: [u8; 32],
     //Backup of the map for that specific turn
-    pub map: [[Option<TileType>; 3]; 30],
+    pub map: Vec<Vec<Option<TileType>>>,
 }
 
 impl TurnData {
-    pub const SIZE: usize = 8 + 1 + 8 + 8 + 8 + 90 * (1 + size_of::<TileType>());
+    pub fn size(levels: u8, columns: u8) -> usize {
+        8 + 1 + 32 + 8 + 8 + 8 + 3 * 8 + 4 + 8 + 32 + map_size(levels, columns, size_of::<TileType>())
+    }
+
+    pub fn get_entropy(&self) -> Result<[u8; 32], ProgramError> {
+        if self.entropy == [0; 32] {
+            return Err(ErrorCode::TurnEntropyNotRecorded.into());
+        }
+
+        Ok(self.entropy)
+    }
+
+    /// Entropy comes from the hash of the crank slot while the slot hashes sysvar still has it,
+    /// the turn has to be over. Recording it again does nothing
+    pub fn record_entropy(&mut self, slot_hashes: &[u8], turn: u32) -> ProgramResult {
+        if self.entropy != [0; 32] {
+            return Ok(());
+        }
+
+        let slot_hash = match get_slot_hash(slot_hashes, self.entropy_slot) {
+            SlotHash::Hashed(slot_hash) => slot_hash,
+            SlotHash::NotHashed => {
+                return Err(ErrorCode::TurnEntropyNotReady.into());
+            }
+            SlotHash::Expired => {
+                return Err(ErrorCode::TurnEntropyExpired.into());
+            }
+        };
+
+        //Turns added by the same crank_many share the crank slot
+        self.entropy = get_turn_entropy(&slot_hash, turn);
+
+        Ok(())
+    }
 }
 
 impl Default for TurnData {
     fn default() -> Self {
         Self {
             bump: 0,
+            payer: Pubkey::default(),
             resource_1_burned: 0,
             resource_2_burned: 0,
             resource_3_burned: 0,
-            map: [[None; 3]; 30],
+            resources_salvaged: [0; 3],
+            rewards_redeemed: 0,
+            entropy_slot: 0,
+            entropy: [0; 32],
+            map: vec![],
         }
     }
 }
 
+/// Last tile changes of a game, the oldest ones get overwritten once full
+/// PDA with seeds ["tile_history", game.key]
+#[account]
+pub struct TileHistory {
+    pub bump: u8,
+    /// Game
+    pub game: Pubkey,
+    /// Index the next change is written at
+    pub head: u16,
+    pub changes: Vec<TileChange>,
+}
+
+impl TileHistory {
+    pub const SIZE: usize = 8 + 1 + 32 + 2 + 4 + TILE_HISTORY_CAPACITY * TileChange::SIZE;
+
+    pub fn record(&mut self, change: TileChange) {
+        if self.changes.len() < TILE_HISTORY_CAPACITY {
+            self.changes.push(change);
+        } else {
+            self.changes[self.head as usize] = change;
+        }
+
+        self.head = ((self.head as usize + 1) % TILE_HISTORY_CAPACITY) as u16;
+    }
+}
+
 /// PDA with seeds ["merkle_roots", game.key, item_type, item_level]
 /// For combined, item type = "combined" and item level = 0
 #[account]
@@ -186,10 +300,83 @@ pub struct Item {
     pub owner: Pubkey,
     pub level: u8,
     pub item_type: ItemType,
-    /// Caster
+    /// Caster it is equipped by, or that committed to reforge it (see release_reforged_item)
     pub equipped_owner: Option<Pubkey>,
 }
 
 impl Item {
     pub const SIZE: usize = 8 + 32 + 32 + 1 + size_of::<ItemType>() + 33 + 300;
 }
+
+/// Items of a player stored inline, instead of an account (and its rent) per item
+/// PDA with seeds ["inventory", player.key]
+#[account]
+pub struct Inventory {
+    pub bump: u8,
+    /// Player
+    pub player: Pubkey,
+    /// Id of the next item added, ids are never reused so an item keeps its id
+    pub next_id: u32,
+    pub items: Vec<InventoryItem>,
+}
+
+impl Inventory {
+    /// The account only has room for the items it holds (see INVENTORY_GROWTH), it's reallocated when full
+    pub const fn space(capacity: usize) -> usize {
+        8 + 1 + 32 + 4 + 4 + capacity * InventoryItem::SIZE
+    }
+
+    pub fn add_item(&mut self, level: u8, item_type: ItemType) -> Result<u32, ProgramError> {
+        if self.items.len() >= INVENTORY_CAPACITY {
+            return Err(ErrorCode::InventoryFull.into());
+        }
+
+        let id = self.next_id;
+
+        self.items.push(InventoryItem {
+            id,
+            level,
+            item_type,
+            equipped_owner: None,
+        });
+        self.next_id = self.next_id.checked_add(1).unwrap();
+
+        Ok(id)
+    }
+
+    pub fn get_item(&self, id: u32) -> Result<&InventoryItem, ProgramError> {
+        match self.items.iter().find(|item| item.id == id) {
+            None => Err(ErrorCode::ItemNotExists.into()),
+            Some(item) => Ok(item),
+        }
+    }
+
+    pub fn get_item_mut(&mut self, id: u32) -> Result<&mut InventoryItem, ProgramError> {
+        match self.items.iter_mut().find(|item| item.id == id) {
+            None => Err(ErrorCode::ItemNotExists.into()),
+            Some(item) => Ok(item),
+        }
+    }
+
+    pub fn remove_item(&mut self, id: u32) -> Result<InventoryItem, ProgramError> {
+        match self.items.iter().position(|item| item.id == id) {
+            None => Err(ErrorCode::ItemNotExists.into()),
+            Some(index) => Ok(self.items.swap_remove(index)),
+        }
+    }
+}
+
+/// Same as an Item account, game and owner are the ones of the inventory's player
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct InventoryItem {
+    pub id: u32,
+    pub level: u8,
+    pub item_type: ItemType,
+    /// Caster it is equipped by, or that committed to reforge it (see release_reforged_item)
+    pub equipped_owner: Option<Pubkey>,
+}
+
+impl InventoryItem {
+    /// Borsh sizes, the in memory size of ItemType would count the padding of its largest variant
+    pub const SIZE: usize = 4 + 1 + ItemType::SIZE + 1 + 32;
+}
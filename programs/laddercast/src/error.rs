@@ -26,7 +26,7 @@ pub enum ErrorCode {
     #[msg("You already did this action, wait for next turn.")]
     ActionAlreadyDone,
 
-    #[msg("Item already equipped by another caster.")]
+    #[msg("Item already in use by a caster, equipped or being reforged.")]
     ItemAlreadyInUse,
 
     #[msg("Item does not exist.")]
@@ -113,4 +113,91 @@ pub enum ErrorCode {
     #[msg("Invalid number of instructions provided.")]
     InvalidInstructionOrdering,
 
+    #[msg("Invalid game rules provided.")]
+    InvalidGameRules,
+
+    #[msg("Invalid map dimensions provided.")]
+    InvalidMapDimensions,
+
+    #[msg("Game signer has already been migrated.")]
+    GameSignerAlreadyMigrated,
+
+    #[msg("Signer is not the pending program admin.")]
+    NotPendingAdmin,
+
+    #[msg("Invalid crank reward accounts.")]
+    InvalidCrankRewardAccounts,
+
+    #[msg("Invalid turn data account.")]
+    InvalidTurnDataAccount,
+
+    #[msg("Turn data can still be redeemed, cannot close it yet.")]
+    TurnDataStillInUse,
+
+    #[msg("Commitment doesn't match the one of the turn.")]
+    InvalidCommitment,
+
+    #[msg("Secret doesn't match the committed hash.")]
+    InvalidSecret,
+
+    #[msg("Item account must be a new account signing the transaction.")]
+    InvalidItemAccount,
+
+    #[msg("Not enough item accounts provided for the items produced.")]
+    ItemAccountMissing,
+
+    #[msg("Inventory is full.")]
+    InventoryFull,
+
+    #[msg("Inventory doesn't belong to the player.")]
+    InvalidInventory,
+
+    #[msg("Same item provided more than once.")]
+    DuplicateItem,
+
+    #[msg("Invalid number of chests or chest accounts.")]
+    InvalidChestBatch,
+
+    #[msg("Only equipment and spell books can be salvaged.")]
+    ItemNotSalvageable,
+
+    #[msg("Account provided doesn't hold the item equipped by the caster.")]
+    EquippedItemAccountMismatch,
+
+    #[msg("Equipped item belongs to another game.")]
+    EquippedItemWrongGame,
+
+    #[msg("Equipped item belongs to another player.")]
+    EquippedItemWrongOwner,
+
+    #[msg("Item isn't equipped by this caster.")]
+    ItemNotEquippedByCaster,
+
+    #[msg("Item isn't equipped in the slot of its equipment type.")]
+    EquippedItemWrongSlot,
+
+    #[msg("Account isn't in the layout it is migrated from.")]
+    InvalidLegacyAccount,
+
+    #[msg("Signer is not the game authority.")]
+    NotGameAuthority,
+
+    #[msg("Turn entropy isn't recorded yet, call record_turn_entropy first.")]
+    TurnEntropyNotRecorded,
+
+    #[msg("Turn entropy can't be recorded before the turn is over and its crank slot hashed.")]
+    TurnEntropyNotReady,
+
+    #[msg("No reforge to redeem.")]
+    EmptyReforge,
+
+    #[msg("Reforge has to be redeemed before the rewards.")]
+    PendingReforge,
+
+    #[msg("Turn entropy wasn't recorded while its crank slot was in the slot hashes, the turn can only be cancelled.")]
+    TurnEntropyExpired,
+
+    #[msg("Stale turn still has a reforge, cancel it to release the item first.")]
+    StaleTurnPendingReforge,
+
 }
@@ -1,9 +1,24 @@
 use anchor_lang::prelude::*;
 
-use crate::Tile;
+use crate::{CrankReward, Tile};
+use crate::utils::TileChange;
 
 #[event]
 pub struct NewTurn {
     pub turn: u32,
-    pub tile_map: [[Option<Tile>; 3]; 30],
+    pub tile_map: Vec<Vec<Option<Tile>>>,
+}
+
+#[event]
+pub struct CrankRewarded {
+    pub turn: u32,
+    pub cranker: Pubkey,
+    pub reward: CrankReward,
+}
+
+/// Only what changed on the map during the crank
+#[event]
+pub struct TilesChanged {
+    pub turn: u32,
+    pub changes: Vec<TileChange>,
 }
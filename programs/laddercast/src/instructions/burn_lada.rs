@@ -3,6 +3,7 @@ use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::account::*;
+use crate::error::ErrorCode;
 
 #[derive(Accounts)]
 pub struct BurnLada<'info> {
@@ -12,6 +13,9 @@ pub struct BurnLada<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 
+    #[account(seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Account<'info, ProgramAdmin>,
+
     pub game_account: Box<Account<'info, Game>>,
 
     #[account(mut, constraint = lada_mint.to_account_info().key() == game_account.lada_mint_account)]
@@ -24,6 +28,10 @@ pub struct BurnLada<'info> {
 //This function is to clear the distributed LADA during our testing phase
 //Don't call this function or you will lose all your LADA
 pub fn burn_lada(ctx: Context<BurnLada>) -> ProgramResult {
+    if ctx.accounts.authority.key() != ctx.accounts.program_admin.admin {
+        return Err(ErrorCode::NotSuperAdmin.into());
+    }
+
     token::burn(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info().clone(),
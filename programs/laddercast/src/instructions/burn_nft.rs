@@ -6,7 +6,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::account::{Caster, Game, Item, MetadataNFTCaster, MetadataNFTItem, Player};
 use crate::error::ErrorCode;
-use crate::utils::{Modifiers};
+use crate::utils::{Modifiers, store_item};
 
 #[derive(Accounts)]
 pub struct RedeemItem<'info> {
@@ -27,8 +27,9 @@ pub struct RedeemItem<'info> {
     bump = nft_metadata.self_bump,
     close = authority)]
     pub nft_metadata: Account<'info, MetadataNFTItem>,
-    #[account(init, payer = authority, space = Item::SIZE)]
-    pub item: Box<Account<'info, Item>>,
+    /// Player's inventory, or a new account signing the transaction
+    #[account(mut)]
+    pub item: UncheckedAccount<'info>,
 }
 
 pub fn redeem_item(ctx: Context<RedeemItem>) -> ProgramResult {
@@ -38,12 +39,19 @@ pub fn redeem_item(ctx: Context<RedeemItem>) -> ProgramResult {
 
     let item_metadata = ctx.accounts.nft_metadata.item;
 
-    let item = &mut ctx.accounts.item;
-    item.item_type = item_metadata.item_type;
-    item.level = item_metadata.level;
-    item.game = ctx.accounts.game.key();
-    item.owner = ctx.accounts.player.key();
-    item.equipped_owner = None;
+    store_item(
+        &ctx.accounts.item.to_account_info(),
+        Item {
+            game: ctx.accounts.game.key(),
+            owner: ctx.accounts.player.key(),
+            level: item_metadata.level,
+            item_type: item_metadata.item_type,
+            equipped_owner: None,
+        },
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.program_id,
+    )?;
 
     token::burn(
         CpiContext::new(
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{EARTH_INDEX, FIRE_INDEX, release_reforged_item, WATER_INDEX};
+
+#[derive(Accounts)]
+#[instruction(turn: u32)]
+pub struct CasterCancelTurn<'info> {
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(mut, constraint = caster.owner == player.key())]
+    pub caster: Box<Account<'info, Caster>>,
+
+    /// Turn data of the committed turn, might already be closed if the commit is stale
+    #[account(mut, seeds = [b"turn_data", game.key().as_ref(), turn.to_string().as_ref()], bump)]
+    pub game_turn_data: UncheckedAccount<'info>,
+    // Remaining accounts: the item reforged (or the inventory holding it) if a reforge was committed
+}
+
+/// Drops the pending turn commit of the given turn, the resources and the spell book burned for it are lost.
+/// An item committed for a reforge is released as it was
+pub fn caster_cancel_turn<'info>(ctx: Context<'_, '_, '_, 'info, CasterCancelTurn<'info>>, turn: u32) -> ProgramResult {
+    let turn_commit = match ctx.accounts.caster.turn_commit {
+        None => {
+            return Err(ErrorCode::EmptyTurnCommit.into());
+        }
+        Some(turn_commit) => turn_commit,
+    };
+
+    if turn_commit.turn != turn {
+        return Err(ErrorCode::InvalidTurnDataAccount.into());
+    }
+
+    let turn_data_info = ctx.accounts.game_turn_data.to_account_info();
+
+    //The burned resources no longer count in the LADA split of the turn, unless a caster already got its
+    //share: lowering the total then would give the casters redeeming after it more than the distribution
+    if turn_data_info.owner == ctx.program_id && !turn_data_info.data_is_empty() {
+        let mut turn_data: Account<TurnData> = Account::try_from(&turn_data_info)?;
+
+        if turn_data.rewards_redeemed == 0 {
+            turn_data.resource_1_burned = turn_data
+                .resource_1_burned
+                .saturating_sub(turn_commit.resources_burned[FIRE_INDEX]);
+            turn_data.resource_2_burned = turn_data
+                .resource_2_burned
+                .saturating_sub(turn_commit.resources_burned[WATER_INDEX]);
+            turn_data.resource_3_burned = turn_data
+                .resource_3_burned
+                .saturating_sub(turn_commit.resources_burned[EARTH_INDEX]);
+
+            turn_data.exit(ctx.program_id)?;
+        }
+    }
+
+    if let Some(reforge_snapshot) = turn_commit.actions.reforge {
+        let item_info = match ctx.remaining_accounts.first() {
+            None => {
+                return Err(ErrorCode::ItemAccountMissing.into());
+            }
+            Some(item_info) => item_info,
+        };
+
+        release_reforged_item(
+            item_info,
+            &reforge_snapshot,
+            &ctx.accounts.game.key(),
+            &ctx.accounts.player.key(),
+            &ctx.accounts.caster.key(),
+            ctx.program_id,
+        )?;
+    }
+
+    ctx.accounts.caster.turn_commit = None;
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::{Tile, TileType};
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{CRAFTING_COST_MULTIPLIER, get_current_tile, ItemRarity, ItemType, zombify_account};
+use crate::utils::{get_crafting_cost, get_current_tile, get_turn_commit_for_new_action, ItemRarity, ItemType, load_item, remove_item};
 use crate::utils::CraftingSnapshot;
 use crate::utils::TurnCommit;
 
@@ -22,6 +22,8 @@ pub struct Craft<'info> {
 
     #[account(mut)]
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
     #[account(mut, constraint = caster.owner == player.key())]
@@ -50,40 +52,43 @@ pub struct Craft<'info> {
     payer = authority)]
     pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut, constraint = item_1.game == game.key(), constraint = item_1.owner == player.key(), constraint = item_1.equipped_owner == None, close = authority)]
-    pub item_1: Box<Account<'info, Item>>,
-    #[account(mut, constraint = item_2.game == game.key(), constraint = item_2.owner == player.key(), constraint = item_2.equipped_owner == None, close = authority)]
-    pub item_2: Box<Account<'info, Item>>,
-    #[account(mut, constraint = item_3.game == game.key(), constraint = item_3.owner == player.key(), constraint = item_3.equipped_owner == None, close = authority)]
-    pub item_3: Box<Account<'info, Item>>,
+    /// Item accounts, or the player's inventory for the ones with an item id
+    #[account(mut)]
+    pub item_1: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub item_2: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub item_3: UncheckedAccount<'info>,
 
     #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
     pub game_turn_data: Box<Account<'info, TurnData>>,
 }
 
-pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
+pub fn caster_commit_craft(
+    ctx: Context<Craft>,
+    commitment: [u8; 32],
+    item_ids: [Option<u32>; 3],
+) -> ProgramResult {
     let map = ctx.accounts.game.map.clone();
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let turn_data_retention = ctx.accounts.game_config.rules.turn_data_retention;
+    let rules = ctx.accounts.game_config.rules;
 
     let caster = &mut ctx.accounts.caster;
     let turn_data = &mut ctx.accounts.game_turn_data;
 
-    let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
-        Some(turn_commit) => turn_commit,
-        None => TurnCommit {
-            turn: game_turn,
-            ..Default::default()
-        },
-    };
-
-    if caster_turn_commit.turn != game_turn {
-        return Err(ErrorCode::PendingTurn.into());
-    }
+    let mut caster_turn_commit: TurnCommit = get_turn_commit_for_new_action(
+        caster.turn_commit,
+        game_turn,
+        turn_data_retention,
+    )?;
 
     if caster_turn_commit.actions.crafting != None {
         return Err(ErrorCode::ActionAlreadyDone.into());
     }
 
+    caster_turn_commit.commit(commitment)?;
+
     let mut crafting_snapshot: CraftingSnapshot = CraftingSnapshot {
         min_level: u8::MAX, //Starts at the max because we want the lowest
         min_rarity: ItemRarity::Legendary, //Starts legendary because we take lowest of all 3
@@ -114,8 +119,8 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
         return Err(ErrorCode::NotCraftingTile.into());
     }
 
-    //Take the resources cost for crafting (since 0 based, gotta add 1)
-    let per_resource_burn = caster.modifiers.tile_level.checked_add(1).unwrap().checked_mul(CRAFTING_COST_MULTIPLIER).unwrap() as u64;
+    //Take the resources cost for crafting
+    let per_resource_burn = get_crafting_cost(caster.modifiers.tile_level, &rules);
 
     let resource_1_token_account = &ctx.accounts.resource_1_token_account;
     let resource_2_token_account = &ctx.accounts.resource_2_token_account;
@@ -194,13 +199,35 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
         crafting_snapshot.max_rarity = ItemRarity::Epic;
     }
 
-    for removed_item in [
-        ctx.accounts.item_1.clone(),
-        ctx.accounts.item_2.clone(),
-        ctx.accounts.item_3.clone(),
-    ]
-        .iter()
+    let item_infos = [
+        ctx.accounts.item_1.to_account_info(),
+        ctx.accounts.item_2.to_account_info(),
+        ctx.accounts.item_3.to_account_info(),
+    ];
+
+    let mut removed_items = vec![];
+
+    for (item_info, item_id) in item_infos.iter().zip(item_ids.iter()) {
+        removed_items.push(load_item(
+            item_info,
+            *item_id,
+            &ctx.accounts.game.key(),
+            &ctx.accounts.player.key(),
+        )?);
+    }
+
+    if removed_items[0].key == removed_items[1].key
+        || removed_items[0].key == removed_items[2].key
+        || removed_items[1].key == removed_items[2].key
     {
+        return Err(ErrorCode::DuplicateItem.into());
+    }
+
+    for removed_item in removed_items.iter() {
+        if removed_item.equipped_owner != None {
+            return Err(ErrorCode::ItemAlreadyInUse.into());
+        }
+
         if removed_item.level < crafting_snapshot.min_level {
             crafting_snapshot.min_level = removed_item.level;
         }
@@ -248,22 +275,15 @@ pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
 
     ctx.accounts.caster.turn_commit = Some(caster_turn_commit);
 
-    //Zombifies the 3 item accounts
-    zombify_account(
-        &mut ctx.accounts.item_1,
-        ctx.accounts.authority.to_account_info(),
-        ctx.program_id,
-    )?;
-    zombify_account(
-        &mut ctx.accounts.item_2,
-        ctx.accounts.authority.to_account_info(),
-        ctx.program_id,
-    )?;
-    zombify_account(
-        &mut ctx.accounts.item_3,
-        ctx.accounts.authority.to_account_info(),
-        ctx.program_id,
-    )?;
+    //Zombifies the 3 item accounts (or takes them out of the inventory)
+    for (item_info, item_id) in item_infos.iter().zip(item_ids.iter()) {
+        remove_item(
+            item_info,
+            *item_id,
+            ctx.accounts.authority.to_account_info(),
+            ctx.program_id,
+        )?;
+    }
 
     Ok(())
 }
@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 use crate::{Tile, TileType};
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{get_current_tile, TurnCommit};
+use crate::utils::{get_current_tile, get_turn_commit_for_new_action, TurnCommit};
 
 #[derive(Accounts)]
 pub struct Loot<'info> {
@@ -14,28 +14,25 @@ pub struct Loot<'info> {
 
     #[account(mut)]
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Account<'info, Player>,
     #[account(mut, constraint = caster.owner == player.key())]
     pub caster: Account<'info, Caster>,
 }
 
-pub fn caster_commit_loot(ctx: Context<Loot>) -> ProgramResult {
+pub fn caster_commit_loot(ctx: Context<Loot>, commitment: [u8; 32]) -> ProgramResult {
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let turn_data_retention = ctx.accounts.game_config.rules.turn_data_retention;
 
     let caster = &mut ctx.accounts.caster;
 
-    let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
-        Some(turn_commit) => turn_commit,
-        None => TurnCommit {
-            turn: game_turn,
-            ..Default::default()
-        },
-    };
-
-    if caster_turn_commit.turn != game_turn {
-        return Err(ErrorCode::PendingTurn.into());
-    }
+    let mut caster_turn_commit: TurnCommit = get_turn_commit_for_new_action(
+        caster.turn_commit,
+        game_turn,
+        turn_data_retention,
+    )?;
 
     //Need to do this because if user moved to a looting tile during that turn, then looting is authorized
     let (dest_level, dest_column): (u8, u8) = {
@@ -73,6 +70,8 @@ pub fn caster_commit_loot(ctx: Context<Loot>) -> ProgramResult {
         return Err(ErrorCode::ActionAlreadyDone.into());
     }
 
+    caster_turn_commit.commit(commitment)?;
+
     caster_turn_commit.actions.loot = true;
 
     caster_turn_commit.actions.add_new_action_order(0);
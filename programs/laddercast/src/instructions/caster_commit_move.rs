@@ -6,7 +6,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::{Tile, TileType};
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{EARTH_INDEX, FIRE_INDEX, get_current_tile, MOVE_COST_MULTIPLIER, WATER_INDEX};
+use crate::utils::{EARTH_INDEX, FIRE_INDEX, get_current_tile, get_move_cost, get_teleport_cost, get_turn_commit_for_new_action, WATER_INDEX};
 use crate::utils::TurnCommit;
 
 #[derive(Accounts)]
@@ -21,6 +21,8 @@ pub struct Move<'info> {
 
     #[account(mut)]
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
     #[account(mut, constraint = caster.owner == player.key())]
@@ -54,22 +56,29 @@ pub struct Move<'info> {
 }
 
 pub fn caster_commit_move(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult {
+    commit_move(ctx, lvl, clm, false)
+}
+
+/// Same as a move (redeemed as one), but can go down the ladder or jump columns for a cost scaled by the distance,
+/// on top of what moving to the destination costs
+pub fn caster_commit_teleport(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult {
+    commit_move(ctx, lvl, clm, true)
+}
+
+fn commit_move(ctx: Context<Move>, lvl: u8, clm: u8, is_teleport: bool) -> ProgramResult {
     let map = ctx.accounts.game.map.clone();
+    let (levels, columns) = (ctx.accounts.game.levels, ctx.accounts.game.columns);
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let turn_data_retention = ctx.accounts.game_config.rules.turn_data_retention;
+    let rules = ctx.accounts.game_config.rules;
     let caster = &mut ctx.accounts.caster;
     let turn_data = &mut ctx.accounts.game_turn_data;
 
-    let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
-        Some(turn_commit) => turn_commit,
-        None => TurnCommit {
-            turn: game_turn,
-            ..Default::default()
-        },
-    };
-
-    if caster_turn_commit.turn != game_turn {
-        return Err(ErrorCode::PendingTurn.into());
-    }
+    let mut caster_turn_commit: TurnCommit = get_turn_commit_for_new_action(
+        caster.turn_commit,
+        game_turn,
+        turn_data_retention,
+    )?;
 
     if caster_turn_commit.actions.mv != None {
         return Err(ErrorCode::ActionAlreadyDone.into());
@@ -77,19 +86,37 @@ pub fn caster_commit_move(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult
 
     let (dest_level, dest_column): (u8, u8) = (lvl, clm);
 
-    //Can only go up the ladder, and can only go if your caster is the right level
+    if dest_level >= levels || dest_column >= columns {
+        return Err(ErrorCode::InvalidMove.into());
+    }
+
+    //Can only go if your caster is the right level
     //Level is 1 based but map is 0 based, so have to remove 1 from caster level
-    if dest_level < caster.modifiers.tile_level || dest_level > (caster.level - 1) {
+    if dest_level > (caster.level - 1) {
         return Err(ErrorCode::InvalidMove.into());
     }
 
-    let d_col = dest_column as i8;
-    let f_col = caster.modifiers.tile_column as i8;
-    let distance = (d_col - f_col).abs();
+    if is_teleport {
+        //Anywhere else down the ladder or on the same level, climbing is only done by moves
+        if dest_level > caster.modifiers.tile_level
+            || (dest_level == caster.modifiers.tile_level && dest_column == caster.modifiers.tile_column)
+        {
+            return Err(ErrorCode::InvalidMove.into());
+        }
+    } else {
+        //Can only go up the ladder
+        if dest_level < caster.modifiers.tile_level {
+            return Err(ErrorCode::InvalidMove.into());
+        }
+
+        let d_col = dest_column as i8;
+        let f_col = caster.modifiers.tile_column as i8;
+        let distance = (d_col - f_col).abs();
 
-    //Can only move to the column next to you or move up by 1
-    if distance > 1 || (dest_level > caster.modifiers.tile_level && distance != 0) {
-        return Err(ErrorCode::InvalidMove.into());
+        //Can only move to the column next to you or move up by 1
+        if distance > 1 || (dest_level > caster.modifiers.tile_level && distance != 0) {
+            return Err(ErrorCode::InvalidMove.into());
+        }
     }
 
     let potential_dest_tile: Option<&Tile> = get_current_tile(&map, dest_level, dest_column);
@@ -100,8 +127,17 @@ pub fn caster_commit_move(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult
 
     let dest_tile = potential_dest_tile.unwrap();
 
-    //MOVE costs 10*resource of tile you're moving too
-    let resource_burned = (dest_level + 1).checked_mul(MOVE_COST_MULTIPLIER).unwrap() as u64;
+    let resource_burned = if is_teleport {
+        get_teleport_cost(
+            caster.modifiers.tile_level,
+            caster.modifiers.tile_column,
+            dest_level,
+            dest_column,
+            &rules,
+        )
+    } else {
+        get_move_cost(dest_level, &rules)
+    };
 
     match dest_tile.tile_type {
         TileType::Fire => {
@@ -5,7 +5,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{EARTH_INDEX, FIRE_INDEX, is_spell_successful, ItemFeature, ItemType, RandomGenerator, SpellSnapshot, SpellType, WATER_INDEX};
+use crate::utils::{EARTH_INDEX, FIRE_INDEX, get_turn_commit_for_new_action, ItemFeature, ItemType, load_item, remove_item, SpellSnapshot, WATER_INDEX};
 use crate::utils::TurnCommit;
 
 #[derive(Accounts)]
@@ -20,14 +20,13 @@ pub struct Spell<'info> {
 
     #[account(mut)]
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
     #[account(mut, constraint = caster.owner == player.key())]
     pub caster: Box<Account<'info, Caster>>,
 
-    #[account(address = sysvar::slot_hashes::id())]
-    pub slots: UncheckedAccount<'info>,
-
     #[account(address = sysvar::instructions::id())]
     pub instruction_sysvar_account: UncheckedAccount<'info>,
 
@@ -54,38 +53,51 @@ pub struct Spell<'info> {
     payer = authority)]
     pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut, constraint = spellbook.game == game.key(), constraint = spellbook.owner == player.key(), constraint = spellbook.equipped_owner.unwrap() == caster.key())]
-    pub spellbook: Box<Account<'info, Item>>,
+    /// Spell book account, or the player's inventory if a spell book id is given, burned by the commit
+    #[account(mut)]
+    pub spellbook: UncheckedAccount<'info>,
 
     #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
     pub game_turn_data: Box<Account<'info, TurnData>>,
 }
 
-pub fn caster_commit_spell(ctx: Context<Spell>) -> ProgramResult {
+pub fn caster_commit_spell(
+    ctx: Context<Spell>,
+    commitment: [u8; 32],
+    spellbook_id: Option<u32>,
+) -> ProgramResult {
+    let spellbook = load_item(
+        &ctx.accounts.spellbook.to_account_info(),
+        spellbook_id,
+        &ctx.accounts.game.key(),
+        &ctx.accounts.player.key(),
+    )?;
+
+    if spellbook.equipped_owner != Some(ctx.accounts.caster.key()) {
+        return Err(ErrorCode::SpellKeyMismatch.into());
+    }
+
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let turn_data_retention = ctx.accounts.game_config.rules.turn_data_retention;
 
     let caster = &mut ctx.accounts.caster;
     let turn_data = &mut ctx.accounts.game_turn_data;
 
-    let mut caster_turn_commit: TurnCommit = match &caster.turn_commit {
-        Some(turn_commit) => *turn_commit,
-        None => TurnCommit {
-            turn: game_turn,
-            ..Default::default()
-        },
-    };
-
-    if caster_turn_commit.turn != game_turn {
-        return Err(ErrorCode::PendingTurn.into());
-    }
+    let mut caster_turn_commit: TurnCommit = get_turn_commit_for_new_action(
+        caster.turn_commit,
+        game_turn,
+        turn_data_retention,
+    )?;
 
     if caster_turn_commit.actions.spell != None {
         return Err(ErrorCode::ActionAlreadyDone.into());
     }
 
+    caster_turn_commit.commit(commitment)?;
+
     if let ItemType::SpellBook {
-        cost_feature, cost, spell, rarity, ..
-    } = ctx.accounts.spellbook.item_type
+        cost_feature, cost, spell, rarity, value
+    } = spellbook.item_type
     {
         let resource_burned = cost as u64;
 
@@ -164,31 +176,26 @@ pub fn caster_commit_spell(ctx: Context<Spell>) -> ProgramResult {
             }
         }
 
-        match spell {
-            SpellType::Craft => {
-                let slots_ref = ctx.accounts.slots.data.borrow();
-                let slots = &**slots_ref;
-
-                let mut rand = RandomGenerator::new(slots, caster.to_account_info().key());
-
-                if is_spell_successful(&mut rand, rarity) {
-                    caster_turn_commit.actions.spell = Some(SpellSnapshot {
-                        is_extra_level_bonus: true
-                    });
-                } else {
-                    caster_turn_commit.actions.spell = Some(SpellSnapshot {
-                        is_extra_level_bonus: false
-                    });
-                }
-            }
-            _ => {
-                caster_turn_commit.actions.spell = Some(SpellSnapshot {
-                    is_extra_level_bonus: false
-                });
-            }
-        }
+        caster_turn_commit.actions.spell = Some(SpellSnapshot {
+            spell,
+            rarity,
+            value,
+            level: spellbook.level,
+        });
+    } else {
+        return Err(ErrorCode::InvalidItemType.into());
     }
 
+    //Burned now rather than when redeemed, a caster can't keep it by not revealing a bad roll
+    remove_item(
+        &ctx.accounts.spellbook.to_account_info(),
+        spellbook_id,
+        ctx.accounts.authority.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    ctx.accounts.caster.modifiers.spell_book = None;
+
     caster_turn_commit.actions.add_new_action_order(1);
 
     ctx.accounts.caster.turn_commit = Some(caster_turn_commit);
@@ -0,0 +1,72 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{ACTION_REFORGE_INDEX, get_action_random_generator, get_turn_commit_to_redeem, reforge_committed_item};
+
+#[derive(Accounts)]
+#[instruction(turn: u32)]
+pub struct CasterRedeemReforgeAction<'info> {
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(mut, constraint = caster.owner == player.key())]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(address = sysvar::instructions::id())]
+    pub instruction_sysvar_account: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::slot_hashes::id())]
+    pub slots: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"turn_data", game.key().as_ref(), turn.to_string().as_ref()], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+
+    /// Item account reforged, or the player's inventory holding it
+    #[account(mut)]
+    pub item: UncheckedAccount<'info>,
+}
+
+/// Rerolls the item committed by reforge_item in the given turn, once that turn is over
+pub fn caster_redeem_reforge(ctx: Context<CasterRedeemReforgeAction>, turn: u32, secret: [u8; 32]) -> ProgramResult {
+    let turn_commit = get_turn_commit_to_redeem(ctx.accounts.caster.turn_commit, ctx.accounts.game.turn_info.turn)?;
+
+    if turn_commit.turn != turn {
+        return Err(ErrorCode::InvalidTurnDataAccount.into());
+    }
+
+    let reforge_snapshot = match turn_commit.actions.reforge {
+        None => {
+            return Err(ErrorCode::EmptyReforge.into());
+        }
+        Some(reforge_snapshot) => reforge_snapshot,
+    };
+
+    turn_commit.verify_secret(&secret)?;
+
+    let slots_ref = ctx.accounts.slots.data.borrow();
+    ctx.accounts.game_turn_data.record_entropy(&slots_ref, turn_commit.turn)?;
+
+    let mut rand = get_action_random_generator(
+        secret,
+        &ctx.accounts.game_turn_data,
+        ctx.accounts.caster.key(),
+        ACTION_REFORGE_INDEX,
+    )?;
+
+    reforge_committed_item(
+        &ctx.accounts.item.to_account_info(),
+        &reforge_snapshot,
+        &ctx.accounts.game.key(),
+        &ctx.accounts.player.key(),
+        &ctx.accounts.caster.key(),
+        &mut rand,
+        ctx.program_id,
+    )?;
+
+    ctx.accounts.caster.turn_commit.as_mut().unwrap().actions.reforge = None;
+
+    Ok(())
+}
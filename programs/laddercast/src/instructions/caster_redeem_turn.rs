@@ -0,0 +1,326 @@
+use std::slice::Iter;
+
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::PlayerBonuses;
+use crate::utils::{
+    ACTION_CRAFT_INDEX, ACTION_LOOT_INDEX, ACTION_MOVE_INDEX, ACTION_REFORGE_INDEX, ACTION_SPELL_INDEX,
+    FIRE_INDEX, get_action_random_generator, get_player_bonuses, get_turn_commit_to_redeem,
+    give_exp_to_caster_spell, is_inventory, ItemType, load_equipped_items, mint_game_resource,
+    new_redeemed_item, reforge_committed_item, roll_crafted_item, roll_loot, roll_spell,
+    settle_turn_reward, SpellEffect, store_item, transfer_game_lada, WATER_INDEX,
+};
+
+/// Redeems every committed action of the turn and its rewards in a single instruction.
+///
+/// Remaining accounts are the item reforged (or the inventory holding it) first if a reforge was committed,
+/// then where the items produced go: the player's inventory, or new item accounts (keypairs signing
+/// the transaction) used in order for the chest found, the spell item and the crafted item.
+/// Only the ones needed are created.
+#[derive(Accounts)]
+pub struct CasterRedeemTurnAction<'info> {
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(mut, constraint = caster.owner == player.key())]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(mut, seeds = [b"game_signer", game.key().as_ref()], bump = game.signer_bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::instructions::id())]
+    pub instruction_sysvar_account: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::slot_hashes::id())]
+    pub slots: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(init_if_needed,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = game_lada_token_account.key() == game.lada_token_account)]
+    pub game_lada_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lada_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [
+    b"turn_data",
+    game.to_account_info().key().as_ref(),
+    (caster.turn_commit.unwrap().turn).to_string().as_ref()
+    ], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+
+    // Accounts of the items equipped by the caster (item accounts or the inventory), read only for the slots in use
+    pub staff: UncheckedAccount<'info>,
+    pub head: UncheckedAccount<'info>,
+    pub robe: UncheckedAccount<'info>,
+}
+
+pub fn caster_redeem_turn<'info>(
+    ctx: Context<'_, '_, '_, 'info, CasterRedeemTurnAction<'info>>,
+    secret: [u8; 32],
+) -> ProgramResult {
+    let turn_commit = get_turn_commit_to_redeem(ctx.accounts.caster.turn_commit, ctx.accounts.game.turn_info.turn)?;
+
+    let actions = turn_commit.actions;
+
+    //A turn with only a move or a burn has nothing random to redeem
+    if actions.loot || actions.spell.is_some() || actions.crafting.is_some() || actions.reforge.is_some() {
+        turn_commit.verify_secret(&secret)?;
+
+        let slots_ref = ctx.accounts.slots.data.borrow();
+        ctx.accounts.game_turn_data.record_entropy(&slots_ref, turn_commit.turn)?;
+    }
+
+    let mut item_accounts = ctx.remaining_accounts.iter();
+
+    if let Some(reforge_snapshot) = actions.reforge {
+        let item_info = match item_accounts.next() {
+            None => {
+                return Err(ErrorCode::ItemAccountMissing.into());
+            }
+            Some(item_info) => item_info,
+        };
+
+        let mut rand = get_action_random_generator(
+            secret,
+            &ctx.accounts.game_turn_data,
+            ctx.accounts.caster.key(),
+            ACTION_REFORGE_INDEX,
+        )?;
+
+        reforge_committed_item(
+            item_info,
+            &reforge_snapshot,
+            &ctx.accounts.game.key(),
+            &ctx.accounts.player.key(),
+            &ctx.accounts.caster.key(),
+            &mut rand,
+            ctx.program_id,
+        )?;
+
+        ctx.accounts.caster.turn_commit.as_mut().unwrap().actions.reforge = None;
+    }
+
+    loop {
+        let index_next_action = ctx
+            .accounts
+            .caster
+            .turn_commit
+            .unwrap()
+            .actions
+            .get_next_action_to_be_executed();
+
+        match index_next_action {
+            ACTION_LOOT_INDEX => {
+                redeem_loot(ctx.accounts, &mut item_accounts, ctx.program_id, secret)?;
+            }
+            ACTION_SPELL_INDEX => {
+                redeem_spell(ctx.accounts, &mut item_accounts, ctx.program_id, secret)?;
+            }
+            ACTION_MOVE_INDEX => {
+                let [tile_level, tile_column] = actions.mv.unwrap();
+
+                ctx.accounts.caster.modifiers.tile_level = tile_level;
+                ctx.accounts.caster.modifiers.tile_column = tile_column;
+            }
+            ACTION_CRAFT_INDEX => {
+                redeem_craft(ctx.accounts, &mut item_accounts, ctx.program_id, secret)?;
+            }
+            _ => break,
+        }
+
+        //Set to 0, since we filter to get the min to find next action
+        ctx.accounts.caster.turn_commit.as_mut().unwrap().actions.action_order[index_next_action] = 0;
+    }
+
+    let amount = settle_turn_reward(
+        &mut ctx.accounts.caster,
+        &mut ctx.accounts.game_turn_data,
+        &ctx.accounts.game_config.rules,
+    );
+
+    transfer_game_lada(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.game_lada_token_account.to_account_info(),
+        ctx.accounts.lada_token_account.to_account_info(),
+        ctx.accounts.game_signer.to_account_info(),
+        &ctx.accounts.game,
+        amount,
+    )
+}
+
+fn next_item_account<'a, 'info>(
+    item_accounts: &mut Iter<'a, AccountInfo<'info>>,
+    program_id: &Pubkey,
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    let item_info = match item_accounts.as_slice().first() {
+        None => {
+            return Err(ErrorCode::ItemAccountMissing.into());
+        }
+        Some(item_info) => item_info,
+    };
+
+    //The inventory holds every item produced, a new item account holds only one
+    if !is_inventory(item_info, program_id) {
+        item_accounts.next();
+    }
+
+    Ok(item_info)
+}
+
+fn mint_resource<'info>(
+    accounts: &CasterRedeemTurnAction<'info>,
+    resource_index: usize,
+    amount: u64,
+) -> ProgramResult {
+    let (mint, to) = match resource_index {
+        FIRE_INDEX => (&accounts.resource_1_mint_account, &accounts.resource_1_token_account),
+        WATER_INDEX => (&accounts.resource_2_mint_account, &accounts.resource_2_token_account),
+        _ => (&accounts.resource_3_mint_account, &accounts.resource_3_token_account),
+    };
+
+    mint_game_resource(
+        accounts.token_program.to_account_info(),
+        mint.to_account_info(),
+        to.to_account_info(),
+        accounts.game_signer.to_account_info(),
+        &accounts.game,
+        amount,
+    )
+}
+
+/// Stores an item produced by the turn in the next item account
+fn store_redeemed_item<'a, 'info>(
+    accounts: &CasterRedeemTurnAction<'info>,
+    item_accounts: &mut Iter<'a, AccountInfo<'info>>,
+    program_id: &Pubkey,
+    item: (u8, ItemType),
+) -> ProgramResult {
+    store_item(
+        next_item_account(item_accounts, program_id)?,
+        new_redeemed_item(accounts.game.key(), accounts.player.key(), item),
+        accounts.authority.to_account_info(),
+        accounts.system_program.to_account_info(),
+        program_id,
+    )
+}
+
+/// Bonuses of the items equipped by the caster, from the staff, head and robe accounts
+fn load_player_bonuses(accounts: &CasterRedeemTurnAction) -> Result<PlayerBonuses, ProgramError> {
+    let caster = &accounts.caster;
+
+    let equipped_items = load_equipped_items(
+        &accounts.head.to_account_info(),
+        &accounts.robe.to_account_info(),
+        &accounts.staff.to_account_info(),
+        &caster.modifiers,
+        &accounts.game.key(),
+        &accounts.player.key(),
+    )?;
+
+    get_player_bonuses(
+        &caster.modifiers,
+        &equipped_items,
+        &caster.key(),
+        accounts.game_config.rules.default_critical_chance_in_percent,
+    )
+}
+
+fn redeem_loot<'a, 'info>(
+    accounts: &mut CasterRedeemTurnAction<'info>,
+    item_accounts: &mut Iter<'a, AccountInfo<'info>>,
+    program_id: &Pubkey,
+    secret: [u8; 32],
+) -> ProgramResult {
+    let mut rand = get_action_random_generator(secret, &accounts.game_turn_data, accounts.caster.key(), ACTION_LOOT_INDEX)?;
+
+    let loot = roll_loot(
+        &accounts.game_turn_data.map,
+        &accounts.caster.modifiers,
+        &accounts.game_config.rules,
+        &load_player_bonuses(accounts)?,
+        &mut rand,
+    )?;
+
+    mint_resource(accounts, loot.resource_index, loot.amount)?;
+
+    match loot.chest {
+        None => Ok(()),
+        Some(chest) => store_redeemed_item(accounts, item_accounts, program_id, chest),
+    }
+}
+
+fn redeem_spell<'a, 'info>(
+    accounts: &mut CasterRedeemTurnAction<'info>,
+    item_accounts: &mut Iter<'a, AccountInfo<'info>>,
+    program_id: &Pubkey,
+    secret: [u8; 32],
+) -> ProgramResult {
+    let mut rand = get_action_random_generator(secret, &accounts.game_turn_data, accounts.caster.key(), ACTION_SPELL_INDEX)?;
+
+    //Spell book was burned by the commit, what it does was kept in the turn commit
+    match roll_spell(&accounts.caster.turn_commit.unwrap().actions.spell.unwrap(), &mut rand) {
+        SpellEffect::Nothing => Ok(()),
+        SpellEffect::Resource { resource_index, amount } => mint_resource(accounts, resource_index, amount),
+        SpellEffect::Experience(value) => {
+            give_exp_to_caster_spell(&mut accounts.caster, value);
+            Ok(())
+        }
+        SpellEffect::Item(level, item_type) => {
+            store_redeemed_item(accounts, item_accounts, program_id, (level, item_type))
+        }
+    }
+}
+
+fn redeem_craft<'a, 'info>(
+    accounts: &mut CasterRedeemTurnAction<'info>,
+    item_accounts: &mut Iter<'a, AccountInfo<'info>>,
+    program_id: &Pubkey,
+    secret: [u8; 32],
+) -> ProgramResult {
+    let mut rand = get_action_random_generator(secret, &accounts.game_turn_data, accounts.caster.key(), ACTION_CRAFT_INDEX)?;
+
+    let crafted_item = roll_crafted_item(
+        &accounts.caster.turn_commit.unwrap().actions,
+        load_player_bonuses(accounts)?.craft_upgrade_chance,
+        &mut rand,
+    );
+
+    store_redeemed_item(accounts, item_accounts, program_id, crafted_item)
+}
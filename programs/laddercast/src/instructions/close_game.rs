@@ -2,7 +2,6 @@ use anchor_lang::prelude::*;
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::constants::*;
 
 #[derive(Accounts)]
 pub struct CloseGame<'info> {
@@ -11,12 +10,15 @@ pub struct CloseGame<'info> {
 
     pub system_program: Program<'info, System>,
 
+    #[account(seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Account<'info, ProgramAdmin>,
+
     #[account(mut)]
     pub game_account: Box<Account<'info, Game>>,
 }
 
 pub fn close_game(ctx: Context<CloseGame>) -> ProgramResult {
-    if ctx.accounts.authority.key().to_string() != GAME_CREATOR_AUTHORITY_PUBKEY {
+    if ctx.accounts.authority.key() != ctx.accounts.program_admin.admin {
         return Err(ErrorCode::NotSuperAdmin.into());
     }
 
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(turn: u32)]
+pub struct CloseTurnData<'info> {
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(mut,
+    seeds = [b"turn_data", game.key().as_ref(), turn.to_string().as_ref()],
+    bump = turn_data.bump,
+    close = payer
+    )]
+    pub turn_data: Account<'info, TurnData>,
+
+    /// Whoever paid for the turn data (the cranker of the turn before)
+    #[account(mut, address = turn_data.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn close_turn_data(ctx: Context<CloseTurnData>, turn: u32) -> ProgramResult {
+    let retention = ctx.accounts.game_config.rules.turn_data_retention;
+
+    //Casters can only redeem a turn while its turn data exists, keep it for the retention window
+    if turn.checked_add(retention).unwrap() >= ctx.accounts.game.turn_info.turn {
+        return Err(ErrorCode::TurnDataStillInUse.into());
+    }
+
+    Ok(())
+}
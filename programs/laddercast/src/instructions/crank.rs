@@ -1,21 +1,30 @@
 use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount};
 
-use crate::account::{Game, TurnData};
+use crate::{CrankReward, GameRules, Tile, TileType};
+use crate::account::{Game, GameConfig, TileHistory, TurnData};
 use crate::error::ErrorCode;
-use crate::event::NewTurn;
-use crate::Tile;
-use crate::utils::{cycle_tile, get_highest_level_and_column, MAX_COLUMN_0_BASED, MAX_LEVEL_0_BASED, RandomGenerator};
+use crate::event::{CrankRewarded, NewTurn, TilesChanged};
+use crate::utils::{cycle_tile, get_highest_level_and_column, RandomGenerator, TileChange, TileChangeKind};
 
 #[derive(Accounts)]
 pub struct Crank<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
 
     #[account(mut)]
     pub game_account: Box<Account<'info, Game>>,
 
+    #[account(seeds = [b"game_signer", game_account.key().as_ref()], bump = game_account.signer_bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"game_config", game_account.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
 
@@ -29,13 +38,25 @@ pub struct Crank<'info> {
     seeds = [b"turn_data", game_account.to_account_info().key().as_ref(), game_account.turn_info.turn.checked_add(1).unwrap().to_string().as_ref()],
     bump,
     payer = authority,
-    space = TurnData::SIZE
+    space = TurnData::size(game_account.levels, game_account.columns)
     )]
     pub game_turn_data: Account<'info, TurnData>,
+
+    #[account(init_if_needed,
+    seeds = [b"tile_history", game_account.key().as_ref()],
+    bump,
+    payer = authority,
+    space = TileHistory::SIZE
+    )]
+    pub tile_history: Box<Account<'info, TileHistory>>,
+    // Remaining accounts, only when the game pays a crank reward (see CrankReward::accounts_len):
+    // game LADA account for a LADA reward or resource mint for a resource reward,
+    // then the token account of the authority receiving it
 }
 
-pub fn crank(ctx: Context<Crank>) -> ProgramResult {
+pub fn crank<'info>(ctx: Context<'_, '_, '_, 'info, Crank<'info>>) -> ProgramResult {
     let game = &mut ctx.accounts.game_account;
+    let rules = &ctx.accounts.game_config.rules;
 
     let clock = Clock::get().unwrap();
 
@@ -48,18 +69,57 @@ pub fn crank(ctx: Context<Crank>) -> ProgramResult {
     let turn_data = &mut ctx.accounts.game_turn_data;
 
     turn_data.bump = *ctx.bumps.get("game_turn_data").unwrap();
+    turn_data.payer = ctx.accounts.authority.key();
+
+    let slots_ref = ctx.accounts.slots.data.borrow();
+    let slots = &**slots_ref;
 
     let current_game_turn_data = &mut ctx.accounts.current_game_turn_data;
 
     current_game_turn_data.map = game.get_map_as_tile_features_only();
+    //Hash of this slot isn't known until it's over, so whoever cranks can't pick the turn entropy
+    current_game_turn_data.entropy_slot = clock.slot;
+
+    let mut rand = RandomGenerator::new(slots, turn_data.to_account_info().key());
+
+    let changes = advance_turn(game, rules, &mut rand);
+
+    game.turn_info.last_crank_seconds = clock.unix_timestamp;
 
+    emit!(NewTurn {
+        turn: game.turn_info.turn,
+        tile_map: game.map.clone()
+    });
+
+    record_tile_changes(
+        &mut ctx.accounts.tile_history,
+        game.key(),
+        *ctx.bumps.get("tile_history").unwrap(),
+        game.turn_info.turn,
+        changes,
+    );
+
+    pay_crank_reward(
+        &ctx.accounts.game_config.rules.crank_reward,
+        &ctx.accounts.game_account,
+        ctx.accounts.authority.key(),
+        ctx.accounts.game_signer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.remaining_accounts,
+    )
+}
+
+/// Moves the game to the next turn: tiles lose a turn of life (cycling when it reaches 0)
+/// and a new tile spawns if enough turns have passed, returns what changed on the map
+pub fn advance_turn(game: &mut Game, rules: &GameRules, rand: &mut RandomGenerator) -> Vec<TileChange> {
     // iterate through array and decrement life turns
     // if turn = 0, spawn a new tile in it's place
     // if a new tile needs to be spawned, spawn a new tile
-    let slots_ref = ctx.accounts.slots.data.borrow();
-    let slots = &**slots_ref;
+    let top_level = game.levels - 1;
+    let last_column = game.columns - 1;
+    let next_turn = game.turn_info.turn + 1;
 
-    let mut rand = RandomGenerator::new(slots, turn_data.to_account_info().key());
+    let mut changes: Vec<TileChange> = vec![];
 
     for i in 0..game.map.len() {
         for j in 0..game.map[i].len() {
@@ -69,7 +129,25 @@ pub fn crank(ctx: Context<Crank>) -> ProgramResult {
                 None => {}
                 Some(tile) => {
                     if tile.life - 1 == 0 {
-                        *tile = cycle_tile(Some(*tile), i as u8, &mut rand);
+                        changes.push(TileChange {
+                            turn: next_turn,
+                            level: i as u8,
+                            column: j as u8,
+                            kind: TileChangeKind::Expired,
+                            tile_type: tile.tile_type,
+                            life: 0,
+                        });
+
+                        *tile = cycle_tile(Some(*tile), i as u8, top_level, rules, rand);
+
+                        changes.push(TileChange {
+                            turn: next_turn,
+                            level: i as u8,
+                            column: j as u8,
+                            kind: TileChangeKind::Cycled,
+                            tile_type: tile.tile_type,
+                            life: tile.life,
+                        });
                     } else {
                         tile.life -= 1;
                     }
@@ -83,28 +161,141 @@ pub fn crank(ctx: Context<Crank>) -> ProgramResult {
 
     if (game.turn_info.turn + 1)
         >= (game.turn_info.last_tile_spawn + game.turn_info.tile_spawn_delay)
-        && !(highest_lvl == MAX_LEVEL_0_BASED && highest_col == MAX_COLUMN_0_BASED)
+        && !(highest_lvl == top_level && highest_col == last_column)
     {
         let new_tile: Tile;
+        let (level, column): (u8, u8);
 
-        if highest_col < MAX_COLUMN_0_BASED {
-            new_tile = cycle_tile(None, highest_lvl, &mut rand);
-            game.map[highest_lvl as usize][(highest_col + 1) as usize] = Some(new_tile);
+        if highest_col < last_column {
+            new_tile = cycle_tile(None, highest_lvl, top_level, rules, rand);
+            level = highest_lvl;
+            column = highest_col + 1;
         } else {
-            new_tile = cycle_tile(None, highest_lvl + 1, &mut rand);
-            game.map[(highest_lvl + 1) as usize][0] = Some(new_tile);
+            new_tile = cycle_tile(None, highest_lvl + 1, top_level, rules, rand);
+            level = highest_lvl + 1;
+            column = 0;
         }
 
+        game.map[level as usize][column as usize] = Some(new_tile);
+
+        changes.push(TileChange {
+            turn: next_turn,
+            level,
+            column,
+            kind: TileChangeKind::Spawned,
+            tile_type: new_tile.tile_type,
+            life: new_tile.life,
+        });
+
         game.turn_info.last_tile_spawn = game.turn_info.turn + 1;
     }
 
     game.turn_info.turn += 1;
-    game.turn_info.last_crank_seconds = clock.unix_timestamp;
     game.last_turn_added = game.turn_info.turn;
 
-    emit!(NewTurn {
+    changes
+}
+
+/// Keeps the changes in the game's tile history and emits them
+pub fn record_tile_changes(tile_history: &mut TileHistory, game: Pubkey, bump: u8, turn: u32, changes: Vec<TileChange>) {
+    //Created by the first crank that needs it
+    tile_history.bump = bump;
+    tile_history.game = game;
+
+    for change in changes.iter() {
+        tile_history.record(*change);
+    }
+
+    emit!(TilesChanged {
+        turn,
+        changes
+    });
+}
+
+/// Reward accounts (source then destination) are only read when the game pays a reward,
+/// so games without one are cranked with the same accounts as before there were rewards
+pub fn pay_crank_reward<'info>(
+    reward: &CrankReward,
+    game: &Account<'info, Game>,
+    cranker: Pubkey,
+    game_signer: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    reward_accounts: &[AccountInfo<'info>],
+) -> ProgramResult {
+    let (expected_source, expected_mint, amount) = match *reward {
+        CrankReward::None => {
+            return Ok(());
+        }
+        CrankReward::Lada { amount } => (game.lada_token_account, game.lada_mint_account, amount),
+        CrankReward::Resource { tile_type, amount } => {
+            let mint = match tile_type {
+                TileType::Fire => game.resource_1_mint_account,
+                TileType::Water => game.resource_2_mint_account,
+                TileType::Earth => game.resource_3_mint_account,
+                _ => {
+                    return Err(ErrorCode::InvalidGameRules.into());
+                }
+            };
+
+            (mint, mint, amount)
+        }
+    };
+
+    let (reward_source, reward_destination) = match reward_accounts {
+        [reward_source, reward_destination, ..] => (reward_source.clone(), reward_destination.clone()),
+        _ => {
+            return Err(ErrorCode::InvalidCrankRewardAccounts.into());
+        }
+    };
+
+    let destination: Account<TokenAccount> = Account::try_from(&reward_destination)?;
+
+    if reward_source.key() != expected_source
+        || destination.owner != cranker
+        || destination.mint != expected_mint
+    {
+        return Err(ErrorCode::InvalidCrankRewardAccounts.into());
+    }
+
+    let game_key = game.key();
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    match *reward {
+        CrankReward::Lada { .. } => {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program,
+                    token::Transfer {
+                        from: reward_source,
+                        to: reward_destination,
+                        authority: game_signer,
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+        _ => {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    token_program,
+                    token::MintTo {
+                        mint: reward_source,
+                        to: reward_destination,
+                        authority: game_signer,
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    emit!(CrankRewarded {
         turn: game.turn_info.turn,
-        tile_map: game.map.clone()
+        cranker,
+        reward: *reward,
     });
 
     Ok(())
@@ -0,0 +1,212 @@
+use std::cmp::min;
+
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token::Token;
+
+use crate::account::{Game, GameConfig, TileHistory, TurnData};
+use crate::error::ErrorCode;
+use crate::event::NewTurn;
+use crate::instructions::{advance_turn, pay_crank_reward, record_tile_changes};
+use crate::utils::RandomGenerator;
+
+/// Same accounts as the crank, except the turn data of every turn that gets added
+/// is passed (in order) in the remaining accounts, after the reward accounts if the game pays one,
+/// and created by the instruction
+#[derive(Accounts)]
+pub struct CrankMany<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut)]
+    pub game_account: Box<Account<'info, Game>>,
+
+    #[account(seeds = [b"game_signer", game_account.key().as_ref()], bump = game_account.signer_bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"game_config", game_account.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(address = sysvar::slot_hashes::id())]
+    pub slots: UncheckedAccount<'info>,
+
+    #[account(address = sysvar::instructions::id())]
+    pub instruction_sysvar_account: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"turn_data", game_account.to_account_info().key().as_ref(), game_account.turn_info.turn.to_string().as_ref()], bump = current_game_turn_data.bump)]
+    pub current_game_turn_data: Account<'info, TurnData>,
+
+    #[account(init_if_needed,
+    seeds = [b"tile_history", game_account.key().as_ref()],
+    bump,
+    payer = authority,
+    space = TileHistory::SIZE
+    )]
+    pub tile_history: Box<Account<'info, TileHistory>>,
+}
+
+pub fn crank_many<'info>(
+    ctx: Context<'_, '_, '_, 'info, CrankMany<'info>>,
+    max_turns: u32,
+) -> ProgramResult {
+    let game = &mut ctx.accounts.game_account;
+    let rules = ctx.accounts.game_config.rules;
+
+    let (reward_accounts, turn_data_accounts) = ctx
+        .remaining_accounts
+        .split_at(min(rules.crank_reward.accounts_len(), ctx.remaining_accounts.len()));
+
+    let clock = Clock::get().unwrap();
+
+    //Every full turn delay since the last crank is a turn to add
+    let elapsed_turns = (clock.unix_timestamp - game.turn_info.last_crank_seconds)
+        .checked_div(game.turn_info.turn_delay as i64)
+        .unwrap_or(0);
+
+    let number_of_turns = min(
+        min(elapsed_turns.max(0) as u64, max_turns as u64),
+        turn_data_accounts.len() as u64,
+    ) as u32;
+
+    if number_of_turns == 0 {
+        return Err(ErrorCode::PrematureCrankPull.into());
+    }
+
+    let slots_ref = ctx.accounts.slots.data.borrow();
+    let slots = &**slots_ref;
+
+    //Hash of this slot isn't known until it's over, so whoever cranks can't pick the turn entropy
+    ctx.accounts.current_game_turn_data.map = game.get_map_as_tile_features_only();
+    ctx.accounts.current_game_turn_data.entropy_slot = clock.slot;
+
+    let game_key = game.key();
+    let space = TurnData::size(game.levels, game.columns);
+    let rent = Rent::get()?;
+    let tile_history_bump = *ctx.bumps.get("tile_history").unwrap();
+
+    for turn_data_info in turn_data_accounts.iter().take(number_of_turns as usize) {
+        let next_turn = game.turn_info.turn.checked_add(1).unwrap().to_string();
+
+        let (turn_data_key, turn_data_bump) = Pubkey::find_program_address(
+            &[b"turn_data".as_ref(), game_key.as_ref(), next_turn.as_ref()],
+            ctx.program_id,
+        );
+
+        if turn_data_info.key() != turn_data_key {
+            return Err(ErrorCode::InvalidTurnDataAccount.into());
+        }
+
+        create_turn_data_account(
+            turn_data_info,
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &rent,
+            space,
+            &[b"turn_data".as_ref(), game_key.as_ref(), next_turn.as_ref(), &[turn_data_bump]],
+            ctx.program_id,
+        )?;
+
+        let mut rand = RandomGenerator::new(slots, turn_data_key);
+
+        let changes = advance_turn(game, &rules, &mut rand);
+
+        //Nobody can act during a skipped turn so its map is already final,
+        //the last one is overwritten by the next crank anyways
+        let turn_data = TurnData {
+            bump: turn_data_bump,
+            payer: ctx.accounts.authority.key(),
+            entropy_slot: clock.slot,
+            map: game.get_map_as_tile_features_only(),
+            ..Default::default()
+        };
+
+        let mut data = turn_data_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        turn_data.try_serialize(&mut writer)?;
+
+        emit!(NewTurn {
+            turn: game.turn_info.turn,
+            tile_map: game.map.clone()
+        });
+
+        record_tile_changes(
+            &mut ctx.accounts.tile_history,
+            game_key,
+            tile_history_bump,
+            game.turn_info.turn,
+            changes,
+        );
+
+        //Paid turn by turn so each reward is reported for the turn it was added for
+        pay_crank_reward(
+            &rules.crank_reward,
+            &**game,
+            ctx.accounts.authority.key(),
+            ctx.accounts.game_signer.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            reward_accounts,
+        )?;
+    }
+
+    //Only count the time of the turns added so the rest isn't lost
+    game.turn_info.last_crank_seconds = game
+        .turn_info
+        .last_crank_seconds
+        .checked_add(number_of_turns as i64 * game.turn_info.turn_delay as i64)
+        .unwrap();
+
+    Ok(())
+}
+
+/// Creates the turn data PDA the way anchor's init does, so lamports sent to the address
+/// beforehand can't block its creation
+fn create_turn_data_account<'info>(
+    turn_data_info: &AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: &Rent,
+    space: usize,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let current_lamports = turn_data_info.lamports();
+
+    if current_lamports == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                turn_data_info.key,
+                rent.minimum_balance(space),
+                space as u64,
+                program_id,
+            ),
+            &[payer, turn_data_info.clone(), system_program],
+            &[seeds],
+        );
+    }
+
+    let required_lamports = rent.minimum_balance(space).max(1).saturating_sub(current_lamports);
+
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, turn_data_info.key, required_lamports),
+            &[payer, turn_data_info.clone(), system_program.clone()],
+        )?;
+    }
+
+    invoke_signed(
+        &system_instruction::allocate(turn_data_info.key, space as u64),
+        &[turn_data_info.clone(), system_program.clone()],
+        &[seeds],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(turn_data_info.key, program_id),
+        &[turn_data_info.clone(), system_program],
+        &[seeds],
+    )
+}
@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::account::{Caster, Game, Item, Player};
+use crate::account::{Caster, Game, Player};
 use crate::error::ErrorCode;
-use crate::utils::{EquipmentType, ItemType};
+use crate::utils::{EquipmentType, ItemRecord, ItemType, load_item, load_item_by_key, Modifiers, set_item_equipped_owner};
 
 #[derive(Accounts)]
 pub struct EquipUnequipItem<'info> {
@@ -17,20 +17,23 @@ pub struct EquipUnequipItem<'info> {
     pub player: Account<'info, Player>,
     #[account(
     mut,
-    constraint = item.owner == player.key(),
+    constraint = caster.owner == player.key(),
     )]
     pub caster: Account<'info, Caster>,
-    #[account(
-    mut,
-    constraint = item.game == game.key(),
-    constraint = item.owner == player.key(),
-    )]
-    pub item: Box<Account<'info, Item>>,
+    /// Item account, or the player's inventory if an item id is given
+    #[account(mut)]
+    pub item: UncheckedAccount<'info>,
 }
 
-pub fn equip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
+pub fn equip_item(ctx: Context<EquipUnequipItem>, item_id: Option<u32>) -> ProgramResult {
     let caster = &mut ctx.accounts.caster;
-    let item = &mut ctx.accounts.item;
+    let item_info = ctx.accounts.item.to_account_info();
+    let item = load_item(
+        &item_info,
+        item_id,
+        &ctx.accounts.game.key(),
+        &ctx.accounts.player.key(),
+    )?;
 
     if item.equipped_owner != None {
         return Err(ErrorCode::ItemAlreadyInUse.into());
@@ -44,8 +47,6 @@ pub fn equip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
         return Err(ErrorCode::NoEquipUnequipOnPendingTurn.into());
     }
 
-    item.equipped_owner = Some(caster.key());
-
     match item.item_type {
         ItemType::Equipment {
             feature: _,
@@ -58,21 +59,21 @@ pub fn equip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
                     return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
                 }
 
-                caster.modifiers.head = Some(item.key());
+                caster.modifiers.head = Some(item.key);
             }
             EquipmentType::Staff => {
                 if caster.modifiers.staff != None {
                     return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
                 }
 
-                caster.modifiers.staff = Some(item.key());
+                caster.modifiers.staff = Some(item.key);
             }
             EquipmentType::Robe => {
                 if caster.modifiers.robe != None {
                     return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
                 }
 
-                caster.modifiers.robe = Some(item.key());
+                caster.modifiers.robe = Some(item.key);
             }
         },
         ItemType::SpellBook { .. } => {
@@ -80,19 +81,25 @@ pub fn equip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
                 return Err(ErrorCode::ItemTypeAlreadyEquipped.into());
             }
 
-            caster.modifiers.spell_book = Some(item.key());
+            caster.modifiers.spell_book = Some(item.key);
         }
         _ => {
             return Err(ErrorCode::InvalidEquipItemType.into());
         }
     }
 
-    Ok(())
+    set_item_equipped_owner(&item_info, item_id, Some(caster.key()), ctx.program_id)
 }
 
-pub fn unequip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
+pub fn unequip_item(ctx: Context<EquipUnequipItem>, item_id: Option<u32>) -> ProgramResult {
     let caster = &mut ctx.accounts.caster;
-    let item = &mut ctx.accounts.item;
+    let item_info = ctx.accounts.item.to_account_info();
+    let item = load_item(
+        &item_info,
+        item_id,
+        &ctx.accounts.game.key(),
+        &ctx.accounts.player.key(),
+    )?;
 
     if item.equipped_owner == None || item.equipped_owner.unwrap() != caster.key() {
         return Err(ErrorCode::ItemNotExists.into());
@@ -122,7 +129,151 @@ pub fn unequip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
         }
     }
 
-    item.equipped_owner = None;
+    set_item_equipped_owner(&item_info, item_id, None, ctx.program_id)
+}
+
+#[derive(Accounts)]
+pub struct SetLoadout<'info> {
+    pub game: Box<Account<'info, Game>>,
+    pub authority: Signer<'info>,
+    #[account(
+    mut,
+    constraint = player.game == game.key(),
+    has_one = authority,
+    has_one = game
+    )]
+    pub player: Account<'info, Player>,
+    #[account(
+    mut,
+    constraint = caster.owner == player.key(),
+    )]
+    pub caster: Account<'info, Caster>,
+
+    // Item accounts, or the player's inventory if an item id is given, any account for an empty slot
+    #[account(mut)]
+    pub head: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub robe: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub staff: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub spell_book: UncheckedAccount<'info>,
+    // Remaining accounts: the items (or the inventory) currently equipped that are taken off
+}
+
+/// Item wanted in a caster slot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct LoadoutItem {
+    /// Set if the item is in the inventory
+    pub item_id: Option<u32>,
+}
+
+/// Items the caster ends up with, a slot left to None is emptied
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct Loadout {
+    pub head: Option<LoadoutItem>,
+    pub robe: Option<LoadoutItem>,
+    pub staff: Option<LoadoutItem>,
+    pub spell_book: Option<LoadoutItem>,
+}
+
+/// Caster slot of that equipment type, no equipment type being the spell book slot
+fn get_slot_mut(modifiers: &mut Modifiers, slot: Option<EquipmentType>) -> &mut Option<Pubkey> {
+    match slot {
+        Some(EquipmentType::Head) => &mut modifiers.head,
+        Some(EquipmentType::Robe) => &mut modifiers.robe,
+        Some(EquipmentType::Staff) => &mut modifiers.staff,
+        None => &mut modifiers.spell_book,
+    }
+}
+
+fn is_item_for_slot(item_type: ItemType, slot: Option<EquipmentType>) -> bool {
+    match item_type {
+        ItemType::Equipment { equipment_type, .. } => slot == Some(equipment_type),
+        ItemType::SpellBook { .. } => slot == None,
+        _ => false,
+    }
+}
+
+pub fn set_loadout<'info>(
+    ctx: Context<'_, '_, '_, 'info, SetLoadout<'info>>,
+    loadout: Loadout,
+) -> ProgramResult {
+    let caster = &mut ctx.accounts.caster;
+    let caster_key = caster.key();
+    let game = ctx.accounts.game.key();
+    let player = ctx.accounts.player.key();
+
+    if caster.turn_commit != None {
+        return Err(ErrorCode::NoEquipUnequipOnPendingTurn.into());
+    }
+
+    let slots = [
+        (Some(EquipmentType::Head), loadout.head, ctx.accounts.head.to_account_info()),
+        (Some(EquipmentType::Robe), loadout.robe, ctx.accounts.robe.to_account_info()),
+        (Some(EquipmentType::Staff), loadout.staff, ctx.accounts.staff.to_account_info()),
+        (None, loadout.spell_book, ctx.accounts.spell_book.to_account_info()),
+    ];
+
+    //Everything is validated before any item changes hands
+    let mut changes: Vec<(Option<EquipmentType>, Option<ItemRecord>, &AccountInfo<'info>)> = vec![];
+
+    for (slot, loadout_item, item_info) in slots.iter() {
+        let equipped_key = *get_slot_mut(&mut caster.modifiers, *slot);
+
+        let item = match loadout_item {
+            None => None,
+            Some(loadout_item) => {
+                let item = load_item(item_info, loadout_item.item_id, &game, &player)?;
+
+                if !is_item_for_slot(item.item_type, *slot) {
+                    return Err(ErrorCode::InvalidEquipItemType.into());
+                }
+
+                if item.level > caster.level {
+                    return Err(ErrorCode::ItemLevelTooHigh.into());
+                }
+
+                //Only an unequipped item, or the one already in that slot
+                if item.equipped_owner != None
+                    && (item.equipped_owner != Some(caster_key) || equipped_key != Some(item.key))
+                {
+                    return Err(ErrorCode::ItemAlreadyInUse.into());
+                }
+
+                Some(item)
+            }
+        };
+
+        if item.map(|item| item.key) != equipped_key {
+            changes.push((*slot, item, item_info));
+        }
+    }
+
+    for (slot, item, item_info) in changes {
+        let equipped_key = get_slot_mut(&mut caster.modifiers, slot);
+
+        if let Some(key) = *equipped_key {
+            let (equipped_info, equipped_item) = ctx
+                .remaining_accounts
+                .iter()
+                .chain(slots.iter().map(|(_, _, info)| info))
+                .find_map(|info| {
+                    load_item_by_key(info, key, &game, &player)
+                        .ok()
+                        .map(|equipped_item| (info, equipped_item))
+                })
+                .ok_or(ErrorCode::EquippedItemAccountMismatch)?;
+
+            set_item_equipped_owner(equipped_info, equipped_item.id, None, ctx.program_id)?;
+        }
+
+        if let Some(item) = item {
+            set_item_equipped_owner(item_info, item.id, Some(caster_key), ctx.program_id)?;
+        }
+
+        *equipped_key = item.map(|item| item.key);
+    }
 
     Ok(())
 }
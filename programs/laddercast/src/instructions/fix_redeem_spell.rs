@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
 
-use crate::account::{Caster, Game, Item, Player};
+use crate::account::{Caster, Game, Item, Player, ProgramAdmin};
 use crate::error::ErrorCode;
-use crate::utils::GAME_CREATOR_AUTHORITY_PUBKEY;
 use crate::utils::{EquipmentType, ItemType};
 
 #[derive(Accounts)]
 pub struct FixRedeemSpell<'info> {
   pub authority: Signer<'info>,
+  #[account(seeds = [b"program_admin"], bump = program_admin.bump)]
+  pub program_admin: Account<'info, ProgramAdmin>,
   #[account(mut)]
   pub caster: Account<'info, Caster>,
 }
@@ -15,7 +16,7 @@ pub struct FixRedeemSpell<'info> {
 pub fn fix_redeem_spell(ctx: Context<FixRedeemSpell>) -> ProgramResult {
   let caster = &mut ctx.accounts.caster;
 
-  if ctx.accounts.authority.key().to_string() != GAME_CREATOR_AUTHORITY_PUBKEY {
+  if ctx.accounts.authority.key() != ctx.accounts.program_admin.admin {
     return Err(ErrorCode::NotSuperAdmin.into());
   }
 
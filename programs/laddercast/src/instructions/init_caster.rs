@@ -2,9 +2,9 @@ use anchor_lang::{prelude::*, solana_program::sysvar};
 use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::account::{Caster, Game, Player};
+use crate::account::{Caster, Game, GameConfig, Player};
 use crate::error::ErrorCode;
-use crate::utils::{COST_IN_LADA_FOR_CASTER, DECIMALS_PRECISION, Modifiers, RandomGenerator};
+use crate::utils::{DECIMALS_PRECISION, Modifiers, RandomGenerator};
 
 #[derive(Accounts)]
 pub struct InitCaster<'info> {
@@ -17,6 +17,9 @@ pub struct InitCaster<'info> {
 
     pub game: Box<Account<'info, Game>>,
 
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Account<'info, Player>,
 
@@ -54,7 +57,7 @@ pub fn init_caster(ctx: Context<InitCaster>) -> ProgramResult {
     caster.turn_commit = None;
     caster.modifiers = Modifiers {
         tile_level: 0,
-        tile_column: rand.random_within_range::<u8, 1>(0, 2),
+        tile_column: rand.random_within_range::<u8, 1>(0, ctx.accounts.game.columns - 1),
         head: None,
         robe: None,
         staff: None,
@@ -62,7 +65,7 @@ pub fn init_caster(ctx: Context<InitCaster>) -> ProgramResult {
     };
 
     //Decimal precision is 9
-    let amount: u64 = u64::from(COST_IN_LADA_FOR_CASTER)
+    let amount: u64 = u64::from(ctx.accounts.game_config.rules.cost_in_lada_for_caster)
         .checked_mul(DECIMALS_PRECISION)
         .unwrap();
 
@@ -10,7 +10,7 @@ use crate::utils::constants::*;
 use crate::utils::{cycle_tile, RandomGenerator};
 
 #[derive(Accounts)]
-#[instruction(turn_info: GameTurnInfo)]
+#[instruction(turn_info: GameTurnInfo, rules: GameRules, levels: u8, columns: u8)]
 pub struct InitGame<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -19,23 +19,34 @@ pub struct InitGame<'info> {
     pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
 
+    #[account(seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Box<Account<'info, ProgramAdmin>>,
+
     #[account(init,
     payer = authority,
-    space = Game::SIZE
+    space = Game::size(levels, columns)
     )]
     pub game_account: Box<Account<'info, Game>>,
 
-    #[account(mut, seeds = [b"game_signer"], bump)]
+    #[account(mut, seeds = [b"game_signer", game_account.key().as_ref()], bump)]
     pub game_signer: UncheckedAccount<'info>,
 
     #[account(init,
     seeds = [b"turn_data", game_account.key().as_ref(), turn_info.turn.to_string().as_ref()],
     bump,
     payer = authority,
-    space = TurnData::SIZE
+    space = TurnData::size(levels, columns)
     )]
     pub game_turn_data: Box<Account<'info, TurnData>>,
 
+    #[account(init,
+    seeds = [b"game_config", game_account.key().as_ref()],
+    bump,
+    payer = authority,
+    space = GameConfig::SIZE
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     #[account(address = sysvar::slot_hashes::id())]
     pub slots: UncheckedAccount<'info>,
 
@@ -56,6 +67,7 @@ pub struct InitGame<'info> {
     pub resource_3_mint: Account<'info, Mint>,
 
     pub lada_mint: Account<'info, Mint>,
+    #[account(constraint = lada_token_account.mint == lada_mint.key(), constraint = lada_token_account.owner == game_signer.key())]
     pub lada_token_account: Account<'info, TokenAccount>,
 }
 
@@ -77,6 +89,213 @@ impl GameTurnInfo {
     pub const SIZE: usize = 8 + 4 + 2 + 8 + 4 + 4;
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct GameRules {
+    /// resources burned per level (1 based) of the destination tile when moving
+    pub move_cost_multiplier: u8,
+    /// resources burned of each type per level (1 based) when crafting
+    pub crafting_cost_multiplier: u8,
+    /// resource tile distribution per level band, ordered by max_level
+    pub tile_bands: [TileBand; 3],
+    /// default chance of a critical loot, 200 = 2%
+    pub default_critical_chance_in_percent: u16,
+    /// default chance of finding a chest while looting, 1000 = 10%
+    pub default_magic_find_in_percent: u16,
+    /// LADA given each turn to the casters (number * 10 ^ 9)
+    pub lada_distribution_per_turn: u64,
+    /// LADA burned to create a caster (without the decimals)
+    pub cost_in_lada_for_caster: u16,
+    /// paid to whoever successfully pulls the crank
+    pub crank_reward: CrankReward,
+    /// number of turns a turn data is kept after its turn before it can be closed
+    pub turn_data_retention: u32,
+    /// what a chest gives when opened, per tier (tier 1 first)
+    pub chest_tiers: [ChestTier; 4],
+    /// resources burned per level skipped or column crossed when teleporting, on top of the move cost
+    pub teleport_cost_multiplier: u8,
+}
+
+impl GameRules {
+    pub const SIZE: usize = 1 + 1 + 3 * TileBand::SIZE + 2 + 2 + 8 + 2 + CrankReward::SIZE + 4 + 4 * ChestTier::SIZE + 1;
+
+    pub fn validate(&self) -> ProgramResult {
+        let mut previous_max_level: Option<u8> = None;
+
+        for band in self.tile_bands.iter() {
+            //A tile with no life would underflow when the crank decrements it
+            if band.min_life == 0 || band.min_life > band.max_life || band.max_life == u8::MAX {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+
+            //At least one resource has to be able to spawn
+            if band.weights.iter().all(|weight| *weight == 0) {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+
+            if previous_max_level.map_or(false, |previous| band.max_level <= previous) {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+
+            previous_max_level = Some(band.max_level);
+        }
+
+        //Every level of the biggest map needs a band
+        if previous_max_level.map_or(true, |max_level| max_level < MAX_MAP_LEVELS - 1) {
+            return Err(ErrorCode::InvalidGameRules.into());
+        }
+
+        //Casters redeem a turn during the next one at the earliest
+        if self.turn_data_retention == 0 {
+            return Err(ErrorCode::InvalidGameRules.into());
+        }
+
+        //Teleporting is a paid action
+        if self.teleport_cost_multiplier == 0 {
+            return Err(ErrorCode::InvalidGameRules.into());
+        }
+
+        //Only resource tiles have a mint
+        if let CrankReward::Resource { tile_type, .. } = self.crank_reward {
+            if !matches!(tile_type, TileType::Fire | TileType::Water | TileType::Earth) {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+        }
+
+        for chest_tier in self.chest_tiers.iter() {
+            if chest_tier.min_item_level == 0 || chest_tier.min_item_level > MAX_LEVEL_1_BASED {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+
+            if chest_tier.item_count == 0 || chest_tier.item_count as usize > MAX_ITEMS_PER_CHEST {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+
+            if chest_tier.spell_book_chance > 100 {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+
+            //At least one rarity has to be possible
+            if chest_tier.rarity_weights.iter().all(|weight| *weight == 0) {
+                return Err(ErrorCode::InvalidGameRules.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            move_cost_multiplier: MOVE_COST_MULTIPLIER,
+            crafting_cost_multiplier: CRAFTING_COST_MULTIPLIER,
+            tile_bands: [
+                TileBand { max_level: 9, ..Default::default() },
+                TileBand { max_level: 19, ..Default::default() },
+                TileBand { max_level: MAX_MAP_LEVELS - 1, ..Default::default() },
+            ],
+            default_critical_chance_in_percent: DEFAULT_CRITICAL_CHANCE_IN_PERCENT,
+            default_magic_find_in_percent: DEFAULT_MAGIC_FIND_IN_PERCENT,
+            lada_distribution_per_turn: LADA_DISTRIBUTION_PER_TURN,
+            cost_in_lada_for_caster: COST_IN_LADA_FOR_CASTER,
+            crank_reward: CrankReward::None,
+            turn_data_retention: TURN_DATA_RETENTION,
+            chest_tiers: [
+                ChestTier {
+                    min_item_level: 1,
+                    item_count: 3,
+                    spell_book_chance: 25,
+                    rarity_weights: [80, 15, 4, 1],
+                },
+                ChestTier {
+                    min_item_level: 6,
+                    item_count: 3,
+                    spell_book_chance: 25,
+                    rarity_weights: [70, 20, 8, 2],
+                },
+                ChestTier {
+                    min_item_level: 11,
+                    item_count: 3,
+                    spell_book_chance: 25,
+                    rarity_weights: [60, 25, 12, 3],
+                },
+                //Every tier gives the 3 items open_chest has accounts for, a game can opt in to more
+                //(clients then pass the accounts of the extra items in the remaining accounts)
+                ChestTier {
+                    min_item_level: 16,
+                    item_count: 3,
+                    spell_book_chance: 25,
+                    rarity_weights: [50, 30, 15, 5],
+                },
+            ],
+            teleport_cost_multiplier: TELEPORT_COST_MULTIPLIER,
+        }
+    }
+}
+
+/// How resource tiles spawn on the levels (0 based) up to max_level included
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct TileBand {
+    pub max_level: u8,
+    /// relative chance of each resource, in the order Earth, Water, Fire
+    pub weights: [u16; 3],
+    pub min_life: u8,
+    pub max_life: u8,
+}
+
+impl TileBand {
+    pub const SIZE: usize = 1 + 3 * 2 + 1 + 1;
+}
+
+impl Default for TileBand {
+    fn default() -> Self {
+        Self {
+            max_level: MAX_MAP_LEVELS - 1,
+            weights: [1, 1, 1],
+            min_life: MINIMUM_TILE_LIFE,
+            max_life: MINIMUM_TILE_LIFE + 3,
+        }
+    }
+}
+
+/// What a chest of a tier gives when opened
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct ChestTier {
+    /// lowest level of the items, the chest level is the highest
+    pub min_item_level: u8,
+    /// number of items given, up to MAX_ITEMS_PER_CHEST
+    pub item_count: u8,
+    /// chance (0-100) of an item being a spell book instead of an equipment
+    pub spell_book_chance: u8,
+    /// relative chance of each rarity, in the order Common, Rare, Epic, Legendary
+    pub rarity_weights: [u16; 4],
+}
+
+impl ChestTier {
+    pub const SIZE: usize = 1 + 1 + 1 + 4 * 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub enum CrankReward {
+    None,
+    /// LADA (number * 10 ^ 9) transferred from the game LADA account
+    Lada { amount: u64 },
+    /// Resource of that tile type minted by the game signer
+    Resource { tile_type: TileType, amount: u64 },
+}
+
+impl CrankReward {
+    pub const SIZE: usize = 1 + size_of::<TileType>() + 8;
+
+    /// Accounts a crank takes first in its remaining accounts to pay the reward (source, then destination)
+    pub fn accounts_len(&self) -> usize {
+        match self {
+            CrankReward::None => 0,
+            _ => 2,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
 pub struct Tile {
     pub tile_type: TileType,
@@ -100,14 +319,27 @@ pub enum TileType {
     Legendary,
 }
 
-pub fn init_game(ctx: Context<InitGame>, turn_info: GameTurnInfo) -> ProgramResult {
+pub fn init_game(
+    ctx: Context<InitGame>,
+    turn_info: GameTurnInfo,
+    rules: GameRules,
+    levels: u8,
+    columns: u8,
+) -> ProgramResult {
     let game = &mut ctx.accounts.game_account;
     let turn_data = &mut ctx.accounts.game_turn_data;
+    let game_config = &mut ctx.accounts.game_config;
+
+    if ctx.accounts.authority.key() != ctx.accounts.program_admin.admin {
+        return Err(ErrorCode::NotSuperAdmin.into());
+    }
+
+    rules.validate()?;
+
+    if levels == 0 || levels > MAX_MAP_LEVELS || columns == 0 || columns > MAX_MAP_COLUMNS {
+        return Err(ErrorCode::InvalidMapDimensions.into());
+    }
 
-    // if ctx.accounts.authority.key().to_string() != GAME_CREATOR_AUTHORITY_PUBKEY {
-    //     return Err(ErrorCode::NotSuperAdmin.into());
-    // }
-    //
     // if ctx.accounts.lada_mint.key().to_string() != LADA_MINT_PUBKEY {
     //     return Err(ErrorCode::InvalidLadaMint.into());
     // }
@@ -119,22 +351,27 @@ pub fn init_game(ctx: Context<InitGame>, turn_info: GameTurnInfo) -> ProgramResu
     game.authority = ctx.accounts.authority.key();
     game.turn_info = turn_info;
     game.last_turn_added = 1;
+    game.levels = levels;
+    game.columns = columns;
+    game.map = vec![vec![None; columns as usize]; levels as usize];
 
     game.signer_bump = *ctx.bumps.get("game_signer").unwrap();
     turn_data.bump = *ctx.bumps.get("game_turn_data").unwrap();
+    turn_data.payer = ctx.accounts.authority.key();
+
+    game_config.bump = *ctx.bumps.get("game_config").unwrap();
+    game_config.game = game.key();
+    game_config.rules = rules;
 
     let slots_ref = ctx.accounts.slots.data.borrow();
     let slots = &**slots_ref;
 
     let mut rand = RandomGenerator::new(slots, game.to_account_info().key());
 
-    let t1 = cycle_tile(None, 1, &mut rand);
-    let t2 = cycle_tile(None, 1, &mut rand);
-    let t3 = cycle_tile(None, 1, &mut rand);
-
-    game.map[0][0] = Some(t1);
-    game.map[0][1] = Some(t2);
-    game.map[0][2] = Some(t3);
+    //First level starts full
+    for column in 0..columns as usize {
+        game.map[0][column] = Some(cycle_tile(None, 0, levels - 1, &rules, &mut rand));
+    }
 
     game.resource_1_mint_account = ctx.accounts.resource_1_mint.to_account_info().key();
     game.resource_2_mint_account = ctx.accounts.resource_2_mint.to_account_info().key();
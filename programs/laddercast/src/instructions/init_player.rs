@@ -32,6 +32,8 @@ pub struct PlayerBonuses {
     pub water_chance: u16,
     //Is a value, not a %
     pub earth_chance: u16,
+    //Same scale as critical_chance, extra chance of a crafted item going up a level or rarity
+    pub craft_upgrade_chance: u16,
 }
 
 pub fn init_player(ctx: Context<InitPlayer>) -> ProgramResult {
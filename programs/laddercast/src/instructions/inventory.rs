@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::account::{Game, Inventory, Item, Player};
+use crate::error::ErrorCode;
+use crate::utils::{grow_inventory_if_full, INVENTORY_GROWTH};
+
+#[derive(Accounts)]
+pub struct InitInventory<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(init,
+    seeds = [b"inventory", player.key().as_ref()],
+    bump,
+    payer = authority,
+    space = Inventory::space(INVENTORY_GROWTH)
+    )]
+    pub inventory: Box<Account<'info, Inventory>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateItemToInventory<'info> {
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(mut,
+    seeds = [b"inventory", player.key().as_ref()],
+    bump = inventory.bump,
+    constraint = inventory.player == player.key()
+    )]
+    pub inventory: Box<Account<'info, Inventory>>,
+
+    #[account(mut,
+    close = authority,
+    constraint = item.game == game.key(),
+    constraint = item.owner == player.key()
+    )]
+    pub item: Box<Account<'info, Item>>,
+}
+
+pub fn init_inventory(ctx: Context<InitInventory>) -> ProgramResult {
+    let inventory = &mut ctx.accounts.inventory;
+
+    inventory.bump = *ctx.bumps.get("inventory").unwrap();
+    inventory.player = ctx.accounts.player.key();
+    inventory.next_id = 0;
+
+    Ok(())
+}
+
+/// Moves an item account into the inventory, the rent of the item account goes back to the player
+pub fn migrate_item_to_inventory(ctx: Context<MigrateItemToInventory>) -> ProgramResult {
+    let item = &ctx.accounts.item;
+
+    //Caster modifiers or a reforge commit hold the item account key, unequip or redeem first
+    if item.equipped_owner != None {
+        return Err(ErrorCode::ItemAlreadyInUse.into());
+    }
+
+    ctx.accounts.inventory.add_item(item.level, item.item_type)?;
+
+    grow_inventory_if_full(
+        &ctx.accounts.inventory,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )
+}
@@ -5,7 +5,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{EARTH_INDEX, FIRE_INDEX, give_exp_to_caster_resources_burned, ItemFeature, TurnCommit, WATER_INDEX};
+use crate::utils::{EARTH_INDEX, FIRE_INDEX, get_turn_commit_for_new_action, give_exp_to_caster_resources_burned, ItemFeature, TurnCommit, WATER_INDEX};
 
 #[derive(Accounts)]
 pub struct ManualResourceBurn<'info> {
@@ -18,6 +18,8 @@ pub struct ManualResourceBurn<'info> {
     pub authority: Signer<'info>,
 
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
 
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Box<Account<'info, Player>>,
@@ -60,21 +62,16 @@ pub fn manual_resource_burn(
     let turn_data = &mut ctx.accounts.game_turn_data;
     let caster = &mut ctx.accounts.caster;
     let game_turn = ctx.accounts.game.turn_info.turn;
+    let turn_data_retention = ctx.accounts.game_config.rules.turn_data_retention;
 
     let token_account_to_burn_from: &Account<TokenAccount>;
     let mint_account_to_burn_from: &Account<Mint>;
 
-    let mut caster_turn_commit: TurnCommit = match caster.turn_commit.clone() {
-        Some(turn_commit) => turn_commit,
-        None => TurnCommit {
-            turn: game_turn,
-            ..Default::default()
-        },
-    };
-
-    if caster_turn_commit.turn != game_turn {
-        return Err(ErrorCode::PendingTurn.into());
-    }
+    let mut caster_turn_commit: TurnCommit = get_turn_commit_for_new_action(
+        caster.turn_commit,
+        game_turn,
+        turn_data_retention,
+    )?;
 
     match resource_type {
         ItemFeature::Fire => {
@@ -0,0 +1,157 @@
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token;
+use anchor_spl::token::{Mint, SetAuthority, Token, TokenAccount, Transfer};
+use spl_token::instruction::AuthorityType;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::GameRules;
+use crate::utils::{read_legacy_game, write_migrated_account};
+
+/// Moves a game created with the old shared signer (seeds ["game_signer"]) to its own
+/// signer (seeds ["game_signer", game.key]), mint authorities and LADA vault included.
+/// Those games predate per-game map dimensions and rules, so the game account is rewritten
+/// in the current layout and gets a game config with the default rules.
+/// The turn data of the current turn (and older ones still to be redeemed) go through migrate_turn_data after.
+#[derive(Accounts)]
+pub struct MigrateGameSigner<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Game in the legacy layout, read and rewritten by the instruction
+    #[account(mut)]
+    pub game: UncheckedAccount<'info>,
+
+    #[account(init,
+    seeds = [b"game_config", game.key().as_ref()],
+    bump,
+    payer = authority,
+    space = GameConfig::SIZE
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(seeds = [b"game_signer"], bump)]
+    pub legacy_game_signer: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"game_signer", game.key().as_ref()], bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    //Checked against the legacy game in the instruction
+    #[account(mut)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    pub lada_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub lada_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+    token::mint = lada_mint,
+    token::authority = game_signer,
+    payer = authority)]
+    pub new_lada_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+pub fn migrate_game_signer(ctx: Context<MigrateGameSigner>) -> ProgramResult {
+    let game_info = ctx.accounts.game.to_account_info();
+    let legacy_game = read_legacy_game(&game_info, ctx.program_id)?;
+
+    if legacy_game.authority != ctx.accounts.authority.key() {
+        return Err(ErrorCode::NotGameAuthority.into());
+    }
+
+    if ctx.accounts.resource_1_mint_account.key() != legacy_game.resource_1_mint_account
+        || ctx.accounts.resource_2_mint_account.key() != legacy_game.resource_2_mint_account
+        || ctx.accounts.resource_3_mint_account.key() != legacy_game.resource_3_mint_account
+    {
+        return Err(ErrorCode::InvalidGame.into());
+    }
+
+    if ctx.accounts.lada_mint.key() != legacy_game.lada_mint_account {
+        return Err(ErrorCode::InvalidLadaMint.into());
+    }
+
+    if ctx.accounts.lada_token_account.key() != legacy_game.lada_token_account {
+        return Err(ErrorCode::InvalidLadaTokenGameAccount.into());
+    }
+
+    let legacy_game_signer = ctx.accounts.legacy_game_signer.key();
+
+    //Only games still using the shared signer can be migrated
+    if ctx.accounts.resource_1_mint_account.mint_authority != COption::Some(legacy_game_signer)
+        || ctx.accounts.lada_token_account.owner != legacy_game_signer
+    {
+        return Err(ErrorCode::GameSignerAlreadyMigrated.into());
+    }
+
+    let seeds = &[
+        b"game_signer".as_ref(),
+        &[*ctx.bumps.get("legacy_game_signer").unwrap()],
+    ];
+    let signer = &[&seeds[..]];
+
+    for mint in [
+        ctx.accounts.resource_1_mint_account.to_account_info(),
+        ctx.accounts.resource_2_mint_account.to_account_info(),
+        ctx.accounts.resource_3_mint_account.to_account_info(),
+    ]
+        .iter()
+    {
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.legacy_game_signer.to_account_info(),
+                    account_or_mint: mint.clone(),
+                },
+                signer,
+            ),
+            AuthorityType::MintTokens,
+            Some(ctx.accounts.game_signer.key()),
+        )?;
+    }
+
+    //Move the LADA still to be distributed to a vault owned by the new signer
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lada_token_account.to_account_info(),
+                to: ctx.accounts.new_lada_token_account.to_account_info(),
+                authority: ctx.accounts.legacy_game_signer.to_account_info(),
+            },
+            signer,
+        ),
+        ctx.accounts.lada_token_account.amount,
+    )?;
+
+    let mut game = legacy_game.into_game();
+
+    game.signer_bump = *ctx.bumps.get("game_signer").unwrap();
+    game.lada_token_account = ctx.accounts.new_lada_token_account.key();
+
+    write_migrated_account(
+        &game_info,
+        &game,
+        Game::size(game.levels, game.columns),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    //Legacy games ran on the constants the default rules are made of
+    let game_config = &mut ctx.accounts.game_config;
+
+    game_config.bump = *ctx.bumps.get("game_config").unwrap();
+    game_config.game = game_info.key();
+    game_config.rules = GameRules::default();
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{LEGACY_MAP_COLUMNS, LEGACY_MAP_LEVELS, read_legacy_turn_data, write_migrated_account};
+
+/// Rewrites a turn data created before per-game map dimensions in the current layout,
+/// the game has to be migrated first (done by migrate_game_signer).
+/// Legacy turn data didn't keep who cranked them, so only the program admin migrates them
+/// (and gets the rent back on close), nobody else can claim it
+#[derive(Accounts)]
+#[instruction(turn: u32)]
+pub struct MigrateTurnData<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Account<'info, ProgramAdmin>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut, seeds = [b"turn_data", game.key().as_ref(), turn.to_string().as_ref()], bump)]
+    pub turn_data: UncheckedAccount<'info>,
+}
+
+pub fn migrate_turn_data(ctx: Context<MigrateTurnData>, _turn: u32) -> ProgramResult {
+    if ctx.accounts.payer.key() != ctx.accounts.program_admin.admin {
+        return Err(ErrorCode::NotSuperAdmin.into());
+    }
+
+    let game = &ctx.accounts.game;
+
+    //Only games created before per-game dimensions have legacy turn data, all of them 30 x 3
+    if game.levels != LEGACY_MAP_LEVELS || game.columns != LEGACY_MAP_COLUMNS {
+        return Err(ErrorCode::InvalidLegacyAccount.into());
+    }
+
+    let turn_data_info = ctx.accounts.turn_data.to_account_info();
+    let legacy_turn_data = read_legacy_turn_data(&turn_data_info, ctx.program_id)?;
+
+    write_migrated_account(
+        &turn_data_info,
+        &legacy_turn_data.into_turn_data(ctx.accounts.payer.key()),
+        TurnData::size(game.levels, game.columns),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )
+}
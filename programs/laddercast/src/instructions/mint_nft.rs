@@ -10,7 +10,7 @@ use spl_token::instruction::AuthorityType;
 
 use crate::account::{Caster, Game, Item, MerkleRootNFT, MetadataCaster, MetadataItem, MetadataNFTCaster, MetadataNFTItem, Player};
 use crate::error::ErrorCode;
-use crate::utils::{EXPERIENCE_REQUIRED_PER_LEVEL, get_merkle_string_for_caster, get_merkle_string_for_item, get_name_for_mint, ItemType, MetaplexTokenMetadata, NFT_CASTER_NAME, NFT_CREATOR_SPLITTER_PUBKEY, NFT_MINT_DESCRIPTION, verify_merkle_proof};
+use crate::utils::{EXPERIENCE_REQUIRED_PER_LEVEL, get_merkle_string_for_caster, get_merkle_string_for_item, get_name_for_mint, ItemType, load_item, MetaplexTokenMetadata, NFT_CASTER_NAME, NFT_CREATOR_SPLITTER_PUBKEY, NFT_MINT_DESCRIPTION, remove_item, verify_merkle_proof};
 
 #[derive(Accounts)]
 #[instruction(item_type_str: String, item_level: u8)]
@@ -24,18 +24,14 @@ pub struct MintItem<'info> {
     pub authority: Signer<'info>,
     pub game: Box<Account<'info, Game>>,
 
-    #[account(mut, seeds = [b"game_signer"], bump)]
+    #[account(mut, seeds = [b"game_signer", game.key().as_ref()], bump = game.signer_bump)]
     pub game_signer: UncheckedAccount<'info>,
 
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Account<'info, Player>,
-    #[account(mut,
-    close = authority,
-    constraint = item.game == game.key(),
-    constraint = item.owner == player.key(),
-    constraint = item.equipped_owner == None
-    )]
-    pub item: Box<Account<'info, Item>>,
+    /// Item account, or the player's inventory if an item id is given
+    #[account(mut)]
+    pub item: UncheckedAccount<'info>,
 
     #[account(mut,
     seeds = [b"merkle_roots", game.key().as_ref(), item_type_str.as_bytes(), item_level.to_string().as_ref()],
@@ -72,8 +68,23 @@ pub fn mint_item(
     merkle_proof: Vec<[u8; 32]>,
     item_type_str: String,
     item_level: u8,
+    item_id: Option<u32>,
 ) -> ProgramResult {
-    let item = **ctx.accounts.item.clone();
+    let item_info = ctx.accounts.item.to_account_info();
+    let item_record = load_item(
+        &item_info,
+        item_id,
+        &ctx.accounts.game.key(),
+        &ctx.accounts.player.key(),
+    )?;
+
+    let item = Item {
+        game: ctx.accounts.game.key(),
+        owner: ctx.accounts.player.key(),
+        level: item_record.level,
+        item_type: item_record.item_type,
+        equipped_owner: item_record.equipped_owner,
+    };
 
     if item.equipped_owner != None {
         return Err(ErrorCode::ItemCantBeMintIfEquipped.into());
@@ -128,13 +139,15 @@ pub fn mint_item(
         ctx.accounts.rent.to_account_info(),
     ];
 
-    let name = get_name_for_mint(&ctx.accounts.item.item_type);
+    let name = get_name_for_mint(&item.item_type);
 
     if name == None {
         return Err(ErrorCode::InvalidItemType.into());
     }
 
-    let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+    let game_key = ctx.accounts.game.key();
+
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[ctx.accounts.game.signer_bump]];
 
     let signer = &[&seeds[..]];
 
@@ -196,7 +209,13 @@ pub fn mint_item(
         None,
     )?;
 
-    Ok(())
+    //The NFT holds the item now
+    remove_item(
+        &item_info,
+        item_id,
+        ctx.accounts.authority.to_account_info(),
+        ctx.program_id,
+    )
 }
 
 #[derive(Accounts)]
@@ -211,7 +230,7 @@ pub struct MintCaster<'info> {
     pub authority: Signer<'info>,
     pub game: Box<Account<'info, Game>>,
 
-    #[account(mut, seeds = [b"game_signer"], bump)]
+    #[account(mut, seeds = [b"game_signer", game.key().as_ref()], bump = game.signer_bump)]
     pub game_signer: UncheckedAccount<'info>,
 
     #[account(mut,
@@ -326,7 +345,9 @@ pub fn mint_caster(
         ctx.accounts.rent.to_account_info(),
     ];
 
-    let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+    let game_key = ctx.accounts.game.key();
+
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[ctx.accounts.game.signer_bump]];
 
     let signer = &[&seeds[..]];
 
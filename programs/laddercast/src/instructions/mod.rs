@@ -1,5 +1,6 @@
 pub use burn_lada::*;
 pub use burn_nft::*;
+pub use caster_cancel_turn::*;
 pub use caster_commit_craft::*;
 pub use caster_commit_loot::*;
 pub use caster_commit_move::*;
@@ -7,23 +8,37 @@ pub use caster_commit_spell::*;
 pub use caster_redeem_craft::*;
 pub use caster_redeem_loot::*;
 pub use caster_redeem_move::*;
+pub use caster_redeem_reforge::*;
 pub use caster_redeem_rewards::*;
 pub use caster_redeem_spell::*;
+pub use caster_redeem_turn::*;
 pub use close_game::*;
+pub use close_turn_data::*;
 pub use crank::*;
+pub use crank_many::*;
 pub use equipment::*;
 pub use fix_redeem_spell::*;
 pub use init_caster::*;
 pub use init_game::*;
 pub use init_player::*;
+pub use inventory::*;
 pub use manual_resource_burn::*;
+pub use migrate_game_signer::*;
+pub use migrate_turn_data::*;
 pub use mint_nft::*;
 pub use open_chest::*;
+pub use program_admin::*;
+pub use record_turn_entropy::*;
+pub use reforge_item::*;
+pub use salvage_item::*;
+#[cfg(feature = "debug")]
 pub use test_helper::*;
+pub use update_game_config::*;
 pub use update_merkle_root::*;
 
 pub mod burn_lada;
 pub mod burn_nft;
+pub mod caster_cancel_turn;
 pub mod caster_commit_craft;
 pub mod caster_commit_loot;
 pub mod caster_commit_move;
@@ -31,17 +46,30 @@ pub mod caster_commit_spell;
 pub mod caster_redeem_craft;
 pub mod caster_redeem_loot;
 pub mod caster_redeem_move;
+pub mod caster_redeem_reforge;
 pub mod caster_redeem_rewards;
 pub mod caster_redeem_spell;
+pub mod caster_redeem_turn;
 pub mod close_game;
+pub mod close_turn_data;
 pub mod crank;
+pub mod crank_many;
 pub mod equipment;
 pub mod fix_redeem_spell;
 pub mod init_caster;
 pub mod init_game;
 pub mod init_player;
+pub mod inventory;
 pub mod manual_resource_burn;
+pub mod migrate_game_signer;
+pub mod migrate_turn_data;
 pub mod mint_nft;
 pub mod open_chest;
+pub mod program_admin;
+pub mod record_turn_entropy;
+pub mod reforge_item;
+pub mod salvage_item;
+#[cfg(feature = "debug")]
 pub mod test_helper;
+pub mod update_game_config;
 pub mod update_merkle_root;
@@ -2,109 +2,185 @@ use anchor_lang::{prelude::*, solana_program::sysvar};
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::{RandomGenerator, zombify_account};
-use crate::utils::{generate_new_equipment, generate_new_spell_book, ItemType};
+use crate::GameRules;
+use crate::utils::{ItemType, load_item, MAX_CHESTS_PER_BATCH, remove_item, roll_sealed_chest_contents, store_item};
 
 #[derive(Accounts)]
 pub struct OpenChest<'info> {
     pub system_program: Program<'info, System>,
 
     pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(mut, has_one = authority, has_one = game)]
     pub player: Account<'info, Player>,
 
-    #[account(address = sysvar::slot_hashes::id())]
-    pub slots: UncheckedAccount<'info>,
-
     #[account(address = sysvar::instructions::id())]
     pub instruction_sysvar_account: UncheckedAccount<'info>,
 
-    #[account(
-    mut,
-    close = authority,
-    constraint = chest.game == game.key(),
-    constraint = chest.owner == player.key(),
-    )]
-    pub chest: Box<Account<'info, Item>>,
-
-    #[account(init, space = Item::SIZE, payer = authority)]
-    pub item_1: Box<Account<'info, Item>>,
-    #[account(init, space = Item::SIZE, payer = authority)]
-    pub item_2: Box<Account<'info, Item>>,
-    #[account(init, space = Item::SIZE, payer = authority)]
-    pub item_3: Box<Account<'info, Item>>,
+    /// Chest account, or the player's inventory if a chest id is given
+    #[account(mut)]
+    pub chest: UncheckedAccount<'info>,
+
+    /// Player's inventory, or new accounts signing the transaction, a tier giving
+    /// more than 3 items takes the accounts of the others in the remaining accounts
+    #[account(mut)]
+    pub item_1: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub item_2: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub item_3: UncheckedAccount<'info>,
 }
 
-pub fn open_chest(ctx: Context<OpenChest>) -> ProgramResult {
-    let player = &ctx.accounts.player;
-    let game = &ctx.accounts.game;
-    let chest = &ctx.accounts.chest;
+/// Same accounts as open_chest, the chests and the accounts receiving their items are passed
+/// in the remaining accounts: a chest, then an account per item its tier gives, then the next chest
+#[derive(Accounts)]
+pub struct OpenChests<'info> {
+    pub system_program: Program<'info, System>,
 
-    let mut item_to_create = vec![
-        &mut ctx.accounts.item_1,
-        &mut ctx.accounts.item_2,
-        &mut ctx.accounts.item_3,
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority, has_one = game)]
+    pub player: Account<'info, Player>,
+
+    #[account(address = sysvar::instructions::id())]
+    pub instruction_sysvar_account: UncheckedAccount<'info>,
+}
+
+pub fn open_chest<'info>(
+    ctx: Context<'_, '_, '_, 'info, OpenChest<'info>>,
+    chest_id: Option<u32>,
+) -> ProgramResult {
+    let chest_info = ctx.accounts.chest.to_account_info();
+
+    let items = roll_chest_items(
+        &chest_info,
+        chest_id,
+        &ctx.accounts.game,
+        &ctx.accounts.player,
+        &ctx.accounts.game_config.rules,
+    )?;
+
+    let mut item_infos = vec![
+        ctx.accounts.item_1.to_account_info(),
+        ctx.accounts.item_2.to_account_info(),
+        ctx.accounts.item_3.to_account_info(),
     ];
+    item_infos.extend(ctx.remaining_accounts.iter().cloned());
 
-    match chest.item_type {
-        ItemType::Chest { .. } => {
-            let min_item_level = match chest.item_type {
-                ItemType::Chest { tier } => match tier {
-                    1 => 1,
-                    2 => 6,
-                    3 => 11,
-                    4 => 16,
-                    _ => 1,
-                },
-                _ => 1,
-            };
-            let max_item_level = chest.level;
-
-            let slots_ref = ctx.accounts.slots.data.borrow();
-            let slots = &**slots_ref;
-
-            let mut rand = RandomGenerator::new(slots, chest.to_account_info().key());
-
-            //Item level of chest gives range 1 to item level
-            // tier sets the minimum, tier 1 = 1, tier 2 = 6
-            //Each chest generates 3 new item
-            for i in 0..3 {
-                let item_level =
-                    rand.random_within_range::<u8, 1>(min_item_level, max_item_level);
-
-                if rand.random_within_range::<u8, 1>(1, 4) == 1 {
-                    //This will generate a spell book (1 in 4 chances, between robe, staff, head and spell book)
-                    generate_new_spell_book(
-                        &mut item_to_create[i],
-                        game,
-                        player,
-                        item_level,
-                        &mut rand,
-                    )?;
-                } else {
-                    generate_new_equipment(
-                        &mut item_to_create[i],
-                        game,
-                        player,
-                        item_level,
-                        None,
-                        &mut rand,
-                    )?;
-                }
-            }
-        }
-        _ => {
-            return Err(ErrorCode::ItemIsNotAChest.into());
-        }
+    if item_infos.len() < items.len() {
+        return Err(ErrorCode::ItemAccountMissing.into());
     }
 
-    zombify_account(
-        &mut ctx.accounts.chest,
+    //Before the new items are added, frees its slot if it's in the inventory
+    remove_item(
+        &chest_info,
+        chest_id,
         ctx.accounts.authority.to_account_info(),
         ctx.program_id,
     )?;
 
+    for (item_info, item) in item_infos.iter().zip(items) {
+        store_item(
+            item_info,
+            item,
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Opens up to MAX_CHESTS_PER_BATCH chests, chest_ids has an entry per chest (None for a chest account)
+pub fn open_chests<'info>(
+    ctx: Context<'_, '_, '_, 'info, OpenChests<'info>>,
+    chest_ids: Vec<Option<u32>>,
+) -> ProgramResult {
+    if chest_ids.is_empty() || chest_ids.len() > MAX_CHESTS_PER_BATCH {
+        return Err(ErrorCode::InvalidChestBatch.into());
+    }
+
+    let mut accounts = ctx.remaining_accounts.iter();
+
+    for chest_id in chest_ids.iter() {
+        let chest_info = match accounts.next() {
+            None => {
+                return Err(ErrorCode::InvalidChestBatch.into());
+            }
+            Some(chest_info) => chest_info,
+        };
+
+        let items = roll_chest_items(
+            chest_info,
+            *chest_id,
+            &ctx.accounts.game,
+            &ctx.accounts.player,
+            &ctx.accounts.game_config.rules,
+        )?;
+
+        remove_item(
+            chest_info,
+            *chest_id,
+            ctx.accounts.authority.to_account_info(),
+            ctx.program_id,
+        )?;
+
+        for item in items {
+            let item_info = match accounts.next() {
+                None => {
+                    return Err(ErrorCode::ItemAccountMissing.into());
+                }
+                Some(item_info) => item_info,
+            };
+
+            store_item(
+                item_info,
+                item,
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
+        }
+    }
+
+    //Accounts left over means the client expected other chests or tiers
+    if accounts.next().is_some() {
+        return Err(ErrorCode::InvalidChestBatch.into());
+    }
+
     Ok(())
 }
+
+/// Items a chest gives following the config of its tier, randomness comes from the seed sealed in the chest
+fn roll_chest_items(
+    chest_info: &AccountInfo,
+    chest_id: Option<u32>,
+    game: &Account<Game>,
+    player: &Account<Player>,
+    rules: &GameRules,
+) -> Result<Vec<Item>, ProgramError> {
+    let chest = load_item(chest_info, chest_id, &game.key(), &player.key())?;
+
+    match chest.item_type {
+        ItemType::Chest { tier, seed } => {
+            Ok(roll_sealed_chest_contents(rules, chest.key, chest.level, tier, seed)
+                .into_iter()
+                .map(|(level, item_type)| Item {
+                    game: game.key(),
+                    owner: player.key(),
+                    level,
+                    item_type,
+                    equipped_owner: None,
+                })
+                .collect())
+        }
+        _ => Err(ErrorCode::ItemIsNotAChest.into()),
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::{prelude::*, solana_program::bpf_loader_upgradeable};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitProgramAdmin<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Program data of this program, its upgrade authority is the first admin
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(init,
+    seeds = [b"program_admin"],
+    bump,
+    payer = authority,
+    space = ProgramAdmin::SIZE
+    )]
+    pub program_admin: Account<'info, ProgramAdmin>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Account<'info, ProgramAdmin>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Account<'info, ProgramAdmin>,
+}
+
+pub fn init_program_admin(ctx: Context<InitProgramAdmin>) -> ProgramResult {
+    let (program_data_address, _) = Pubkey::find_program_address(
+        &[ctx.program_id.as_ref()],
+        &bpf_loader_upgradeable::id(),
+    );
+
+    if ctx.accounts.program_data.key() != program_data_address
+        || ctx.accounts.program_data.upgrade_authority_address != Some(ctx.accounts.authority.key())
+    {
+        return Err(ErrorCode::NotSuperAdmin.into());
+    }
+
+    let program_admin = &mut ctx.accounts.program_admin;
+
+    program_admin.bump = *ctx.bumps.get("program_admin").unwrap();
+    program_admin.admin = ctx.accounts.authority.key();
+    program_admin.pending_admin = None;
+
+    Ok(())
+}
+
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> ProgramResult {
+    let program_admin = &mut ctx.accounts.program_admin;
+
+    if ctx.accounts.authority.key() != program_admin.admin {
+        return Err(ErrorCode::NotSuperAdmin.into());
+    }
+
+    program_admin.pending_admin = Some(new_admin);
+
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> ProgramResult {
+    let program_admin = &mut ctx.accounts.program_admin;
+
+    if program_admin.pending_admin != Some(ctx.accounts.authority.key()) {
+        return Err(ErrorCode::NotPendingAdmin.into());
+    }
+
+    program_admin.admin = ctx.accounts.authority.key();
+    program_admin.pending_admin = None;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(turn: u32)]
+pub struct RecordTurnEntropy<'info> {
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut,
+    seeds = [b"turn_data", game.key().as_ref(), turn.to_string().as_ref()],
+    bump = turn_data.bump
+    )]
+    pub turn_data: Box<Account<'info, TurnData>>,
+
+    #[account(address = sysvar::slot_hashes::id())]
+    pub slots: UncheckedAccount<'info>,
+}
+
+/// Anyone can record it (the crank bot right after the crank, or the first caster redeeming, which records
+/// it too), it's the same whoever does. It has to be within 512 slots of the crank: late, whoever redeems
+/// alone could choose between recording it in time or not, so past that the turn can only be cancelled
+pub fn record_turn_entropy(ctx: Context<RecordTurnEntropy>, turn: u32) -> ProgramResult {
+    //Crank slot is only set once the turn is over
+    if turn >= ctx.accounts.game.turn_info.turn {
+        return Err(ErrorCode::TurnEntropyNotReady.into());
+    }
+
+    let slots_ref = ctx.accounts.slots.data.borrow();
+
+    ctx.accounts.turn_data.record_entropy(&slots_ref, turn)
+}
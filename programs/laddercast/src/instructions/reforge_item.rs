@@ -0,0 +1,173 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::TileType;
+use crate::utils::{get_current_tile, get_reforge_cost, get_turn_commit_for_new_action, ItemType, load_item, ReforgeSnapshot, ReforgeType, set_item_equipped_owner, TurnCommit};
+
+#[derive(Accounts)]
+pub struct ReforgeItem<'info> {
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(mut, constraint = caster.owner == player.key())]
+    pub caster: Box<Account<'info, Caster>>,
+
+    #[account(address = sysvar::instructions::id())]
+    pub instruction_sysvar_account: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(init_if_needed,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+
+    /// Item account, or the player's inventory if an item id is given
+    #[account(mut)]
+    pub item: UncheckedAccount<'info>,
+}
+
+/// Commits to reroll the value (or the feature) of an unequipped equipment, the caster has to be on a crafting tile.
+/// Resources of each type are burned and count for the turn like the ones of a manual burn, the item is
+/// rerolled from the turn's commit-reveal randomness once the turn is over (caster_redeem_reforge).
+/// Until then the item is locked to the caster, see release_reforged_item
+pub fn reforge_item(
+    ctx: Context<ReforgeItem>,
+    commitment: [u8; 32],
+    reforge_type: ReforgeType,
+    item_id: Option<u32>,
+) -> ProgramResult {
+    let game_turn = ctx.accounts.game.turn_info.turn;
+    let rules = ctx.accounts.game_config.rules;
+    let caster = &ctx.accounts.caster;
+
+    let mut caster_turn_commit: TurnCommit = get_turn_commit_for_new_action(
+        caster.turn_commit,
+        game_turn,
+        rules.turn_data_retention,
+    )?;
+
+    if caster_turn_commit.actions.reforge != None {
+        return Err(ErrorCode::ActionAlreadyDone.into());
+    }
+
+    caster_turn_commit.commit(commitment)?;
+
+    let current_tile = match get_current_tile(
+        &ctx.accounts.game.map,
+        caster.modifiers.tile_level,
+        caster.modifiers.tile_column,
+    ) {
+        None => {
+            return Err(ErrorCode::TileNotExists.into());
+        }
+        Some(tile) => *tile,
+    };
+
+    if current_tile.tile_type != TileType::Crafting && current_tile.tile_type != TileType::Legendary {
+        return Err(ErrorCode::NotCraftingTile.into());
+    }
+
+    let item_info = ctx.accounts.item.to_account_info();
+    let item = load_item(
+        &item_info,
+        item_id,
+        &ctx.accounts.game.key(),
+        &ctx.accounts.player.key(),
+    )?;
+
+    if item.equipped_owner != None {
+        return Err(ErrorCode::ItemAlreadyInUse.into());
+    }
+
+    //Only an equipment can be reforged, checked now so the burn isn't for nothing
+    let rarity = match item.item_type {
+        ItemType::Equipment { rarity, .. } => rarity,
+        _ => {
+            return Err(ErrorCode::InvalidItemType.into());
+        }
+    };
+
+    let per_resource_burn = get_reforge_cost(item.level, rarity, reforge_type, &rules);
+
+    let resource_accounts = [
+        (&ctx.accounts.resource_1_mint_account, &ctx.accounts.resource_1_token_account),
+        (&ctx.accounts.resource_2_mint_account, &ctx.accounts.resource_2_token_account),
+        (&ctx.accounts.resource_3_mint_account, &ctx.accounts.resource_3_token_account),
+    ];
+
+    if resource_accounts
+        .iter()
+        .any(|(_, token_account)| token_account.amount < per_resource_burn)
+    {
+        return Err(ErrorCode::PlayerIsPoor.into());
+    }
+
+    for (mint, token_account) in resource_accounts.iter() {
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: mint.to_account_info(),
+                    to: token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            per_resource_burn,
+        )?;
+    }
+
+    set_item_equipped_owner(&item_info, item_id, Some(ctx.accounts.caster.key()), ctx.program_id)?;
+
+    let turn_data = &mut ctx.accounts.game_turn_data;
+    turn_data.resource_1_burned += per_resource_burn;
+    turn_data.resource_2_burned += per_resource_burn;
+    turn_data.resource_3_burned += per_resource_burn;
+
+    for resource_burned in caster_turn_commit.resources_burned.iter_mut() {
+        *resource_burned = resource_burned.checked_add(per_resource_burn).unwrap();
+    }
+
+    caster_turn_commit.actions.reforge = Some(ReforgeSnapshot {
+        item: item.key,
+        item_id,
+        reforge_type,
+    });
+
+    ctx.accounts.caster.turn_commit = Some(caster_turn_commit);
+
+    Ok(())
+}
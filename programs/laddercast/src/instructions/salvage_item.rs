@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::utils::{FIRE_INDEX, get_salvage_value, load_item, remove_item, WATER_INDEX};
+
+#[derive(Accounts)]
+pub struct SalvageItem<'info> {
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub game: Box<Account<'info, Game>>,
+    #[account(has_one = authority, has_one = game)]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(seeds = [b"game_signer", game.key().as_ref()], bump = game.signer_bump)]
+    pub game_signer: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = resource_1_mint_account.to_account_info().key() == game.resource_1_mint_account)]
+    pub resource_1_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_2_mint_account.to_account_info().key() == game.resource_2_mint_account)]
+    pub resource_2_mint_account: Box<Account<'info, Mint>>,
+    #[account(mut, constraint = resource_3_mint_account.to_account_info().key() == game.resource_3_mint_account)]
+    pub resource_3_mint_account: Box<Account<'info, Mint>>,
+
+    #[account(init_if_needed,
+    associated_token::mint = resource_1_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_1_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_2_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_2_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+    associated_token::mint = resource_3_mint_account,
+    associated_token::authority = authority,
+    payer = authority)]
+    pub resource_3_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"turn_data", game.to_account_info().key().as_ref(), game.turn_info.turn.to_string().as_ref()], bump = game_turn_data.bump)]
+    pub game_turn_data: Box<Account<'info, TurnData>>,
+
+    /// Item account, or the player's inventory if an item id is given
+    #[account(mut)]
+    pub item: UncheckedAccount<'info>,
+}
+
+/// Destroys an unequipped item and mints back resources depending on its feature, rarity and level
+pub fn salvage_item(ctx: Context<SalvageItem>, item_id: Option<u32>) -> ProgramResult {
+    let item_info = ctx.accounts.item.to_account_info();
+    let item = load_item(
+        &item_info,
+        item_id,
+        &ctx.accounts.game.key(),
+        &ctx.accounts.player.key(),
+    )?;
+
+    if item.equipped_owner != None {
+        return Err(ErrorCode::ItemAlreadyInUse.into());
+    }
+
+    let resources = match get_salvage_value(&item.item_type, item.level) {
+        None => {
+            return Err(ErrorCode::ItemNotSalvageable.into());
+        }
+        Some(resources) => resources,
+    };
+
+    remove_item(
+        &item_info,
+        item_id,
+        ctx.accounts.authority.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    let game_key = ctx.accounts.game.key();
+
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[ctx.accounts.game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    for (resource_index, amount) in resources.iter().enumerate() {
+        if *amount == 0 {
+            continue;
+        }
+
+        let (mint, to) = match resource_index {
+            FIRE_INDEX => (&ctx.accounts.resource_1_mint_account, &ctx.accounts.resource_1_token_account),
+            WATER_INDEX => (&ctx.accounts.resource_2_mint_account, &ctx.accounts.resource_2_token_account),
+            _ => (&ctx.accounts.resource_3_mint_account, &ctx.accounts.resource_3_token_account),
+        };
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: ctx.accounts.game_signer.to_account_info(),
+                },
+                signer,
+            ),
+            *amount,
+        )?;
+
+        let turn_data = &mut ctx.accounts.game_turn_data;
+        turn_data.resources_salvaged[resource_index] = turn_data.resources_salvaged[resource_index]
+            .checked_add(*amount)
+            .unwrap();
+    }
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
-use core::mem::size_of;
+//Only compiled in with the "debug" feature, these instructions aren't part of the program
+//interface (IDL), the program fallback dispatches them (see dispatch_debug_instruction).
+//Their own IDL is generated from this file by `yarn test-debug` (see laddercast_debug below)
 
-use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_lang::{prelude::*, solana_program::hash, solana_program::sysvar};
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
@@ -19,7 +21,7 @@ pub struct GiveResources<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(mut, seeds = [b"game_signer"], bump)]
+    #[account(mut, seeds = [b"game_signer", game.key().as_ref()], bump = game.signer_bump)]
     pub game_signer: UncheckedAccount<'info>,
 
     pub game: Box<Account<'info, Game>>,
@@ -73,7 +75,7 @@ pub struct GiveLada<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(mut, seeds = [b"game_signer"], bump)]
+    #[account(mut, seeds = [b"game_signer", game.key().as_ref()], bump = game.signer_bump)]
     pub game_signer: UncheckedAccount<'info>,
 
     pub game: Box<Account<'info, Game>>,
@@ -96,11 +98,9 @@ pub struct ChangeTile<'info> {
     pub game: Box<Account<'info, Game>>,
 }
 
-#[allow(unused_variables)]
 pub fn give_resources(ctx: Context<GiveResources>, amount: u64) -> ProgramResult {
-    // #[cfg(feature = "debug")]
-    // {
-    let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+    let game_key = ctx.accounts.game.key();
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[ctx.accounts.game.signer_bump]];
     let signer = &[&seeds[..]];
     token::mint_to(
         CpiContext::new(
@@ -140,16 +140,11 @@ pub fn give_resources(ctx: Context<GiveResources>, amount: u64) -> ProgramResult
     )?;
 
     Ok(())
-    // }
-    // #[cfg(not(feature = "debug"))]
-    // {
-    //     Err(ErrorCode::InvalidFunction.into())
-    // }
 }
 
-#[allow(unused_variables)]
 pub fn give_lada(ctx: Context<GiveLada>, amount: u64) -> ProgramResult {
-    let seeds = &[b"game_signer".as_ref(), &[ctx.accounts.game.signer_bump]];
+    let game_key = ctx.accounts.game.key();
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[ctx.accounts.game.signer_bump]];
     let signer = &[&seeds[..]];
 
     let cpi_accounts = Transfer {
@@ -172,15 +167,12 @@ pub fn give_lada(ctx: Context<GiveLada>, amount: u64) -> ProgramResult {
     Ok(())
 }
 
-#[allow(unused_variables)]
 pub fn give_item(ctx: Context<GiveItems>, item_type: ItemType, level: u8) -> ProgramResult {
-    // #[cfg(feature = "debug")]
-    // {
     let player_acc = &mut ctx.accounts.player;
     let game = &ctx.accounts.game;
 
     match item_type {
-        ItemType::Chest { .. } => {
+        ItemType::Chest { seed, .. } => {
             // Item {
             let item = &mut ctx.accounts.item;
             item.game = ctx.accounts.game.key();
@@ -195,6 +187,7 @@ pub fn give_item(ctx: Context<GiveItems>, item_type: ItemType, level: u8) -> Pro
                     16..=30 => 4,
                     _ => 1,
                 },
+                seed,
             };
         }
         ItemType::SpellBook { .. } => {
@@ -216,29 +209,128 @@ pub fn give_item(ctx: Context<GiveItems>, item_type: ItemType, level: u8) -> Pro
         _ => {}
     }
     Ok(())
-    // }
-    // #[cfg(not(feature = "debug"))]
-    // {
-    //     Err(ErrorCode::InvalidFunction.into())
-    // }
 }
 
-#[allow(unused_variables)]
 pub fn change_tile(
     ctx: Context<ChangeTile>,
     tile_type: TileType,
     lvl: u8,
     col: u8,
 ) -> ProgramResult {
+    if lvl >= ctx.accounts.game.levels || col >= ctx.accounts.game.columns {
+        return Err(ErrorCode::TileNotExists.into());
+    }
+
     ctx.accounts.game.map[lvl as usize][col as usize] = Some(Tile {
         tile_type,
         life: 1,
         is_first_time_spawning: false,
     });
     Ok(())
-    // }
-    // #[cfg(not(feature = "debug"))]
-    // {
-    //     Err(ErrorCode::InvalidFunction.into())
-    // }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct GiveResourcesArgs {
+    pub amount: u64,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct GiveLadaArgs {
+    pub amount: u64,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct GiveItemArgs {
+    pub item_type: ItemType,
+    pub level: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct ChangeTileArgs {
+    pub tile_type: TileType,
+    pub lvl: u8,
+    pub col: u8,
+}
+
+/// Never compiled, only there for `anchor idl parse` to generate the IDL of the debug instructions
+/// from their account structs, has to list the instructions dispatch_debug knows
+#[cfg(any())]
+#[program]
+pub mod laddercast_debug {
+    use super::*;
+
+    pub fn give_resources(ctx: Context<GiveResources>, amount: u64) -> ProgramResult {
+        super::give_resources(ctx, amount)
+    }
+
+    pub fn give_lada(ctx: Context<GiveLada>, amount: u64) -> ProgramResult {
+        super::give_lada(ctx, amount)
+    }
+
+    pub fn give_item(ctx: Context<GiveItems>, item_type: ItemType, level: u8) -> ProgramResult {
+        super::give_item(ctx, item_type, level)
+    }
+
+    pub fn change_tile(ctx: Context<ChangeTile>, tile_type: TileType, lvl: u8, col: u8) -> ProgramResult {
+        super::change_tile(ctx, tile_type, lvl, col)
+    }
+}
+
+/// Same discriminator anchor gives to the program instructions, so clients call these the same way
+pub fn debug_instruction_sighash(name: &str) -> [u8; 8] {
+    let mut sighash = [0u8; 8];
+    sighash.copy_from_slice(&hash::hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]);
+    sighash
+}
+
+pub fn dispatch_debug(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    //Anchor sends instruction data too short for a discriminator to the fallback as well
+    if data.len() < 8 {
+        return Err(anchor_lang::__private::ErrorCode::InstructionMissing.into());
+    }
+
+    let (sighash, ix_data) = data.split_at(8);
+
+    if sighash == debug_instruction_sighash("give_resources") {
+        run_debug_instruction(program_id, accounts, ix_data, |ctx, args: GiveResourcesArgs| {
+            give_resources(ctx, args.amount)
+        })
+    } else if sighash == debug_instruction_sighash("give_lada") {
+        run_debug_instruction(program_id, accounts, ix_data, |ctx, args: GiveLadaArgs| {
+            give_lada(ctx, args.amount)
+        })
+    } else if sighash == debug_instruction_sighash("give_item") {
+        run_debug_instruction(program_id, accounts, ix_data, |ctx, args: GiveItemArgs| {
+            give_item(ctx, args.item_type, args.level)
+        })
+    } else if sighash == debug_instruction_sighash("change_tile") {
+        run_debug_instruction(program_id, accounts, ix_data, |ctx, args: ChangeTileArgs| {
+            change_tile(ctx, args.tile_type, args.lvl, args.col)
+        })
+    } else {
+        Err(anchor_lang::__private::ErrorCode::InstructionFallbackNotFound.into())
+    }
+}
+
+//What anchor generates for the program instructions: deserialize, validate the accounts, run, then persist them
+fn run_debug_instruction<'info, T, A>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    ix_data: &[u8],
+    handler: impl FnOnce(Context<'_, '_, '_, 'info, T>, A) -> ProgramResult,
+) -> ProgramResult
+where
+    T: Accounts<'info> + AccountsExit<'info>,
+    A: AnchorDeserialize,
+{
+    let args = A::deserialize(&mut &ix_data[..])
+        .map_err(|_| anchor_lang::__private::ErrorCode::InstructionDidNotDeserialize)?;
+
+    let mut bumps = std::collections::BTreeMap::new();
+    let mut remaining_accounts: &[AccountInfo<'info>] = accounts;
+    let mut accounts = T::try_accounts(program_id, &mut remaining_accounts, ix_data, &mut bumps)?;
+
+    handler(Context::new(program_id, &mut accounts, remaining_accounts, bumps), args)?;
+
+    accounts.exit(program_id)
 }
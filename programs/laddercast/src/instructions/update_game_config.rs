@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::GameRules;
+
+#[derive(Accounts)]
+pub struct UpdateGameConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Box<Account<'info, ProgramAdmin>>,
+
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut, seeds = [b"game_config", game.key().as_ref()], bump = game_config.bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+}
+
+pub fn update_game_config(ctx: Context<UpdateGameConfig>, rules: GameRules) -> ProgramResult {
+    //Games are created by the program admin, so it is the one tuning them (even after a handover)
+    if ctx.accounts.authority.key() != ctx.accounts.program_admin.admin {
+        return Err(ErrorCode::NotSuperAdmin.into());
+    }
+
+    rules.validate()?;
+
+    ctx.accounts.game_config.rules = rules;
+
+    Ok(())
+}
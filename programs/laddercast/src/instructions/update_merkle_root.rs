@@ -2,7 +2,6 @@ use anchor_lang::prelude::*;
 
 use crate::account::*;
 use crate::error::ErrorCode;
-use crate::utils::GAME_CREATOR_AUTHORITY_PUBKEY;
 
 #[derive(Accounts)]
 #[instruction(item_type_str: String, item_level: u8)]
@@ -12,6 +11,9 @@ pub struct UpdateMerkleRoot<'info> {
 
     pub system_program: Program<'info, System>,
 
+    #[account(seeds = [b"program_admin"], bump = program_admin.bump)]
+    pub program_admin: Account<'info, ProgramAdmin>,
+
     pub game_account: Box<Account<'info, Game>>,
 
     #[account(init_if_needed,
@@ -29,9 +31,9 @@ pub fn update_merkle_root(
     _item_type_str: String,
     _item_level: u8,
 ) -> ProgramResult {
-    // if ctx.accounts.authority.key().to_string() != GAME_CREATOR_AUTHORITY_PUBKEY {
-    //     return Err(ErrorCode::NotSuperAdmin.into());
-    // }
+    if ctx.accounts.authority.key() != ctx.accounts.program_admin.admin {
+        return Err(ErrorCode::NotSuperAdmin.into());
+    }
 
     let merkle_root_nft_acc = &mut ctx.accounts.merkle_root_nft;
 
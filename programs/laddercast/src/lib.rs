@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 
 use instructions::*;
 
+use crate::utils::dispatch_debug_instruction;
 use crate::utils::validate_instruction_order_and_program_ids;
 use crate::utils::validate_is_last_instructions_and_program_ids;
-use crate::utils::{ActionType, ItemFeature, ItemType};
+use crate::utils::{ActionType, ItemFeature, ReforgeType};
 
 mod account;
 mod config;
@@ -13,6 +14,20 @@ mod event;
 mod instructions;
 mod utils;
 
+/// What the off-chain simulator (sim crate) needs to run the same rules as the program
+pub mod rules {
+    pub use crate::account::{Caster, Game};
+    pub use crate::instructions::{advance_turn, GameRules, GameTurnInfo, PlayerBonuses, TileType};
+    pub use crate::utils::{
+        CommittedActions, CraftingSnapshot, cycle_tile, EARTH_INDEX, EntropySource, EquipmentType,
+        FIRE_INDEX, get_crafting_cost, get_current_tile, get_lada_reward, get_move_cost,
+        get_player_bonuses, give_exp_to_caster_resources_burned, give_exp_to_caster_spell, ItemFeature,
+        ItemRarity, ItemRecord, ItemType, LootRoll, MAX_MAP_LEVELS, Modifiers, RandomGenerator,
+        roll_crafted_item, roll_loot, roll_sealed_chest_contents, roll_spell, SpellEffect, SpellSnapshot,
+        SpellType, WATER_INDEX,
+    };
+}
+
 //declare_id!("LCNTy2Q4HsKUecKEETwwzujjbAx9DGFJort8SjGzGFj");
 // declare_id!("LCThBz55Ma7hcueUQA3iiofBhvidQHfNLxxwfLsycxb");
 declare_id!("Aejjj86wGd3B4BEEaQyn4663xabWxLyNkJr6d7qLRcov");
@@ -24,8 +39,14 @@ pub mod laddercast {
     //********************************************
     //Initialization functions
     //********************************************
-    pub fn init_game(ctx: Context<InitGame>, turn_info: GameTurnInfo) -> ProgramResult {
-        init_game::init_game(ctx, turn_info)
+    pub fn init_game(
+        ctx: Context<InitGame>,
+        turn_info: GameTurnInfo,
+        rules: GameRules,
+        levels: u8,
+        columns: u8,
+    ) -> ProgramResult {
+        init_game::init_game(ctx, turn_info, rules, levels, columns)
     }
 
     pub fn init_player(ctx: Context<InitPlayer>) -> ProgramResult {
@@ -37,6 +58,39 @@ pub mod laddercast {
         init_caster::init_caster(ctx)
     }
 
+    //********************************************
+    //Admin functions
+    //********************************************
+    pub fn init_program_admin(ctx: Context<InitProgramAdmin>) -> ProgramResult {
+        program_admin::init_program_admin(ctx)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> ProgramResult {
+        program_admin::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> ProgramResult {
+        program_admin::accept_admin(ctx)
+    }
+
+    //********************************************
+    //Configuration functions
+    //********************************************
+    pub fn update_game_config(ctx: Context<UpdateGameConfig>, rules: GameRules) -> ProgramResult {
+        update_game_config::update_game_config(ctx, rules)
+    }
+
+    //********************************************
+    //Migration functions
+    //********************************************
+    pub fn migrate_game_signer(ctx: Context<MigrateGameSigner>) -> ProgramResult {
+        migrate_game_signer::migrate_game_signer(ctx)
+    }
+
+    pub fn migrate_turn_data(ctx: Context<MigrateTurnData>, turn: u32) -> ProgramResult {
+        migrate_turn_data::migrate_turn_data(ctx, turn)
+    }
+
     //********************************************
     //Close functions
     //********************************************
@@ -44,25 +98,48 @@ pub mod laddercast {
         close_game::close_game(ctx)
     }
 
+    pub fn close_turn_data(ctx: Context<CloseTurnData>, turn: u32) -> ProgramResult {
+        close_turn_data::close_turn_data(ctx, turn)
+    }
+
     //********************************************
     //Turn based functions
     //********************************************
 
-    pub fn caster_commit_loot(ctx: Context<Loot>) -> ProgramResult {
-        caster_commit_loot::caster_commit_loot(ctx)
+    pub fn caster_commit_loot(ctx: Context<Loot>, commitment: [u8; 32]) -> ProgramResult {
+        caster_commit_loot::caster_commit_loot(ctx, commitment)
     }
 
     pub fn caster_commit_move(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult {
         caster_commit_move::caster_commit_move(ctx, lvl, clm)
     }
 
-    pub fn caster_commit_craft(ctx: Context<Craft>) -> ProgramResult {
-        caster_commit_craft::caster_commit_craft(ctx)
+    pub fn caster_commit_teleport(ctx: Context<Move>, lvl: u8, clm: u8) -> ProgramResult {
+        caster_commit_move::caster_commit_teleport(ctx, lvl, clm)
+    }
+
+    pub fn caster_commit_craft(
+        ctx: Context<Craft>,
+        commitment: [u8; 32],
+        item_ids: [Option<u32>; 3],
+    ) -> ProgramResult {
+        caster_commit_craft::caster_commit_craft(ctx, commitment, item_ids)
     }
 
     #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
-    pub fn caster_commit_spell(ctx: Context<Spell>) -> ProgramResult {
-        caster_commit_spell::caster_commit_spell(ctx)
+    pub fn caster_commit_spell(
+        ctx: Context<Spell>,
+        commitment: [u8; 32],
+        spellbook_id: Option<u32>,
+    ) -> ProgramResult {
+        caster_commit_spell::caster_commit_spell(ctx, commitment, spellbook_id)
+    }
+
+    pub fn caster_cancel_turn<'info>(
+        ctx: Context<'_, '_, '_, 'info, CasterCancelTurn<'info>>,
+        turn: u32,
+    ) -> ProgramResult {
+        caster_cancel_turn::caster_cancel_turn(ctx, turn)
     }
 
     #[access_control(validate_instruction_order_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id, & ctx.accounts.caster.turn_commit, ActionType::Move))]
@@ -75,22 +152,34 @@ pub mod laddercast {
     #[access_control(validate_instruction_order_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id, & ctx.accounts.caster.turn_commit, ActionType::Loot))]
     pub fn caster_redeem_loot<'info>(
         ctx: Context<'_, '_, '_, 'info, CasterRedeemLootAction<'info>>,
+        secret: [u8; 32],
     ) -> ProgramResult {
-        caster_redeem_loot::caster_redeem_loot(ctx)
+        caster_redeem_loot::caster_redeem_loot(ctx, secret)
     }
 
     #[access_control(validate_instruction_order_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id, & ctx.accounts.caster.turn_commit, ActionType::Craft))]
     pub fn caster_redeem_craft<'info>(
         ctx: Context<'_, '_, '_, 'info, CasterRedeemCraftAction<'info>>,
+        secret: [u8; 32],
     ) -> ProgramResult {
-        caster_redeem_craft::caster_redeem_craft(ctx)
+        caster_redeem_craft::caster_redeem_craft(ctx, secret)
     }
 
     #[access_control(validate_instruction_order_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id, & ctx.accounts.caster.turn_commit, ActionType::Spell))]
     pub fn caster_redeem_spell<'info>(
         ctx: Context<'_, '_, '_, 'info, CasterRedeemSpellAction<'info>>,
+        secret: [u8; 32],
+    ) -> ProgramResult {
+        caster_redeem_spell::caster_redeem_spell(ctx, secret)
+    }
+
+    #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
+    pub fn caster_redeem_reforge(
+        ctx: Context<CasterRedeemReforgeAction>,
+        turn: u32,
+        secret: [u8; 32],
     ) -> ProgramResult {
-        caster_redeem_spell::caster_redeem_spell(ctx)
+        caster_redeem_reforge::caster_redeem_reforge(ctx, turn, secret)
     }
 
     #[access_control(validate_instruction_order_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id, & ctx.accounts.caster.turn_commit, ActionType::Reward))]
@@ -101,25 +190,85 @@ pub mod laddercast {
     }
 
     #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
-    pub fn crank(ctx: Context<Crank>) -> ProgramResult {
+    pub fn caster_redeem_turn<'info>(
+        ctx: Context<'_, '_, '_, 'info, CasterRedeemTurnAction<'info>>,
+        secret: [u8; 32],
+    ) -> ProgramResult {
+        caster_redeem_turn::caster_redeem_turn(ctx, secret)
+    }
+
+    #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
+    pub fn crank<'info>(ctx: Context<'_, '_, '_, 'info, Crank<'info>>) -> ProgramResult {
         crank::crank(ctx)
     }
 
+    #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
+    pub fn crank_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, CrankMany<'info>>,
+        max_turns: u32,
+    ) -> ProgramResult {
+        crank_many::crank_many(ctx, max_turns)
+    }
+
+    pub fn record_turn_entropy(ctx: Context<RecordTurnEntropy>, turn: u32) -> ProgramResult {
+        record_turn_entropy::record_turn_entropy(ctx, turn)
+    }
+
     //********************************************
     //Non-turn based functions
     //********************************************
 
-    pub fn equip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
-        equipment::equip_item(ctx)
+    pub fn init_inventory(ctx: Context<InitInventory>) -> ProgramResult {
+        inventory::init_inventory(ctx)
+    }
+
+    pub fn migrate_item_to_inventory(ctx: Context<MigrateItemToInventory>) -> ProgramResult {
+        inventory::migrate_item_to_inventory(ctx)
+    }
+
+    pub fn equip_item(ctx: Context<EquipUnequipItem>, item_id: Option<u32>) -> ProgramResult {
+        equipment::equip_item(ctx, item_id)
+    }
+
+    pub fn unequip_item(ctx: Context<EquipUnequipItem>, item_id: Option<u32>) -> ProgramResult {
+        equipment::unequip_item(ctx, item_id)
+    }
+
+    pub fn set_loadout<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetLoadout<'info>>,
+        loadout: Loadout,
+    ) -> ProgramResult {
+        equipment::set_loadout(ctx, loadout)
     }
 
-    pub fn unequip_item(ctx: Context<EquipUnequipItem>) -> ProgramResult {
-        equipment::unequip_item(ctx)
+    #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
+    pub fn open_chest<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenChest<'info>>,
+        chest_id: Option<u32>,
+    ) -> ProgramResult {
+        open_chest::open_chest(ctx, chest_id)
     }
 
     #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
-    pub fn open_chest(ctx: Context<OpenChest>) -> ProgramResult {
-        open_chest::open_chest(ctx)
+    pub fn open_chests<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenChests<'info>>,
+        chest_ids: Vec<Option<u32>>,
+    ) -> ProgramResult {
+        open_chest::open_chests(ctx, chest_ids)
+    }
+
+    #[access_control(validate_is_last_instructions_and_program_ids(& ctx.accounts.instruction_sysvar_account, & ctx.program_id))]
+    pub fn reforge_item(
+        ctx: Context<ReforgeItem>,
+        commitment: [u8; 32],
+        reforge_type: ReforgeType,
+        item_id: Option<u32>,
+    ) -> ProgramResult {
+        reforge_item::reforge_item(ctx, commitment, reforge_type, item_id)
+    }
+
+    pub fn salvage_item(ctx: Context<SalvageItem>, item_id: Option<u32>) -> ProgramResult {
+        salvage_item::salvage_item(ctx, item_id)
     }
 
     pub fn manual_resource_burn(
@@ -139,8 +288,9 @@ pub mod laddercast {
         item_level: u8,
         nft_uri: String,
         merkle_proof: Vec<[u8; 32]>,
+        item_id: Option<u32>,
     ) -> ProgramResult {
-        mint_nft::mint_item(ctx, nft_uri, merkle_proof, item_type_str, item_level)
+        mint_nft::mint_item(ctx, nft_uri, merkle_proof, item_type_str, item_level, item_id)
     }
 
     pub fn mint_caster(
@@ -170,27 +320,11 @@ pub mod laddercast {
     }
 
     //********************************************
-    //Debug functions only for testing
+    //Debug functions only for testing, they only exist
+    //when built with the "debug" feature and aren't in the IDL
     //********************************************
-    pub fn give_resources(ctx: Context<GiveResources>, amount: u64) -> ProgramResult {
-        test_helper::give_resources(ctx, amount)
-    }
-
-    pub fn give_lada(ctx: Context<GiveLada>, amount: u64) -> ProgramResult {
-        test_helper::give_lada(ctx, amount)
-    }
-
-    pub fn give_item(ctx: Context<GiveItems>, item_type: ItemType, level: u8) -> ProgramResult {
-        test_helper::give_item(ctx, item_type, level)
-    }
-
-    pub fn change_tile(
-        ctx: Context<ChangeTile>,
-        tile_type: TileType,
-        lvl: u8,
-        col: u8,
-    ) -> ProgramResult {
-        test_helper::change_tile(ctx, tile_type, lvl, col)
+    pub fn fallback(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        dispatch_debug_instruction(program_id, accounts, data)
     }
 
     //********************************************
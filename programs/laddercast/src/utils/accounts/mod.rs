@@ -1,5 +1,7 @@
 pub use metaplex_accounts::*;
+pub use tile_accounts::*;
 pub use turn_accounts::*;
 
 pub mod turn_accounts;
+pub mod tile_accounts;
 pub mod metaplex_accounts;
\ No newline at end of file
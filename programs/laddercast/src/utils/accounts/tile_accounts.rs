@@ -0,0 +1,31 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+
+use crate::TileType;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub struct TileChange {
+    /// Turn the change happened in (the turn that was added by the crank)
+    pub turn: u32,
+    pub level: u8,
+    pub column: u8,
+    pub kind: TileChangeKind,
+    pub tile_type: TileType,
+    pub life: u8,
+}
+
+impl TileChange {
+    pub const SIZE: usize = 4 + 1 + 1 + size_of::<TileChangeKind>() + size_of::<TileType>() + 1;
+}
+
+/// Life going down by 1 every turn isn't recorded since it's always the case
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+pub enum TileChangeKind {
+    /// New tile on an empty spot of the map
+    Spawned,
+    /// Tile that ran out of life (with its old type), always followed by the tile replacing it
+    Expired,
+    /// Tile replacing one that ran out of life
+    Cycled,
+}
@@ -2,17 +2,48 @@ use std::mem::size_of;
 
 use anchor_lang::prelude::*;
 
-use crate::utils::ItemRarity;
+use crate::error::ErrorCode;
+use crate::utils::{get_commitment, ItemRarity, ReforgeType, SpellType};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
 pub struct TurnCommit {
     pub turn: u32,
     pub resources_burned: [u64; 3],
     pub actions: CommittedActions,
+    /// Hash of the secret revealed when redeeming, zeroed until a random action is committed
+    pub commitment: [u8; 32],
 }
 
 impl TurnCommit {
-    pub const SIZE: usize = 8 + 4 + 8 * 3 + CommittedActions::SIZE;
+    pub const SIZE: usize = 8 + 4 + 8 * 3 + CommittedActions::SIZE + 32;
+
+    /// Every action of a turn shares the same secret
+    pub fn commit(&mut self, commitment: [u8; 32]) -> ProgramResult {
+        if commitment == [0; 32] {
+            return Err(ErrorCode::InvalidCommitment.into());
+        }
+
+        if self.commitment == [0; 32] {
+            self.commitment = commitment;
+        } else if self.commitment != commitment {
+            return Err(ErrorCode::InvalidCommitment.into());
+        }
+
+        Ok(())
+    }
+
+    /// Past the retention its turn data can be closed, it can't be redeemed anymore
+    pub fn is_stale(&self, game_turn: u32, turn_data_retention: u32) -> bool {
+        self.turn.saturating_add(turn_data_retention) < game_turn
+    }
+
+    pub fn verify_secret(&self, secret: &[u8; 32]) -> ProgramResult {
+        if self.commitment == [0; 32] || get_commitment(secret) != self.commitment {
+            return Err(ErrorCode::InvalidSecret.into());
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
@@ -22,11 +53,13 @@ pub struct CommittedActions {
     pub mv: Option<[u8; 2]>,
     pub crafting: Option<CraftingSnapshot>,
     pub action_order: [u8; 4],
+    /// Not in the action order, redeemed on its own any time before the rewards
+    pub reforge: Option<ReforgeSnapshot>,
 }
 
 impl CommittedActions {
     pub const SIZE: usize =
-        8 + 1 + 1 + SpellSnapshot::SIZE + 1 + 1 * 2 + 1 + CraftingSnapshot::SIZE + 1 * 4;
+        8 + 1 + 1 + SpellSnapshot::SIZE + 1 + 1 * 2 + 1 + CraftingSnapshot::SIZE + 1 * 4 + 1 + ReforgeSnapshot::SIZE;
 
     pub fn get_highest_value(&self) -> u8 {
         *self
@@ -72,12 +105,32 @@ impl CraftingSnapshot {
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+/// Spell book burned when the spell is committed, so not revealing the turn costs it too
 pub struct SpellSnapshot {
-    pub is_extra_level_bonus: bool,
+    pub spell: SpellType,
+    pub rarity: ItemRarity,
+    /// Resources or experience given
+    pub value: u16,
+    /// Level of the equipment given by an item spell
+    pub level: u8,
 }
 
 impl SpellSnapshot {
-    pub const SIZE: usize = 8 + 1;
+    pub const SIZE: usize = 8 + size_of::<SpellType>() + size_of::<ItemRarity>() + 2 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+/// Item reforged once the turn is over, the resources are burned when it's committed
+pub struct ReforgeSnapshot {
+    /// Item account, or the key derived from the inventory and the item id
+    pub item: Pubkey,
+    /// Set if the item is in the inventory
+    pub item_id: Option<u32>,
+    pub reforge_type: ReforgeType,
+}
+
+impl ReforgeSnapshot {
+    pub const SIZE: usize = 32 + 1 + 4 + size_of::<ReforgeType>();
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default, Copy)]
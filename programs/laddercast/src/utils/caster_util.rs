@@ -1,5 +1,8 @@
+use anchor_lang::prelude::ProgramError;
+
 use crate::account::Caster;
-use crate::utils::{ItemRarity, MAX_LEVEL_0_BASED, RandomGenerator};
+use crate::error::ErrorCode;
+use crate::utils::{ItemRarity, MAX_LEVEL_0_BASED, RandomGenerator, TurnCommit};
 
 pub const EXPERIENCE_REQUIRED_PER_LEVEL: [u64; 30] = [502, 2000, 4985, 9950, 17387, 27789, 41648, 59457, 81707, 108891, 141502, 180032, 224973, 276818, 336059, 403189, 478700, 563084, 656833, 760441, 874399, 999200, 1135336, 1283300, 1443584, 1616681, 1803082, 2003280, 2217768, 2447038];
 
@@ -28,6 +31,19 @@ pub fn give_exp_to_caster_spell(caster: &mut Caster, value: u64) {
     }
 }
 
+/// LADA (number * 10 ^ 9) of the turn given to a caster, proportional to the resources it burned
+pub fn get_lada_reward(total_resources_burned_for_turn: u64, total_resources_burned_for_caster: u64, lada_distribution_per_turn: u64) -> u64 {
+    let mut total_resources_burned_for_turn = total_resources_burned_for_turn as f64;
+
+    if total_resources_burned_for_turn == 0.0 {
+        total_resources_burned_for_turn = 1.0;
+    }
+
+    let proportion_total = total_resources_burned_for_caster as f64 / total_resources_burned_for_turn;
+
+    (proportion_total * lada_distribution_per_turn as f64) as u64
+}
+
 pub fn is_spell_successful(rand: &mut RandomGenerator, spell_book_rarity: ItemRarity) -> bool {
     //Spell have a chance of working, they won't always work
     let max_range = match spell_book_rarity {
@@ -38,4 +54,24 @@ pub fn is_spell_successful(rand: &mut RandomGenerator, spell_book_rarity: ItemRa
     };
 
     rand.random_within_range::<u8, 1>(1, max_range) == 1
-}
\ No newline at end of file
+}
+
+/// Turn commit a new action gets added to, a commit left unredeemed past the retention is dropped
+pub fn get_turn_commit_for_new_action(
+    turn_commit: Option<TurnCommit>,
+    game_turn: u32,
+    turn_data_retention: u32,
+) -> Result<TurnCommit, ProgramError> {
+    match turn_commit {
+        Some(turn_commit) if turn_commit.turn == game_turn => Ok(turn_commit),
+        Some(turn_commit) if !turn_commit.is_stale(game_turn, turn_data_retention) => {
+            Err(ErrorCode::PendingTurn.into())
+        }
+        //Dropping it would leave the reforged item locked, it's released by cancelling the turn
+        Some(turn_commit) if turn_commit.actions.reforge.is_some() => Err(ErrorCode::StaleTurnPendingReforge.into()),
+        _ => Ok(TurnCommit {
+            turn: game_turn,
+            ..Default::default()
+        }),
+    }
+}
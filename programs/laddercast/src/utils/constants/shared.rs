@@ -2,6 +2,8 @@ pub const FIRE_INDEX: usize = 0;
 pub const WATER_INDEX: usize = 1;
 pub const EARTH_INDEX: usize = 2;
 
+//Values below that are used by GameRules are only the defaults, each game has its own GameConfig
+
 //is number * 10 ^ 9 (1 000 000 000) since can only use u64 in transfer
 pub const LADA_DISTRIBUTION_PER_TURN: u64 = 1_984_126_984_130;
 
@@ -12,16 +14,38 @@ pub const DECIMALS_PRECISION: u64 = 1_000_000_000;
 //Game constants
 pub const MAX_LEVEL_0_BASED: u8 = 29;
 pub const MAX_LEVEL_1_BASED: u8 = 30;
-pub const MAX_COLUMN_0_BASED: u8 = 2;
 pub const CRAFTING_COST_MULTIPLIER: u8 = 5;
 pub const MOVE_COST_MULTIPLIER: u8 = 10;
+pub const TELEPORT_COST_MULTIPLIER: u8 = 20;
+//Resources given back per item level when salvaging a common item
+pub const SALVAGE_VALUE_PER_LEVEL: u64 = 3;
+
+//Map constants (levels x columns is chosen per game at init)
+pub const MAX_MAP_LEVELS: u8 = 30;
+pub const MAX_MAP_COLUMNS: u8 = 5;
+
+//Turn constants
+pub const TURN_DATA_RETENTION: u32 = 720;
+pub const TILE_HISTORY_CAPACITY: usize = 256;
 
 //Tile constants
 pub const MINIMUM_TILE_LIFE: u8 = 3;
 
 //Player constants
+//Items an inventory holds, bounds the compute of looking an item up by id
+pub const INVENTORY_CAPACITY: usize = 200;
+//An inventory account starts with room for that many items and grows by as many once full,
+//so a player doesn't pay the rent of 200 items upfront
+pub const INVENTORY_GROWTH: usize = 10;
 pub const DEFAULT_CRITICAL_CHANCE_IN_PERCENT: u16 = 200;
 pub const DEFAULT_MAGIC_FIND_IN_PERCENT: u16 = 1000;
+//Set bonuses (same scale as the chances above, 100 = 1%) for equipped pieces sharing a feature or rarity
+pub const SET_BONUS_TWO_PIECES: u16 = 100;
+pub const SET_BONUS_THREE_PIECES: u16 = 300;
+//Items a chest tier can give, bounds the accounts and compute of opening a chest
+pub const MAX_ITEMS_PER_CHEST: usize = 5;
+//Chests open_chests takes at once, each one can load and write the inventory MAX_ITEMS_PER_CHEST + 2 times
+pub const MAX_CHESTS_PER_BATCH: usize = 4;
 
 //NFT related
 pub const NFT_MINT_DESCRIPTION: &str = "LC";
@@ -32,9 +56,10 @@ pub const ACTION_LOOT_INDEX: usize = 0;
 pub const ACTION_SPELL_INDEX: usize = 1;
 pub const ACTION_MOVE_INDEX: usize = 2;
 pub const ACTION_CRAFT_INDEX: usize = 3;
+//Not in the action order, only tells the random numbers of the reforge apart from the others
+pub const ACTION_REFORGE_INDEX: usize = 4;
 
 //Pub keys
 pub const LADA_MINT_PUBKEY: &str = "95bzgMCtKw2dwaWufV9iZyu64DQo1eqw6QWnFMUSnsuF";
 pub const LADA_ACCOUNT_PUBKEY: &str = "21XuJ9PZos9xYChGfVC4T9YKENc3UommR15qB7T6k6nN";
-pub const GAME_CREATOR_AUTHORITY_PUBKEY: &str = "LCHZ3weMDr4ukhoEGHFMZGT4edLRN79L7foRMyTczA5";
 pub const NFT_CREATOR_SPLITTER_PUBKEY: &str = "4HAz1eNba28njBhWKeVRUUn4tSobY1rNPP6MdUwMoBpa";
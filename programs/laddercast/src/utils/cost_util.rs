@@ -0,0 +1,53 @@
+use crate::GameRules;
+use crate::utils::{ItemRarity, ReforgeType};
+
+/// Resources of the destination tile type burned to move, level is 0 based
+pub fn get_move_cost(dest_level: u8, rules: &GameRules) -> u64 {
+    //MOVE costs multiplier * level (1 based) of tile you're moving too
+    (dest_level as u64 + 1)
+        .checked_mul(rules.move_cost_multiplier as u64)
+        .unwrap()
+}
+
+/// Resources of the destination tile type burned to teleport, levels are 0 based
+pub fn get_teleport_cost(tile_level: u8, tile_column: u8, dest_level: u8, dest_column: u8, rules: &GameRules) -> u64 {
+    //TELEPORT costs the move to the destination tile, plus multiplier * (levels skipped + columns crossed),
+    //so it's never cheaper than moving there
+    let distance = (tile_level as i16 - dest_level as i16).abs() + (tile_column as i16 - dest_column as i16).abs();
+
+    (distance as u64)
+        .checked_mul(rules.teleport_cost_multiplier as u64)
+        .unwrap()
+        .checked_add(get_move_cost(dest_level, rules))
+        .unwrap()
+}
+
+/// Resources of each type burned to craft, level is 0 based
+pub fn get_crafting_cost(tile_level: u8, rules: &GameRules) -> u64 {
+    //Since 0 based, gotta add 1
+    (tile_level as u64 + 1)
+        .checked_mul(rules.crafting_cost_multiplier as u64)
+        .unwrap()
+}
+
+/// Resources of each type burned to reforge an equipment, level is the item level (1 based)
+pub fn get_reforge_cost(item_level: u8, item_rarity: ItemRarity, reforge_type: ReforgeType, rules: &GameRules) -> u64 {
+    let rarity_multiplier: u64 = match item_rarity {
+        ItemRarity::Common => 1,
+        ItemRarity::Rare => 2,
+        ItemRarity::Epic => 3,
+        ItemRarity::Legendary => 4,
+    };
+
+    //Changing the feature costs twice as much as only rerolling the value
+    let reforge_multiplier: u64 = match reforge_type {
+        ReforgeType::Value => 1,
+        ReforgeType::Feature => 2,
+    };
+
+    (item_level as u64)
+        .checked_mul(rules.crafting_cost_multiplier as u64)
+        .unwrap()
+        .checked_mul(rarity_multiplier * reforge_multiplier)
+        .unwrap()
+}
@@ -0,0 +1,46 @@
+use crate::utils::{CraftingSnapshot, ItemRarity, MAX_LEVEL_1_BASED, RandomGenerator};
+
+/// Level and rarity of a crafted item, starts from the lowest of the items burned
+pub fn get_crafted_item_level_and_rarity(
+    crafting_snapshot: &CraftingSnapshot,
+    is_extra_level_bonus: bool,
+    craft_upgrade_chance: u16,
+    rand: &mut RandomGenerator,
+) -> (u8, ItemRarity) {
+    //Item level or rarity has a 10% chance of going up
+    let mut new_item_level = crafting_snapshot.min_level;
+    let mut new_item_rarity = crafting_snapshot.min_rarity;
+
+    //If you have a spell that increases the level, it defaults to common for rarity
+    if is_extra_level_bonus && crafting_snapshot.min_level < MAX_LEVEL_1_BASED {
+        new_item_level += 1;
+        new_item_rarity = ItemRarity::Common;
+    } else if rand.random_within_range::<u8, 1>(0, 10) == 5
+        //Set bonus, rolled only when there is one
+        || (craft_upgrade_chance > 0 && rand.is_rolled_within(craft_upgrade_chance))
+    {
+        if rand.random_within_range::<u8, 1>(1, 2) == 1
+            && crafting_snapshot.min_level < MAX_LEVEL_1_BASED
+        {
+            new_item_level += 1;
+            new_item_rarity = ItemRarity::Common;
+        } else {
+            match new_item_rarity {
+                ItemRarity::Common => {
+                    new_item_rarity = ItemRarity::Rare;
+                }
+                ItemRarity::Rare => {
+                    new_item_rarity = ItemRarity::Epic;
+                }
+                ItemRarity::Epic => {
+                    if crafting_snapshot.max_rarity == ItemRarity::Legendary {
+                        new_item_rarity = ItemRarity::Legendary;
+                    }
+                }
+                ItemRarity::Legendary => {}
+            }
+        }
+    }
+
+    (new_item_level, new_item_rarity)
+}
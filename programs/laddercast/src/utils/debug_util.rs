@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Debug instructions (free resources, LADA, items, tile changes) only exist when the program
+/// is built with the "debug" feature, anything else reaching the fallback is an unknown instruction
+#[cfg(feature = "debug")]
+pub fn dispatch_debug_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::test_helper::dispatch_debug(program_id, accounts, data)
+}
+
+/// Same errors as anchor gives without a fallback
+#[cfg(not(feature = "debug"))]
+pub fn dispatch_debug_instruction(_program_id: &Pubkey, _accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.len() < 8 {
+        return Err(anchor_lang::__private::ErrorCode::InstructionMissing.into());
+    }
+
+    Err(anchor_lang::__private::ErrorCode::InstructionFallbackNotFound.into())
+}
@@ -10,6 +10,9 @@ pub enum ItemType {
     Chest {
         /// Between 1 to 4 based on level of tile where found 1-5 tier 1, 6-10 tier 2, 11-15 tier 3, 16-30 tier 4
         tier: u8,
+        /// Rolled when the chest is looted, its items are rolled from it. Chests looted before it
+        /// existed read 0 (the bytes after the tier were zeroed) and roll from their key instead
+        seed: u32,
     },
     Equipment {
         feature: ItemFeature,
@@ -47,6 +50,11 @@ impl fmt::Display for ItemType {
     }
 }
 
+impl ItemType {
+    /// Largest Borsh encoding (spell book): variant index, then 3 enums (1 byte each) and 2 u16
+    pub const SIZE: usize = 1 + 1 + 1 + 1 + 2 + 2;
+}
+
 impl Default for ItemType {
     fn default() -> Self {
         ItemType::Zombie
@@ -103,6 +111,17 @@ pub enum SpellType {
     Item,
 }
 
+/// What reforge_item rerolls on an equipment
+#[derive(
+AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy
+)]
+pub enum ReforgeType {
+    /// Value within the band of its rarity and level
+    Value,
+    /// Feature, and the value for the new feature
+    Feature,
+}
+
 #[derive(
 AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy
 )]
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction;
 
-use crate::account::{Game, Item, Player};
-use crate::utils::{EquipmentType, ItemFeature, ItemRarity, ItemType, RandomGenerator, SpellType};
+use crate::account::Item;
+use crate::error::ErrorCode;
+use crate::utils::{EARTH_INDEX, EquipmentType, FIRE_INDEX, ItemFeature, ItemRarity, ItemType, RandomGenerator, ReforgeType, SALVAGE_VALUE_PER_LEVEL, SpellType, WATER_INDEX};
 
 pub fn get_item_resource_value(
     item_rarity: ItemRarity,
@@ -107,64 +110,105 @@ pub fn get_item_rarity(rand: &mut RandomGenerator) -> ItemRarity {
     }
 }
 
-pub fn generate_new_equipment(
-    item: &mut Account<Item>,
-    game: &Account<Game>,
-    player: &Account<Player>,
+fn get_item_value(item_feature: ItemFeature, item_rarity: ItemRarity, item_level: u8, rand: &mut RandomGenerator) -> u16 {
+    match item_feature {
+        ItemFeature::Fire | ItemFeature::Water | ItemFeature::Earth => {
+            get_item_resource_value(item_rarity, item_level, rand)
+        }
+        ItemFeature::Power | ItemFeature::Magic => {
+            get_item_percentage_value(item_rarity, item_level, rand)
+        }
+    }
+}
+
+/// Equipment with its value (and feature for a feature reforge) rerolled, rarity and type are kept
+pub fn reforge_equipment(
+    item_type: ItemType,
     item_level: u8,
-    item_rarity: Option<ItemRarity>,
+    reforge_type: ReforgeType,
     rand: &mut RandomGenerator,
-) -> ProgramResult {
-    let item_value: u16;
-
-    let item_feature = rand.random_enum::<ItemFeature>();
-    let new_item_rarity: ItemRarity;
+) -> Result<ItemType, ProgramError> {
+    match item_type {
+        ItemType::Equipment { feature, rarity, equipment_type, .. } => {
+            let new_feature = match reforge_type {
+                ReforgeType::Value => feature,
+                ReforgeType::Feature => rand.random_enum::<ItemFeature>(),
+            };
 
-    match item_rarity {
-        None => {
-            new_item_rarity = get_item_rarity(rand);
-        }
-        Some(item_rarity) => {
-            new_item_rarity = item_rarity;
+            Ok(ItemType::Equipment {
+                feature: new_feature,
+                rarity,
+                equipment_type,
+                value: get_item_value(new_feature, rarity, item_level, rand),
+            })
         }
+        _ => Err(ErrorCode::InvalidItemType.into()),
     }
+}
 
-    match item_feature {
-        ItemFeature::Fire | ItemFeature::Water | ItemFeature::Earth => {
-            item_value = get_item_resource_value(new_item_rarity, item_level, rand);
-        }
-        ItemFeature::Power | ItemFeature::Magic => {
-            item_value = get_item_percentage_value(new_item_rarity, item_level, rand);
+/// Resources given back when salvaging an item, indexed with FIRE_INDEX, WATER_INDEX and EARTH_INDEX,
+/// None if the item can't be salvaged
+pub fn get_salvage_value(item_type: &ItemType, level: u8) -> Option<[u64; 3]> {
+    let (feature, rarity) = match *item_type {
+        ItemType::Equipment { feature, rarity, .. } => (feature, rarity),
+        ItemType::SpellBook { cost_feature, rarity, .. } => (cost_feature, rarity),
+        _ => {
+            return None;
         }
+    };
+
+    let rarity_multiplier: u64 = match rarity {
+        ItemRarity::Common => 1,
+        ItemRarity::Rare => 2,
+        ItemRarity::Epic => 4,
+        ItemRarity::Legendary => 8,
+    };
+
+    let amount = level as u64 * rarity_multiplier * SALVAGE_VALUE_PER_LEVEL;
+    let mut resources = [0; 3];
+
+    match feature {
+        ItemFeature::Fire => resources[FIRE_INDEX] = amount,
+        ItemFeature::Water => resources[WATER_INDEX] = amount,
+        ItemFeature::Earth => resources[EARTH_INDEX] = amount,
+        //Not tied to a resource, the amount is split between the three
+        ItemFeature::Power | ItemFeature::Magic => resources = [amount / 3; 3],
     }
 
-    item.game = game.key();
-    item.owner = player.key();
-    item.equipped_owner = None;
-    item.item_type = ItemType::Equipment {
+    Some(resources)
+}
+
+/// Equipment of a given level, its rarity is rolled if not given
+pub fn roll_equipment(item_level: u8, item_rarity: Option<ItemRarity>, rand: &mut RandomGenerator) -> ItemType {
+    let item_feature = rand.random_enum::<ItemFeature>();
+
+    let new_item_rarity = match item_rarity {
+        None => get_item_rarity(rand),
+        Some(item_rarity) => item_rarity,
+    };
+
+    let item_value = get_item_value(item_feature, new_item_rarity, item_level, rand);
+
+    ItemType::Equipment {
         feature: item_feature,
         rarity: new_item_rarity,
         equipment_type: rand.random_enum::<EquipmentType>(),
         value: item_value,
-    };
-    item.level = item_level;
-    Ok(())
+    }
 }
 
-pub fn generate_new_spell_book(
-    item: &mut Account<Item>,
-    game: &Account<Game>,
-    player: &Account<Player>,
-    item_level: u8,
-    rand: &mut RandomGenerator,
-) -> ProgramResult {
+/// Spell book of a given level, its rarity is rolled if not given
+pub fn roll_spell_book(item_level: u8, item_rarity: Option<ItemRarity>, rand: &mut RandomGenerator) -> ItemType {
     let spell = rand.random_enum::<SpellType>();
     let mut item_value: u16 = 0;
 
     let cost: u16 = 5 * item_level as u16;
     let cost_feature: ItemFeature = rand.random_enum_within_range::<ItemFeature>(2, 4);
 
-    let spell_book_rarity = get_item_rarity(rand);
+    let spell_book_rarity = match item_rarity {
+        None => get_item_rarity(rand),
+        Some(item_rarity) => item_rarity,
+    };
 
     match spell {
         //These values are based on spell book level and spell book rarity
@@ -177,18 +221,13 @@ pub fn generate_new_spell_book(
         _ => {}
     }
 
-    item.game = game.key();
-    item.owner = player.key();
-    item.equipped_owner = None;
-    item.item_type = ItemType::SpellBook {
+    ItemType::SpellBook {
         spell,
         cost_feature,
         cost,
         value: item_value,
         rarity: spell_book_rarity,
-    };
-    item.level = item_level;
-    Ok(())
+    }
 }
 
 pub fn zombify_account(
@@ -211,6 +250,33 @@ pub fn zombify_account(
     Ok(())
 }
 
+/// Creates an item account (new keypair signing the transaction) once it's known an item goes
+/// in it, instead of creating it upfront and zombifying it when nothing was found
+pub fn init_item_account<'info>(
+    item_info: &AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<Account<'info, Item>, ProgramError> {
+    if !item_info.is_signer || item_info.lamports() != 0 || !item_info.data_is_empty() {
+        return Err(ErrorCode::InvalidItemAccount.into());
+    }
+
+    invoke(
+        &system_instruction::create_account(
+            &payer.key(),
+            &item_info.key(),
+            Rent::get()?.minimum_balance(Item::SIZE),
+            Item::SIZE as u64,
+            program_id,
+        ),
+        &[payer, item_info.clone(), system_program],
+    )?;
+
+    //Data is zeroed, same as what init does before the handler fills it
+    Account::try_from_unchecked(item_info)
+}
+
 pub fn get_name_for_mint(item_type: &ItemType) -> Option<String> {
     match item_type {
         ItemType::Equipment {
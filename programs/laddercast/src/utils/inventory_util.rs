@@ -0,0 +1,236 @@
+use std::cmp::min;
+
+use anchor_lang::Discriminator;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_lang::solana_program::keccak::hashv;
+
+use crate::account::{Inventory, Item};
+use crate::error::ErrorCode;
+use crate::utils::{init_item_account, INVENTORY_CAPACITY, INVENTORY_GROWTH, ItemType, zombify_account};
+
+/// Item read from a legacy item account or from the player's inventory
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ItemRecord {
+    /// Item account, or the key derived from the inventory and the item id
+    pub key: Pubkey,
+    /// Set if the item is in the inventory
+    pub id: Option<u32>,
+    pub level: u8,
+    pub item_type: ItemType,
+    /// Caster
+    pub equipped_owner: Option<Pubkey>,
+}
+
+/// Key an inventory item is referred to by (caster modifiers, chest randomness), same role as an item account key
+pub fn get_inventory_item_key(inventory: &Pubkey, id: u32) -> Pubkey {
+    Pubkey::new_from_array(hashv(&[b"inventory_item", inventory.as_ref(), &id.to_le_bytes()]).to_bytes())
+}
+
+pub fn is_inventory(info: &AccountInfo, program_id: &Pubkey) -> bool {
+    info.owner == program_id
+        && info
+        .try_borrow_data()
+        .map_or(false, |data| data.len() >= 8 && data[..8] == Inventory::discriminator())
+}
+
+pub fn load_inventory<'info>(
+    info: &AccountInfo<'info>,
+    player: &Pubkey,
+) -> Result<Account<'info, Inventory>, ProgramError> {
+    let inventory: Account<Inventory> = Account::try_from(info)?;
+
+    if inventory.player != *player {
+        return Err(ErrorCode::InvalidInventory.into());
+    }
+
+    Ok(inventory)
+}
+
+/// Reads a legacy item account if item_id is None, else the item with that id of the inventory passed
+pub fn load_item(
+    info: &AccountInfo,
+    item_id: Option<u32>,
+    game: &Pubkey,
+    player: &Pubkey,
+) -> Result<ItemRecord, ProgramError> {
+    match item_id {
+        None => {
+            let item: Account<Item> = Account::try_from(info)?;
+
+            if item.game != *game || item.owner != *player {
+                return Err(ErrorCode::ItemNotExists.into());
+            }
+
+            Ok(ItemRecord {
+                key: info.key(),
+                id: None,
+                level: item.level,
+                item_type: item.item_type,
+                equipped_owner: item.equipped_owner,
+            })
+        }
+        Some(id) => {
+            let inventory = load_inventory(info, player)?;
+            let item = inventory.get_item(id)?;
+
+            Ok(ItemRecord {
+                key: get_inventory_item_key(&info.key(), id),
+                id: Some(id),
+                level: item.level,
+                item_type: item.item_type,
+                equipped_owner: item.equipped_owner,
+            })
+        }
+    }
+}
+
+/// Same as load_item, for an item only known by its key (caster modifiers)
+pub fn load_item_by_key(
+    info: &AccountInfo,
+    key: Pubkey,
+    game: &Pubkey,
+    player: &Pubkey,
+) -> Result<ItemRecord, ProgramError> {
+    if info.key() == key {
+        return load_item(info, None, game, player);
+    }
+
+    let inventory = load_inventory(info, player)?;
+
+    match inventory
+        .items
+        .iter()
+        .find(|item| get_inventory_item_key(&info.key(), item.id) == key)
+    {
+        None => Err(ErrorCode::ItemNotExists.into()),
+        Some(item) => load_item(info, Some(item.id), game, player),
+    }
+}
+
+pub fn set_item_equipped_owner(
+    info: &AccountInfo,
+    item_id: Option<u32>,
+    equipped_owner: Option<Pubkey>,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    match item_id {
+        None => {
+            let mut item: Account<Item> = Account::try_from(info)?;
+            item.equipped_owner = equipped_owner;
+            item.exit(program_id)
+        }
+        Some(id) => {
+            let mut inventory: Account<Inventory> = Account::try_from(info)?;
+            inventory.get_item_mut(id)?.equipped_owner = equipped_owner;
+            inventory.exit(program_id)
+        }
+    }
+}
+
+pub fn set_item_type(
+    info: &AccountInfo,
+    item_id: Option<u32>,
+    item_type: ItemType,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    match item_id {
+        None => {
+            let mut item: Account<Item> = Account::try_from(info)?;
+            item.item_type = item_type;
+            item.exit(program_id)
+        }
+        Some(id) => {
+            let mut inventory: Account<Inventory> = Account::try_from(info)?;
+            inventory.get_item_mut(id)?.item_type = item_type;
+            inventory.exit(program_id)
+        }
+    }
+}
+
+/// Zombifies a legacy item account or takes the item out of the inventory
+pub fn remove_item<'info>(
+    info: &AccountInfo<'info>,
+    item_id: Option<u32>,
+    authority: AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    match item_id {
+        None => {
+            let mut item: Account<Item> = Account::try_from(info)?;
+            zombify_account(&mut item, authority, program_id)
+        }
+        Some(id) => {
+            let mut inventory: Account<Inventory> = Account::try_from(info)?;
+            inventory.remove_item(id)?;
+            inventory.exit(program_id)
+        }
+    }
+}
+
+/// Adds a new item to the player's inventory if that's the account passed,
+/// else creates the item account (new keypair signing the transaction)
+pub fn store_item<'info>(
+    info: &AccountInfo<'info>,
+    item: Item,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if is_inventory(info, program_id) {
+        let mut inventory = load_inventory(info, &item.owner)?;
+        inventory.add_item(item.level, item.item_type)?;
+        grow_inventory_if_full(&inventory, &payer, &system_program)?;
+        return inventory.exit(program_id);
+    }
+
+    let mut item_account = init_item_account(info, payer, system_program, program_id)?;
+
+    item_account.game = item.game;
+    item_account.owner = item.owner;
+    item_account.level = item.level;
+    item_account.item_type = item.item_type;
+    item_account.equipped_owner = None;
+
+    item_account.exit(program_id)
+}
+
+/// Makes room for the items of the inventory before it's written, INVENTORY_GROWTH more at a time
+pub fn grow_inventory_if_full<'info>(
+    inventory: &Account<'info, Inventory>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    let info = inventory.to_account_info();
+    let items = inventory.items.len();
+
+    if info.data_len() >= Inventory::space(items) {
+        return Ok(());
+    }
+
+    grow_account(
+        &info,
+        Inventory::space(min(items + INVENTORY_GROWTH, INVENTORY_CAPACITY)),
+        payer,
+        system_program,
+    )
+}
+
+/// Reallocates the account to the given size, payer tops up the rent
+pub fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    size: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    let minimum_balance = Rent::get()?.minimum_balance(size);
+
+    if info.lamports() < minimum_balance {
+        invoke(
+            &system_instruction::transfer(payer.key, info.key, minimum_balance - info.lamports()),
+            &[payer.clone(), info.clone(), system_program.clone()],
+        )?;
+    }
+
+    info.realloc(size, true)
+}
@@ -0,0 +1,106 @@
+use strum::IntoEnumIterator;
+
+use crate::{ChestTier, GameRules, PlayerBonuses, Pubkey, TileType};
+use crate::utils::{ChestEntropy, ItemRarity, ItemType, RandomGenerator, roll_equipment, roll_spell_book};
+
+/// Range (both included) of resources looted on a resource tile, level is 0 based
+pub fn get_loot_range(tile_level: u8, tile_type: TileType, player_bonuses: &PlayerBonuses) -> (u64, u64) {
+    let range_min_resource: u64 = 1;
+    let mut range_max_resource: u64 = 10 * (tile_level + 1) as u64; // +1 since 0 based
+
+    match tile_type {
+        TileType::Earth => {
+            range_max_resource += player_bonuses.earth_chance as u64;
+        }
+        TileType::Fire => {
+            range_max_resource += player_bonuses.fire_chance as u64;
+        }
+        TileType::Water => {
+            range_max_resource += player_bonuses.water_chance as u64;
+        }
+        _ => {}
+    }
+
+    (range_min_resource, range_max_resource)
+}
+
+/// Resources looted, doubled on a critical
+pub fn roll_loot_resources(
+    tile_level: u8,
+    tile_type: TileType,
+    player_bonuses: &PlayerBonuses,
+    rand: &mut RandomGenerator,
+) -> u64 {
+    let (range_min_resource, range_max_resource) = get_loot_range(tile_level, tile_type, player_bonuses);
+
+    let mut number_of_resources_given = rand.random_within_range::<u64, 8>(range_min_resource, range_max_resource);
+
+    if rand.is_rolled_within(player_bonuses.critical_chance) {
+        number_of_resources_given *= 2;
+    }
+
+    number_of_resources_given
+}
+
+pub fn is_chest_found(rules: &GameRules, player_bonuses: &PlayerBonuses, rand: &mut RandomGenerator) -> bool {
+    //default is 10% so 1000 since we work in % (to not have floating)
+    let magic_find_chance = rules.default_magic_find_in_percent + player_bonuses.magic_find_chance;
+
+    rand.is_rolled_within(magic_find_chance)
+}
+
+pub fn get_chest_tier(tile_level: u8) -> u8 {
+    match tile_level {
+        0..=4 => 1,
+        5..=9 => 2,
+        10..=14 => 3,
+        15..=30 => 4, //29 should be the max tile level since 0 based
+        _ => 1,
+    }
+}
+
+/// Config of a chest tier, an unknown tier is treated as tier 1
+pub fn get_chest_tier_rules(rules: &GameRules, tier: u8) -> &ChestTier {
+    match tier {
+        1..=4 => &rules.chest_tiers[tier as usize - 1],
+        _ => &rules.chest_tiers[0],
+    }
+}
+
+pub fn get_chest_item_rarity(chest_tier: &ChestTier, rand: &mut RandomGenerator) -> ItemRarity {
+    ItemRarity::iter()
+        .nth(rand.random_weighted_index(&chest_tier.rarity_weights))
+        .unwrap()
+}
+
+pub fn is_chest_item_spell_book(chest_tier: &ChestTier, rand: &mut RandomGenerator) -> bool {
+    rand.random_within_range::<u8, 1>(1, 100) <= chest_tier.spell_book_chance
+}
+
+/// Level and type of the items in a chest, levels go from the min item level of the tier to the chest level
+pub fn roll_chest_contents(chest_tier: &ChestTier, chest_level: u8, rand: &mut RandomGenerator) -> Vec<(u8, ItemType)> {
+    let max_item_level = chest_level;
+    let min_item_level = chest_tier.min_item_level.min(max_item_level);
+
+    (0..chest_tier.item_count)
+        .map(|_| {
+            let item_level = rand.random_within_range::<u8, 1>(min_item_level, max_item_level);
+            let item_rarity = get_chest_item_rarity(chest_tier, rand);
+
+            let item_type = if is_chest_item_spell_book(chest_tier, rand) {
+                roll_spell_book(item_level, Some(item_rarity), rand)
+            } else {
+                roll_equipment(item_level, Some(item_rarity), rand)
+            };
+
+            (item_level, item_type)
+        })
+        .collect()
+}
+
+/// Items a looted chest gives when opened, only from what was sealed in it (tier, seed) and its key
+pub fn roll_sealed_chest_contents(rules: &GameRules, chest_key: Pubkey, chest_level: u8, tier: u8, seed: u32) -> Vec<(u8, ItemType)> {
+    let mut rand = RandomGenerator::from_entropy(&ChestEntropy { seed, pubkey: chest_key });
+
+    roll_chest_contents(get_chest_tier_rules(rules, tier), chest_level, &mut rand)
+}
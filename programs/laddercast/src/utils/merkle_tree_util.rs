@@ -114,7 +114,7 @@ pub fn get_merkle_string_for_item(uri: &str, item: Item) -> Option<String> {
         ItemType::SpellBook { spell, cost_feature, rarity, cost, value } => {
             Some(get_merkle_string_for_spell_book(uri, item, spell, cost_feature, rarity, cost, value))
         }
-        ItemType::Chest { tier } => {
+        ItemType::Chest { tier, .. } => {
             Some(get_merkle_string_for_chest(uri, item, tier))
         }
         ItemType::Equipment { feature, rarity, equipment_type, value } => {
@@ -0,0 +1,121 @@
+use core::mem::size_of;
+
+use anchor_lang::{AccountSerialize, Discriminator};
+use anchor_lang::prelude::*;
+
+use crate::{GameTurnInfo, Tile, TileType};
+use crate::account::{Game, TurnData};
+use crate::error::ErrorCode;
+use crate::utils::grow_account;
+
+//Games created before the map dimensions were chosen per game are all 30 x 3
+pub const LEGACY_MAP_LEVELS: u8 = 30;
+pub const LEGACY_MAP_COLUMNS: u8 = 3;
+
+/// Game layout before per-game map dimensions, only read to migrate the account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct LegacyGame {
+    pub authority: Pubkey,
+    pub map: [[Option<Tile>; 3]; 30],
+    pub turn_info: GameTurnInfo,
+    pub last_turn_added: u32,
+    pub signer_bump: u8,
+    pub resource_1_mint_account: Pubkey,
+    pub resource_2_mint_account: Pubkey,
+    pub resource_3_mint_account: Pubkey,
+    pub lada_mint_account: Pubkey,
+    pub lada_token_account: Pubkey,
+}
+
+impl LegacyGame {
+    pub fn into_game(self) -> Game {
+        Game {
+            authority: self.authority,
+            levels: LEGACY_MAP_LEVELS,
+            columns: LEGACY_MAP_COLUMNS,
+            map: self.map.iter().map(|level| level.to_vec()).collect(),
+            turn_info: self.turn_info,
+            last_turn_added: self.last_turn_added,
+            signer_bump: self.signer_bump,
+            resource_1_mint_account: self.resource_1_mint_account,
+            resource_2_mint_account: self.resource_2_mint_account,
+            resource_3_mint_account: self.resource_3_mint_account,
+            lada_mint_account: self.lada_mint_account,
+            lada_token_account: self.lada_token_account,
+        }
+    }
+}
+
+/// Turn data layout before per-game map dimensions, only read to migrate the account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct LegacyTurnData {
+    pub bump: u8,
+    pub resource_1_burned: u64,
+    pub resource_2_burned: u64,
+    pub resource_3_burned: u64,
+    pub map: [[Option<TileType>; 3]; 30],
+}
+
+impl LegacyTurnData {
+    /// Turn data accounts were created with exactly that size
+    pub const SIZE: usize = 8 + 1 + 8 + 8 + 8 + 90 * (1 + size_of::<TileType>());
+
+    /// No secret was committed for legacy turns so the entropy stays empty,
+    /// payer is the one paying the rent of the bigger account (and getting it back on close)
+    pub fn into_turn_data(self, payer: Pubkey) -> TurnData {
+        TurnData {
+            bump: self.bump,
+            payer,
+            resource_1_burned: self.resource_1_burned,
+            resource_2_burned: self.resource_2_burned,
+            resource_3_burned: self.resource_3_burned,
+            map: self.map.iter().map(|level| level.to_vec()).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Reads an account of this program written with an older layout, the discriminator is the one of the account type
+pub fn read_legacy_account<T: AnchorDeserialize>(
+    info: &AccountInfo,
+    discriminator: [u8; 8],
+    program_id: &Pubkey,
+) -> Result<T, ProgramError> {
+    let data = info.try_borrow_data()?;
+
+    if info.owner != program_id || data.len() < 8 || data[..8] != discriminator {
+        return Err(ErrorCode::InvalidLegacyAccount.into());
+    }
+
+    T::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::InvalidLegacyAccount.into())
+}
+
+pub fn read_legacy_game(info: &AccountInfo, program_id: &Pubkey) -> Result<LegacyGame, ProgramError> {
+    read_legacy_account(info, Game::discriminator(), program_id)
+}
+
+pub fn read_legacy_turn_data(info: &AccountInfo, program_id: &Pubkey) -> Result<LegacyTurnData, ProgramError> {
+    //The size is what tells a legacy turn data apart, the legacy map always fits the same number of bytes
+    if info.data_len() != LegacyTurnData::SIZE {
+        return Err(ErrorCode::InvalidLegacyAccount.into());
+    }
+
+    read_legacy_account(info, TurnData::discriminator(), program_id)
+}
+
+/// Writes the account in its new layout, growing it first if needed (payer tops up the rent)
+pub fn write_migrated_account<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    account: &T,
+    size: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    if info.data_len() < size {
+        grow_account(info, size, payer, system_program)?;
+    }
+
+    let mut data = info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    account.try_serialize(&mut writer)
+}
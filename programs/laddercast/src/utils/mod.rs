@@ -1,12 +1,19 @@
 pub use accounts::*;
 pub use caster_util::*;
 pub use constants::*;
+pub use cost_util::*;
+pub use crafting_util::*;
+pub use debug_util::*;
 pub use enums::*;
 pub use equipment_util::*;
 pub use instruction_sysvar_util::*;
+pub use inventory_util::*;
+pub use loot_util::*;
 pub use merkle_tree_util::*;
+pub use migration_util::*;
 pub use player_util::*;
 pub use random_util::*;
+pub use redeem_util::*;
 pub use tests::*;
 pub use tile_util::*;
 pub use vector_util::*;
@@ -22,4 +29,11 @@ pub mod vector_util;
 pub mod player_util;
 pub mod accounts;
 pub mod merkle_tree_util;
-pub mod instruction_sysvar_util;
\ No newline at end of file
+pub mod instruction_sysvar_util;
+pub mod debug_util;
+pub mod crafting_util;
+pub mod cost_util;
+pub mod loot_util;
+pub mod inventory_util;
+pub mod migration_util;
+pub mod redeem_util;
@@ -1,67 +1,171 @@
-use anchor_lang::{Key, ToAccountInfo};
-use anchor_lang::prelude::Account;
-use anchor_lang::prelude::ProgramError;
+use anchor_lang::Key;
+use anchor_lang::prelude::{Account, AccountInfo, ProgramError, Pubkey};
 
 use crate::{ItemFeature, ItemType, PlayerBonuses};
-use crate::account::{Caster, Game, Item, Player};
-use crate::utils::{DEFAULT_CRITICAL_CHANCE_IN_PERCENT, EquipmentType, Modifiers};
+use crate::account::{Inventory, Item};
+use crate::error::ErrorCode;
+use crate::utils::{EquipmentType, get_inventory_item_key, ItemRarity, ItemRecord, Modifiers, SET_BONUS_THREE_PIECES, SET_BONUS_TWO_PIECES};
 
+/// Item equipped in a caster slot (key from the caster modifiers), read from the item account or the inventory passed
+pub fn load_equipped_item(
+    info: &AccountInfo,
+    key: Pubkey,
+    game: &Pubkey,
+    player: &Pubkey,
+) -> Result<ItemRecord, ProgramError> {
+    if info.key() == key {
+        let item: Account<Item> = Account::try_from(info)?;
+
+        if item.game != *game {
+            return Err(ErrorCode::EquippedItemWrongGame.into());
+        }
+
+        if item.owner != *player {
+            return Err(ErrorCode::EquippedItemWrongOwner.into());
+        }
+
+        return Ok(ItemRecord {
+            key,
+            id: None,
+            level: item.level,
+            item_type: item.item_type,
+            equipped_owner: item.equipped_owner,
+        });
+    }
+
+    let inventory: Account<Inventory> =
+        Account::try_from(info).map_err(|_| ErrorCode::EquippedItemAccountMismatch)?;
+
+    if inventory.player != *player {
+        return Err(ErrorCode::EquippedItemWrongOwner.into());
+    }
+
+    match inventory
+        .items
+        .iter()
+        .find(|item| get_inventory_item_key(&info.key(), item.id) == key)
+    {
+        None => Err(ErrorCode::EquippedItemAccountMismatch.into()),
+        Some(item) => Ok(ItemRecord {
+            key,
+            id: Some(item.id),
+            level: item.level,
+            item_type: item.item_type,
+            equipped_owner: item.equipped_owner,
+        }),
+    }
+}
+
+/// Items equipped in the head, robe and staff slots of the caster, each read from the account passed for its slot,
+/// an account is only read if something is equipped in its slot
+pub fn load_equipped_items(
+    head: &AccountInfo,
+    robe: &AccountInfo,
+    staff: &AccountInfo,
+    modifiers: &Modifiers,
+    game: &Pubkey,
+    player: &Pubkey,
+) -> Result<Vec<ItemRecord>, ProgramError> {
+    let mut equipped_items = vec![];
+
+    for (info, key) in [(head, modifiers.head), (robe, modifiers.robe), (staff, modifiers.staff)] {
+        if let Some(key) = key {
+            equipped_items.push(load_equipped_item(info, key, game, player)?);
+        }
+    }
+
+    Ok(equipped_items)
+}
+
+fn get_equipped_key(modifiers: &Modifiers, equipment_type: EquipmentType) -> Option<Pubkey> {
+    match equipment_type {
+        EquipmentType::Head => modifiers.head,
+        EquipmentType::Robe => modifiers.robe,
+        EquipmentType::Staff => modifiers.staff,
+    }
+}
+
+/// Bonuses of the items equipped by the caster, every item of the caster modifiers has to be provided
 pub fn get_player_bonuses(
     modifiers: &Modifiers,
-    item_accounts: Vec<&Result<Account<Item>, ProgramError>>,
-    game: &Account<Game>,
-    player: &Account<Player>,
-    caster: &Account<Caster>,
-) -> PlayerBonuses {
+    items: &[ItemRecord],
+    caster: &Pubkey,
+    default_critical_chance: u16,
+) -> Result<PlayerBonuses, ProgramError> {
     let mut player_bonuses: PlayerBonuses = PlayerBonuses {
-        critical_chance: DEFAULT_CRITICAL_CHANCE_IN_PERCENT, //By default they have 2% chance of critical strike
+        critical_chance: default_critical_chance, //By default they have 2% chance of critical strike
         magic_find_chance: 0,
         fire_chance: 0,
         water_chance: 0,
         earth_chance: 0,
+        craft_upgrade_chance: 0,
     };
 
-    for item in item_accounts {
-        match item {
-            Ok(equipment) => {
-                if equipment.owner != player.to_account_info().key()
-                    || equipment.equipped_owner.unwrap() != caster.to_account_info().key()
-                    || equipment.game != game.to_account_info().key()
-                {
-                    continue;
+    let mut equipped_pieces: Vec<(ItemFeature, ItemRarity)> = vec![];
+
+    for key in [modifiers.head, modifiers.robe, modifiers.staff].iter().flatten() {
+        let equipment = match items.iter().find(|item| item.key == *key) {
+            None => return Err(ErrorCode::EquippedItemAccountMismatch.into()),
+            Some(equipment) => equipment,
+        };
+
+        if equipment.equipped_owner != Some(*caster) {
+            return Err(ErrorCode::ItemNotEquippedByCaster.into());
+        }
+
+        match equipment.item_type {
+            ItemType::Equipment { feature, rarity, value, equipment_type } => {
+                //Item has to be in the slot of its equipment type
+                if get_equipped_key(modifiers, equipment_type) != Some(*key) {
+                    return Err(ErrorCode::EquippedItemWrongSlot.into());
                 }
 
-                if let ItemType::Equipment { feature, value, equipment_type, .. } = equipment.item_type {
-                    match equipment_type {
-                        EquipmentType::Head => {
-                            if modifiers.head == None || modifiers.head.unwrap() != equipment.to_account_info().key() {
-                                continue;
-                            }
-                        }
-                        EquipmentType::Staff => {
-                            if modifiers.staff == None || modifiers.staff.unwrap() != equipment.to_account_info().key() {
-                                continue;
-                            }
-                        }
-                        EquipmentType::Robe => {
-                            if modifiers.robe == None || modifiers.robe.unwrap() != equipment.to_account_info().key() {
-                                continue;
-                            }
-                        }
-                    }
-
-                    match feature {
-                        ItemFeature::Fire => player_bonuses.fire_chance += value,
-                        ItemFeature::Water => player_bonuses.water_chance += value,
-                        ItemFeature::Earth => player_bonuses.earth_chance += value,
-                        ItemFeature::Magic => player_bonuses.magic_find_chance += value,
-                        ItemFeature::Power => player_bonuses.critical_chance += value,
-                    }
+                match feature {
+                    ItemFeature::Fire => player_bonuses.fire_chance += value,
+                    ItemFeature::Water => player_bonuses.water_chance += value,
+                    ItemFeature::Earth => player_bonuses.earth_chance += value,
+                    ItemFeature::Magic => player_bonuses.magic_find_chance += value,
+                    ItemFeature::Power => player_bonuses.critical_chance += value,
                 }
+
+                equipped_pieces.push((feature, rarity));
             }
-            Err(_) => {}
+            _ => return Err(ErrorCode::EquippedItemWrongSlot.into()),
         }
     }
 
-    player_bonuses
+    add_set_bonuses(&mut player_bonuses, &equipped_pieces);
+
+    Ok(player_bonuses)
+}
+
+fn get_set_bonus(matching_pieces: usize) -> u16 {
+    match matching_pieces {
+        0 | 1 => 0,
+        2 => SET_BONUS_TWO_PIECES,
+        _ => SET_BONUS_THREE_PIECES,
+    }
+}
+
+/// Pieces (feature and rarity of the equipped head, robe and staff) sharing a feature give critical chance,
+/// sharing a rarity above common give magic find and crafting upgrade chance
+pub fn add_set_bonuses(player_bonuses: &mut PlayerBonuses, equipped_pieces: &[(ItemFeature, ItemRarity)]) {
+    let matching_feature = equipped_pieces
+        .iter()
+        .map(|(feature, _)| equipped_pieces.iter().filter(|(other, _)| other == feature).count())
+        .max()
+        .unwrap_or(0);
+
+    let matching_rarity = equipped_pieces
+        .iter()
+        .filter(|(_, rarity)| *rarity != ItemRarity::Common)
+        .map(|(_, rarity)| equipped_pieces.iter().filter(|(_, other)| other == rarity).count())
+        .max()
+        .unwrap_or(0);
+
+    player_bonuses.critical_chance += get_set_bonus(matching_feature);
+
+    let rarity_set_bonus = get_set_bonus(matching_rarity);
+    player_bonuses.magic_find_chance += rarity_set_bonus;
+    player_bonuses.craft_upgrade_chance += rarity_set_bonus;
 }
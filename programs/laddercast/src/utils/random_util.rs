@@ -1,32 +1,175 @@
 use std::convert::TryInto;
 
 use anchor_lang::solana_program::keccak::hashv;
-use num_traits::Num;
+use num_traits::{Num, PrimInt};
 use strum::EnumCount;
 
 use crate::Pubkey;
 
+/// Where the seed of a RandomGenerator comes from
+pub trait EntropySource {
+    fn seed(&self) -> [u8; 32];
+}
+
+/// Most recent slot hashes mixed with a pubkey, known to anyone simulating the transaction
+pub struct SlotHashesEntropy<'a> {
+    pub slot_hashes: &'a [u8],
+    pub pubkey: Pubkey,
+}
+
+impl EntropySource for SlotHashesEntropy<'_> {
+    fn seed(&self) -> [u8; 32] {
+        hashv(&[&self.slot_hashes[8..64], &self.pubkey.to_bytes()]).to_bytes()
+    }
+}
+
+/// Secret committed (hashed) by the caster mixed with the entropy recorded for the turn, which
+/// comes from the hash of the slot the crank ending the turn landed in: neither side knows the other
+/// when the commit is done, and the cranker can't know that hash when sending the crank.
+/// Each committed action (its index in the action order) gets its own numbers
+pub struct CommitRevealEntropy {
+    pub secret: [u8; 32],
+    pub turn_entropy: [u8; 32],
+    pub pubkey: Pubkey,
+    pub action: usize,
+}
+
+impl EntropySource for CommitRevealEntropy {
+    fn seed(&self) -> [u8; 32] {
+        hashv(&[
+            &self.secret,
+            &self.turn_entropy,
+            &self.pubkey.to_bytes(),
+            &(self.action as u64).to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+/// Seed sealed in a chest when it's looted, nothing about the chest picked afterwards (key, slot
+/// it's opened in) changes its items. Chests without a seed (0) were looted before it existed,
+/// their key is mixed in instead, their account already exists so it can't be ground anymore
+pub struct ChestEntropy {
+    pub seed: u32,
+    pub pubkey: Pubkey,
+}
+
+impl EntropySource for ChestEntropy {
+    fn seed(&self) -> [u8; 32] {
+        match self.seed {
+            0 => hashv(&[&self.pubkey.to_bytes()]).to_bytes(),
+            seed => hashv(&[&seed.to_le_bytes()]).to_bytes(),
+        }
+    }
+}
+
+/// Hash committed for a secret, revealed secret has to match it
+pub fn get_commitment(secret: &[u8; 32]) -> [u8; 32] {
+    hashv(&[secret]).to_bytes()
+}
+
+/// Entropy of a given turn, so turns added by the same crank don't share one
+pub fn get_turn_entropy(entropy: &[u8; 32], turn: u32) -> [u8; 32] {
+    hashv(&[entropy, &turn.to_le_bytes()]).to_bytes()
+}
+
+/// Hash of the given slot in the slot hashes sysvar data, the sysvar only keeps the last 512 slots
+pub fn get_slot_hash(slot_hashes: &[u8], slot: u64) -> SlotHash {
+    //8 bytes of length, then the entries as (slot: u64, hash: [u8; 32]) from the most recent slot
+    let count = u64::from_le_bytes(slot_hashes[..8].try_into().unwrap()) as usize;
+    let mut entries = slot_hashes[8..]
+        .chunks_exact(40)
+        .take(count)
+        .map(|entry| (u64::from_le_bytes(entry[..8].try_into().unwrap()), entry))
+        .peekable();
+
+    match entries.peek() {
+        Some((newest_slot, _)) if *newest_slot >= slot => {}
+        _ => return SlotHash::NotHashed,
+    }
+
+    //Not in it by now, it never will be: it aged out (or no block was produced in that slot)
+    entries
+        .find(|(entry_slot, _)| *entry_slot == slot)
+        .map_or(SlotHash::Expired, |(_, entry)| SlotHash::Hashed(entry[8..].try_into().unwrap()))
+}
+
+#[derive(Clone, PartialEq, Debug, Copy)]
+pub enum SlotHash {
+    Hashed([u8; 32]),
+    NotHashed,
+    /// Nothing stands in for it, any fallback would let whoever records it pick between two values
+    Expired,
+}
+
+/// Random bytes are taken from a keccak hash of the seed, once they are all used
+/// the seed is hashed again with a counter (counter mode) so it never runs out
 pub struct RandomGenerator {
     offset: u8,
+    counter: u64,
+    seed: [u8; 32],
     hash: [u8; 32],
 }
 
 impl RandomGenerator {
     pub fn new(slot_hashes: &[u8], pubkey: Pubkey) -> Self {
+        Self::from_entropy(&SlotHashesEntropy { slot_hashes, pubkey })
+    }
+
+    pub fn from_entropy<S: EntropySource>(source: &S) -> Self {
+        let seed = source.seed();
+
         RandomGenerator {
             offset: 0,
-            hash: hashv(&[&slot_hashes[8..64], &pubkey.to_bytes()]).to_bytes(),
+            counter: 0,
+            seed,
+            hash: seed,
         }
     }
 
+    fn next_block(&mut self) {
+        self.counter += 1;
+        self.hash = hashv(&[&self.seed, &self.counter.to_le_bytes()]).to_bytes();
+        self.offset = 0;
+    }
+
     pub fn random<T: FromNE<N> + Num + Copy, const N: usize>(&mut self) -> T {
-        self.offset += 1;
-        T::from_ne_bytes(self.hash[self.offset as usize - 1..][..N].try_into().expect("Ran out of random"))
+        if self.offset as usize + N > self.hash.len() {
+            self.next_block();
+        }
+
+        let start = self.offset as usize;
+        self.offset += N as u8;
+
+        T::from_ne_bytes(self.hash[start..start + N].try_into().unwrap())
     }
 
     //Max is included as a potential number
-    pub fn random_within_range<T: FromNE<N> + Num + Copy, const N: usize>(&mut self, min: T, max: T) -> T {
-        self.random::<T, N>() % (max + T::one() - min) + min
+    pub fn random_within_range<T: FromNE<N> + PrimInt, const N: usize>(&mut self, min: T, max: T) -> T {
+        let span = max - min;
+
+        if span == T::max_value() {
+            return self.random::<T, N>();
+        }
+
+        let range = span + T::one();
+
+        //Values above the last full multiple of the range would make the lowest results more likely
+        //than the others (modulo bias), so they are drawn again
+        let limit = T::max_value() - (T::max_value() % range + T::one()) % range;
+
+        loop {
+            let value = self.random::<T, N>();
+
+            if value <= limit {
+                return value % range + min;
+            }
+        }
+    }
+
+    //Chance out of 10 000 (the % the game works in), 0 never happens and 10 000 always does
+    pub fn is_rolled_within(&mut self, chance: u16) -> bool {
+        self.random_within_range::<u16, 2>(1, 10_000) <= chance
     }
 
     pub fn random_enum<E: EnumCount + strum::IntoEnumIterator>(&mut self) -> E {
@@ -40,6 +183,22 @@ impl RandomGenerator {
 
         E::iter().nth(random_value as usize).unwrap()
     }
+
+    //Index picked proportionally to its weight, weights can't all be 0
+    pub fn random_weighted_index(&mut self, weights: &[u16]) -> usize {
+        let total: u32 = weights.iter().map(|weight| *weight as u32).sum();
+        let mut roll: u32 = self.random_within_range::<u32, 4>(0, total - 1);
+
+        for (index, weight) in weights.iter().enumerate() {
+            if roll < *weight as u32 {
+                return index;
+            }
+
+            roll -= *weight as u32;
+        }
+
+        unreachable!()
+    }
 }
 
 pub trait FromNE<const N: usize> {
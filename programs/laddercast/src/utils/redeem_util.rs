@@ -0,0 +1,282 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::Transfer;
+
+use crate::{GameRules, PlayerBonuses, TileType};
+use crate::account::{Caster, Game, Item, TurnData};
+use crate::error::ErrorCode;
+use crate::utils::{
+    CommitRevealEntropy, CommittedActions, EARTH_INDEX, FIRE_INDEX, get_chest_tier,
+    get_crafted_item_level_and_rarity, get_current_tile_feature, get_lada_reward,
+    give_exp_to_caster_resources_burned, is_chest_found, is_spell_successful, ItemType, load_item,
+    Modifiers, RandomGenerator, reforge_equipment, ReforgeSnapshot, roll_equipment,
+    roll_loot_resources, set_item_equipped_owner, set_item_type, SpellSnapshot, SpellType, TurnCommit,
+    WATER_INDEX,
+};
+
+/// Turn commit of the caster, once the turn it was committed for is over
+pub fn get_turn_commit_to_redeem(turn_commit: Option<TurnCommit>, game_turn: u32) -> Result<TurnCommit, ProgramError> {
+    match turn_commit {
+        None => Err(ErrorCode::EmptyTurnCommit.into()),
+        Some(turn_commit) if turn_commit.turn == game_turn => Err(ErrorCode::SameTurnRedeem.into()),
+        Some(turn_commit) => Ok(turn_commit),
+    }
+}
+
+/// Random numbers of one committed action, the same whether the turn is redeemed in a single
+/// instruction or one action at a time, and never the ones of another action of the turn
+pub fn get_action_random_generator(
+    secret: [u8; 32],
+    turn_data: &TurnData,
+    caster: Pubkey,
+    action: usize,
+) -> Result<RandomGenerator, ProgramError> {
+    Ok(RandomGenerator::from_entropy(&CommitRevealEntropy {
+        secret,
+        turn_entropy: turn_data.get_entropy()?,
+        pubkey: caster,
+        action,
+    }))
+}
+
+/// Resources given by looting the caster's tile, and the chest found (level, item type) if any
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LootRoll {
+    pub resource_index: usize,
+    pub amount: u64,
+    pub chest: Option<(u8, ItemType)>,
+}
+
+pub fn roll_loot(
+    map: &[Vec<Option<TileType>>],
+    modifiers: &Modifiers,
+    rules: &GameRules,
+    player_bonuses: &PlayerBonuses,
+    rand: &mut RandomGenerator,
+) -> Result<LootRoll, ProgramError> {
+    let tile_level = modifiers.tile_level;
+
+    let looted_tile_type = match get_current_tile_feature(map, tile_level, modifiers.tile_column) {
+        None => {
+            return Err(ErrorCode::TileNotExists.into());
+        }
+        Some(tile_type) => *tile_type,
+    };
+
+    let resource_index = match looted_tile_type {
+        TileType::Fire => FIRE_INDEX,
+        TileType::Water => WATER_INDEX,
+        TileType::Earth => EARTH_INDEX,
+        _ => {
+            return Err(ErrorCode::InvalidTileForLooting.into());
+        }
+    };
+
+    let amount = roll_loot_resources(tile_level, looted_tile_type, player_bonuses, rand);
+
+    let chest = match is_chest_found(rules, player_bonuses, rand) {
+        //Since 0 based, +1
+        true => Some((
+            tile_level + 1,
+            ItemType::Chest {
+                tier: get_chest_tier(tile_level),
+                seed: rand.random_within_range::<u32, 4>(1, u32::MAX),
+            },
+        )),
+        false => None,
+    };
+
+    Ok(LootRoll {
+        resource_index,
+        amount,
+        chest,
+    })
+}
+
+/// What a committed spell gives once redeemed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpellEffect {
+    /// Spell failed, or a craft spell (it only upgrades the craft of its turn)
+    Nothing,
+    Resource { resource_index: usize, amount: u64 },
+    Experience(u64),
+    /// Level and item type of the equipment given
+    Item(u8, ItemType),
+}
+
+pub fn roll_spell(spell_snapshot: &SpellSnapshot, rand: &mut RandomGenerator) -> SpellEffect {
+    let SpellSnapshot { spell, rarity, value, level } = *spell_snapshot;
+
+    if !is_spell_successful(rand, rarity) {
+        return SpellEffect::Nothing;
+    }
+
+    match spell {
+        SpellType::Fire => SpellEffect::Resource { resource_index: FIRE_INDEX, amount: value as u64 },
+        SpellType::Water => SpellEffect::Resource { resource_index: WATER_INDEX, amount: value as u64 },
+        SpellType::Earth => SpellEffect::Resource { resource_index: EARTH_INDEX, amount: value as u64 },
+        SpellType::Experience => SpellEffect::Experience(value as u64),
+        SpellType::Item => SpellEffect::Item(level, roll_equipment(level, Some(rarity), rand)),
+        _ => SpellEffect::Nothing,
+    }
+}
+
+/// Level and item type of the crafted equipment, a successful craft spell of the same turn adds a level
+pub fn roll_crafted_item(
+    actions: &CommittedActions,
+    craft_upgrade_chance: u16,
+    rand: &mut RandomGenerator,
+) -> (u8, ItemType) {
+    let is_extra_level_bonus = actions
+        .spell
+        .filter(|spell_snapshot| spell_snapshot.spell == SpellType::Craft)
+        .map_or(false, |spell_snapshot| is_spell_successful(rand, spell_snapshot.rarity));
+
+    let (new_item_level, new_item_rarity) = get_crafted_item_level_and_rarity(
+        &actions.crafting.unwrap(),
+        is_extra_level_bonus,
+        craft_upgrade_chance,
+        rand,
+    );
+
+    (new_item_level, roll_equipment(new_item_level, Some(new_item_rarity), rand))
+}
+
+/// Rerolls the item of a committed reforge and releases it, the account passed has to still hold that item
+pub fn reforge_committed_item(
+    info: &AccountInfo,
+    reforge_snapshot: &ReforgeSnapshot,
+    game: &Pubkey,
+    player: &Pubkey,
+    caster: &Pubkey,
+    rand: &mut RandomGenerator,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let item = load_item(info, reforge_snapshot.item_id, game, player)?;
+
+    //Moved to the inventory (new key) before reforged items were locked, the turn can only be cancelled
+    if item.key != reforge_snapshot.item {
+        return Err(ErrorCode::ItemNotExists.into());
+    }
+
+    set_item_type(
+        info,
+        reforge_snapshot.item_id,
+        reforge_equipment(item.item_type, item.level, reforge_snapshot.reforge_type, rand)?,
+        program_id,
+    )?;
+
+    release_reforged_item(info, reforge_snapshot, game, player, caster, program_id)
+}
+
+/// From the reforge commit until it's redeemed or cancelled, the item is locked to the caster (its equipped
+/// owner without being in a caster slot) so it can't be equipped, salvaged, crafted, minted or moved meanwhile.
+/// Items of reforges committed before the lock existed weren't locked, nothing to release then
+pub fn release_reforged_item(
+    info: &AccountInfo,
+    reforge_snapshot: &ReforgeSnapshot,
+    game: &Pubkey,
+    player: &Pubkey,
+    caster: &Pubkey,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    match load_item(info, reforge_snapshot.item_id, game, player) {
+        Ok(item) if item.key == reforge_snapshot.item && item.equipped_owner == Some(*caster) => {
+            set_item_equipped_owner(info, reforge_snapshot.item_id, None, program_id)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Item given to the player by a redeem, from its (level, item type)
+pub fn new_redeemed_item(game: Pubkey, player: Pubkey, (level, item_type): (u8, ItemType)) -> Item {
+    Item {
+        game,
+        owner: player,
+        level,
+        item_type,
+        equipped_owner: None,
+    }
+}
+
+/// Ends the caster's turn: experience for the resources burned, and the LADA its share of the turn's
+/// burn is worth. The redeem is counted so the turn's burn totals don't go down anymore (see cancel)
+pub fn settle_turn_reward(caster: &mut Caster, turn_data: &mut TurnData, rules: &GameRules) -> u64 {
+    let resources_burned = caster.turn_commit.unwrap().resources_burned;
+
+    give_exp_to_caster_resources_burned(
+        caster,
+        Some(resources_burned[FIRE_INDEX]),
+        Some(resources_burned[EARTH_INDEX]),
+        Some(resources_burned[WATER_INDEX]),
+    );
+
+    let amount = get_lada_reward(
+        turn_data.resource_1_burned + turn_data.resource_2_burned + turn_data.resource_3_burned,
+        resources_burned.iter().sum(),
+        rules.lada_distribution_per_turn,
+    );
+
+    turn_data.rewards_redeemed += 1;
+
+    //Reset caster's turn commit
+    caster.turn_commit = None;
+
+    amount
+}
+
+/// Mints resources to a player's token account, the game signer being the mint authority
+pub fn mint_game_resource<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    game_signer: AccountInfo<'info>,
+    game: &Account<'info, Game>,
+    amount: u64,
+) -> ProgramResult {
+    let game_key = game.key();
+
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program,
+            token::MintTo {
+                mint,
+                to,
+                authority: game_signer,
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
+/// Sends LADA from the game's token account, owned by the game signer
+pub fn transfer_game_lada<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    game_signer: AccountInfo<'info>,
+    game: &Account<'info, Game>,
+    amount: u64,
+) -> ProgramResult {
+    let game_key = game.key();
+
+    let seeds = &[b"game_signer".as_ref(), game_key.as_ref(), &[game.signer_bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program,
+            Transfer {
+                from,
+                to,
+                authority: game_signer,
+            },
+            signer,
+        ),
+        amount,
+    )
+}
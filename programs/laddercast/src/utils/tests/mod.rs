@@ -1,15 +1,29 @@
 pub use test_caster_util::*;
+pub use test_crafting_util::*;
+pub use test_debug_util::*;
 pub use test_equipment_util::*;
+pub use test_inventory_util::*;
+pub use test_loot_util::*;
 pub use test_merkle_tree_util::*;
+pub use test_migration_util::*;
+pub use test_player_util::*;
 pub use test_random_util::*;
+pub use test_redeem_util::*;
 pub use test_tile_util::*;
 pub use test_vector_util::*;
 pub use testing_utils::*;
 
 pub mod test_caster_util;
+pub mod test_crafting_util;
+pub mod test_debug_util;
 pub mod test_equipment_util;
+pub mod test_inventory_util;
+pub mod test_loot_util;
+pub mod test_player_util;
 pub mod test_random_util;
+pub mod test_redeem_util;
 pub mod test_tile_util;
 pub mod test_vector_util;
 pub mod testing_utils;
 pub mod test_merkle_tree_util;
+pub mod test_migration_util;
@@ -6,7 +6,9 @@ mod test_internal_functions {
     use lazy_static::lazy_static;
     use rand::random;
 
-    use crate::utils::{create_caster_for_testing, give_exp_to_caster_resources_burned, give_exp_to_caster_spell, is_spell_successful, ItemRarity, RandomGenerator};
+    use crate::error::ErrorCode;
+    use crate::Pubkey;
+    use crate::utils::{CommittedActions, create_caster_for_testing, get_turn_commit_for_new_action, give_exp_to_caster_resources_burned, give_exp_to_caster_spell, is_spell_successful, ItemRarity, RandomGenerator, ReforgeSnapshot, ReforgeType, TurnCommit};
 
     lazy_static! {
         static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
@@ -103,4 +105,52 @@ mod test_internal_functions {
         //No really good way to test it except to make sure it doesn't error out
         assert!(is_success || !is_success);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_turn_commit_for_new_action() {
+        let turn_commit = TurnCommit {
+            turn: 10,
+            resources_burned: [1, 2, 3],
+            ..Default::default()
+        };
+
+        //No commit, starts a new one
+        let new_turn_commit = get_turn_commit_for_new_action(None, 10, 5).unwrap();
+        assert_eq!(new_turn_commit.turn, 10);
+        assert_eq!(new_turn_commit.resources_burned, [0; 3]);
+
+        //Same turn, keeps adding to it
+        assert_eq!(get_turn_commit_for_new_action(Some(turn_commit), 10, 5).unwrap(), turn_commit);
+
+        //Still redeemable, has to be redeemed or cancelled first
+        assert!(get_turn_commit_for_new_action(Some(turn_commit), 11, 5).is_err());
+        assert!(get_turn_commit_for_new_action(Some(turn_commit), 15, 5).is_err());
+
+        //Past the retention, it's dropped
+        let new_turn_commit = get_turn_commit_for_new_action(Some(turn_commit), 16, 5).unwrap();
+        assert_eq!(new_turn_commit.turn, 16);
+        assert_eq!(new_turn_commit.resources_burned, [0; 3]);
+    }
+
+    #[test]
+    fn test_get_turn_commit_for_new_action_keeps_stale_reforge() {
+        let turn_commit = TurnCommit {
+            turn: 10,
+            actions: CommittedActions {
+                reforge: Some(ReforgeSnapshot {
+                    item: Pubkey::new_unique(),
+                    item_id: None,
+                    reforge_type: ReforgeType::Value,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        //Its item stays locked until the turn is cancelled
+        assert_eq!(
+            get_turn_commit_for_new_action(Some(turn_commit), 16, 5),
+            Err(ErrorCode::StaleTurnPendingReforge.into())
+        );
+    }
+}
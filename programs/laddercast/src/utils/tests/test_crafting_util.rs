@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use std::convert::TryInto;
+
+    use anchor_lang::prelude::Pubkey;
+    use lazy_static::lazy_static;
+    use rand::random;
+
+    use crate::GameRules;
+    use crate::utils::{CraftingSnapshot, get_crafted_item_level_and_rarity, get_crafting_cost, get_move_cost, get_reforge_cost, get_teleport_cost, ItemRarity, MAX_LEVEL_1_BASED, RandomGenerator, ReforgeType, SET_BONUS_TWO_PIECES};
+
+    lazy_static! {
+        static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
+    }
+
+    fn generate_slot_hashes(is_random: bool) -> Vec<u8> {
+        if is_random {
+            (0..512 * 40).map(|_| random()).collect()
+        } else {
+            vec![1; 512 * 40]
+        }
+    }
+
+    fn create_crafting_snapshot_for_testing(min_level: u8, min_rarity: ItemRarity, max_rarity: ItemRarity) -> CraftingSnapshot {
+        CraftingSnapshot {
+            min_level,
+            min_rarity,
+            max_rarity,
+        }
+    }
+
+    #[test]
+    fn test_crafted_item_with_extra_level_bonus() {
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+        let crafting_snapshot = create_crafting_snapshot_for_testing(3, ItemRarity::Epic, ItemRarity::Epic);
+
+        assert_eq!(
+            get_crafted_item_level_and_rarity(&crafting_snapshot, true, 0, &mut rand),
+            (4, ItemRarity::Common)
+        );
+    }
+
+    #[test]
+    fn test_crafted_item_never_above_max() {
+        for _i in 0..50 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+            let crafting_snapshot = create_crafting_snapshot_for_testing(MAX_LEVEL_1_BASED, ItemRarity::Epic, ItemRarity::Epic);
+
+            //Max level and rarity already reached on a normal crafting tile
+            assert_eq!(
+                get_crafted_item_level_and_rarity(&crafting_snapshot, true, 0, &mut rand),
+                (MAX_LEVEL_1_BASED, ItemRarity::Epic)
+            );
+        }
+    }
+
+    #[test]
+    fn test_crafted_item_goes_up_by_one_step_at_most() {
+        for _i in 0..50 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+            let crafting_snapshot = create_crafting_snapshot_for_testing(5, ItemRarity::Common, ItemRarity::Legendary);
+
+            let crafted = get_crafted_item_level_and_rarity(&crafting_snapshot, false, 0, &mut rand);
+
+            assert!(
+                crafted == (5, ItemRarity::Common)
+                    || crafted == (6, ItemRarity::Common)
+                    || crafted == (5, ItemRarity::Rare)
+            );
+        }
+    }
+
+    #[test]
+    fn test_crafted_item_always_upgraded_with_full_craft_upgrade_chance() {
+        for _i in 0..50 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+            let crafting_snapshot = create_crafting_snapshot_for_testing(5, ItemRarity::Common, ItemRarity::Legendary);
+
+            let crafted = get_crafted_item_level_and_rarity(&crafting_snapshot, false, 10_000, &mut rand);
+
+            assert!(crafted == (6, ItemRarity::Common) || crafted == (5, ItemRarity::Rare));
+        }
+    }
+
+    fn count_upgraded_crafts(craft_upgrade_chance: u16, crafts: u32) -> u32 {
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+        let crafting_snapshot = create_crafting_snapshot_for_testing(5, ItemRarity::Common, ItemRarity::Legendary);
+
+        (0..crafts)
+            .filter(|_| {
+                get_crafted_item_level_and_rarity(&crafting_snapshot, false, craft_upgrade_chance, &mut rand)
+                    != (5, ItemRarity::Common)
+            })
+            .count() as u32
+    }
+
+    #[test]
+    fn test_crafted_item_upgraded_more_often_with_two_pieces_set_bonus() {
+        //1 in 11 without a set bonus, about 1% more with the smallest one
+        let without_set_bonus = count_upgraded_crafts(0, 200_000);
+        let with_set_bonus = count_upgraded_crafts(SET_BONUS_TWO_PIECES, 200_000);
+
+        assert!(
+            with_set_bonus > without_set_bonus + 1_000,
+            "{} upgrades with the set bonus, {} without",
+            with_set_bonus,
+            without_set_bonus
+        );
+    }
+
+    #[test]
+    fn test_costs() {
+        let rules = GameRules::default();
+
+        //Level is 0 based
+        assert_eq!(get_move_cost(0, &rules), rules.move_cost_multiplier as u64);
+        assert_eq!(get_move_cost(9, &rules), 10 * rules.move_cost_multiplier as u64);
+        assert_eq!(get_crafting_cost(4, &rules), 5 * rules.crafting_cost_multiplier as u64);
+    }
+
+    #[test]
+    fn test_get_teleport_cost() {
+        let rules = GameRules::default();
+        let multiplier = rules.teleport_cost_multiplier as u64;
+
+        //Move to the destination, plus the levels skipped and columns crossed
+        assert_eq!(get_teleport_cost(0, 0, 0, 2, &rules), get_move_cost(0, &rules) + 2 * multiplier);
+        assert_eq!(get_teleport_cost(10, 1, 2, 1, &rules), get_move_cost(2, &rules) + 8 * multiplier);
+        assert_eq!(get_teleport_cost(2, 1, 10, 1, &rules), get_move_cost(10, &rules) + 8 * multiplier);
+        assert_eq!(get_teleport_cost(29, 0, 0, 4, &rules), get_move_cost(0, &rules) + 33 * multiplier);
+        assert_eq!(get_teleport_cost(5, 2, 5, 2, &rules), get_move_cost(5, &rules));
+
+        //Teleporting can't be free
+        let mut free_teleport = GameRules::default();
+        free_teleport.teleport_cost_multiplier = 0;
+        assert!(free_teleport.validate().is_err());
+    }
+
+    #[test]
+    fn test_teleport_never_cheaper_than_move() {
+        let rules = GameRules::default();
+
+        //Sideways on a high level, where a move costs the most
+        assert!(get_teleport_cost(29, 1, 29, 2, &rules) > get_move_cost(29, &rules));
+
+        for tile_level in 0..30 {
+            for dest_level in 0..=tile_level {
+                for &(tile_column, dest_column) in [(0, 0), (0, 1), (2, 0)].iter() {
+                    assert!(
+                        get_teleport_cost(tile_level, tile_column, dest_level, dest_column, &rules)
+                            >= get_move_cost(dest_level, &rules)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_reforge_cost() {
+        let rules = GameRules::default();
+        let multiplier = rules.crafting_cost_multiplier as u64;
+
+        assert_eq!(get_reforge_cost(1, ItemRarity::Common, ReforgeType::Value, &rules), multiplier);
+        assert_eq!(get_reforge_cost(10, ItemRarity::Common, ReforgeType::Value, &rules), 10 * multiplier);
+        assert_eq!(get_reforge_cost(10, ItemRarity::Legendary, ReforgeType::Value, &rules), 40 * multiplier);
+        assert_eq!(get_reforge_cost(10, ItemRarity::Epic, ReforgeType::Feature, &rules), 60 * multiplier);
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::__private::ErrorCode as AnchorErrorCode;
+    use anchor_lang::solana_program::hash;
+
+    use crate::utils::dispatch_debug_instruction;
+
+    fn sighash(name: &str) -> Vec<u8> {
+        hash::hash(format!("global:{}", name).as_bytes()).to_bytes()[..8].to_vec()
+    }
+
+    #[test]
+    #[cfg(not(feature = "debug"))]
+    fn test_dispatch_debug_instruction_default_build() {
+        for name in ["give_resources", "give_lada", "give_item", "change_tile"] {
+            assert_eq!(
+                dispatch_debug_instruction(&crate::ID, &[], &sighash(name)),
+                Err(AnchorErrorCode::InstructionFallbackNotFound.into())
+            );
+        }
+
+        //Too short for a discriminator
+        assert_eq!(
+            dispatch_debug_instruction(&crate::ID, &[], &[1, 2, 3]),
+            Err(AnchorErrorCode::InstructionMissing.into())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_dispatch_debug_instruction_debug_build() {
+        use crate::instructions::test_helper::debug_instruction_sighash;
+
+        assert_eq!(debug_instruction_sighash("give_item").to_vec(), sighash("give_item"));
+
+        //Known debug instruction, but its arguments and accounts are missing
+        assert_eq!(
+            dispatch_debug_instruction(&crate::ID, &[], &sighash("give_item")),
+            Err(AnchorErrorCode::InstructionDidNotDeserialize.into())
+        );
+
+        assert_eq!(
+            dispatch_debug_instruction(&crate::ID, &[], &sighash("not_a_debug_instruction")),
+            Err(AnchorErrorCode::InstructionFallbackNotFound.into())
+        );
+
+        //Too short for a discriminator, doesn't get to the debug instructions
+        assert_eq!(
+            dispatch_debug_instruction(&crate::ID, &[], &[]),
+            Err(AnchorErrorCode::InstructionMissing.into())
+        );
+        assert_eq!(
+            dispatch_debug_instruction(&crate::ID, &[], &sighash("give_item")[..7]),
+            Err(AnchorErrorCode::InstructionMissing.into())
+        );
+    }
+}
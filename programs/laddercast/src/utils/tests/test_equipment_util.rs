@@ -7,7 +7,7 @@ mod test_internal_functions {
     use rand::random;
 
     use crate::{ItemFeature, ItemType};
-    use crate::utils::{create_game_for_testing, generate_new_equipment, generate_new_spell_book, get_item_percentage_value, get_item_rarity, get_item_resource_value, ItemRarity, RandomGenerator, SpellType};
+    use crate::utils::{create_game_for_testing, EARTH_INDEX, EquipmentType, FIRE_INDEX, get_item_percentage_value, get_item_rarity, get_item_resource_value, get_salvage_value, ItemRarity, RandomGenerator, reforge_equipment, ReforgeType, SpellType, WATER_INDEX};
 
     lazy_static! {
         static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
@@ -68,4 +68,100 @@ mod test_internal_functions {
             ItemRarity::Common | ItemRarity::Rare | ItemRarity::Epic | ItemRarity::Legendary
         ));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_salvage_value() {
+        let equipment = |feature: ItemFeature, rarity: ItemRarity| ItemType::Equipment {
+            feature,
+            rarity,
+            equipment_type: EquipmentType::Head,
+            value: 1,
+        };
+
+        let mut expected = [0; 3];
+        expected[FIRE_INDEX] = 15;
+        assert_eq!(get_salvage_value(&equipment(ItemFeature::Fire, ItemRarity::Common), 5), Some(expected));
+
+        let mut expected = [0; 3];
+        expected[EARTH_INDEX] = 60;
+        assert_eq!(get_salvage_value(&equipment(ItemFeature::Earth, ItemRarity::Epic), 5), Some(expected));
+
+        //Split between the three resources
+        assert_eq!(get_salvage_value(&equipment(ItemFeature::Power, ItemRarity::Legendary), 2), Some([16; 3]));
+
+        let spell_book = ItemType::SpellBook {
+            spell: SpellType::Fire,
+            cost_feature: ItemFeature::Water,
+            rarity: ItemRarity::Rare,
+            cost: 5,
+            value: 1,
+        };
+
+        let mut expected = [0; 3];
+        expected[WATER_INDEX] = 6;
+        assert_eq!(get_salvage_value(&spell_book, 1), Some(expected));
+
+        assert_eq!(get_salvage_value(&ItemType::Chest { tier: 1, seed: 1 }, 5), None);
+        assert_eq!(get_salvage_value(&ItemType::Zombie, 5), None);
+    }
+
+    #[test]
+    fn test_reforge_equipment_value() {
+        let item_type = ItemType::Equipment {
+            feature: ItemFeature::Fire,
+            rarity: ItemRarity::Epic,
+            equipment_type: EquipmentType::Robe,
+            value: 1,
+        };
+
+        for _i in 0..20 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+            match reforge_equipment(item_type, 10, ReforgeType::Value, &mut rand).unwrap() {
+                ItemType::Equipment { feature, rarity, equipment_type, value } => {
+                    assert_eq!(feature, ItemFeature::Fire);
+                    assert_eq!(rarity, ItemRarity::Epic);
+                    assert_eq!(equipment_type, EquipmentType::Robe);
+                    //Resource value band of an epic level 10 item
+                    assert!((201..=300).contains(&value));
+                }
+                _ => panic!("Reforged item is not an equipment"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_reforge_equipment_feature() {
+        let item_type = ItemType::Equipment {
+            feature: ItemFeature::Power,
+            rarity: ItemRarity::Common,
+            equipment_type: EquipmentType::Head,
+            value: 100,
+        };
+
+        let mut features = vec![];
+
+        for _i in 0..50 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+            if let ItemType::Equipment { feature, rarity, equipment_type, .. } =
+                reforge_equipment(item_type, 10, ReforgeType::Feature, &mut rand).unwrap()
+            {
+                assert_eq!(rarity, ItemRarity::Common);
+                assert_eq!(equipment_type, EquipmentType::Head);
+                features.push(feature);
+            }
+        }
+
+        assert_eq!(features.len(), 50);
+        assert!(features.iter().any(|feature| *feature != ItemFeature::Power));
+    }
+
+    #[test]
+    fn test_reforge_equipment_rejects_other_items() {
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+        assert!(reforge_equipment(ItemType::Chest { tier: 1, seed: 1 }, 1, ReforgeType::Value, &mut rand).is_err());
+        assert!(reforge_equipment(ItemType::Zombie, 1, ReforgeType::Feature, &mut rand).is_err());
+    }
+}
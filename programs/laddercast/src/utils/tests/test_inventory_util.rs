@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::AnchorSerialize;
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::account::{Inventory, InventoryItem};
+    use crate::utils::{EquipmentType, get_inventory_item_key, INVENTORY_CAPACITY, ItemFeature, ItemRarity, ItemType, SpellType};
+
+    fn create_inventory_for_testing() -> Inventory {
+        Inventory {
+            bump: 0,
+            player: Pubkey::new_unique(),
+            next_id: 0,
+            items: vec![],
+        }
+    }
+
+    #[test]
+    fn test_inventory_add_and_remove_item() {
+        let mut inventory = create_inventory_for_testing();
+
+        assert_eq!(inventory.add_item(1, ItemType::Chest { tier: 1, seed: 1 }).unwrap(), 0);
+        assert_eq!(inventory.add_item(2, ItemType::Chest { tier: 2, seed: 1 }).unwrap(), 1);
+
+        let removed = inventory.remove_item(0).unwrap();
+        assert_eq!(removed.level, 1);
+        assert!(inventory.get_item(0).is_err());
+        assert!(inventory.remove_item(0).is_err());
+
+        //Ids are never reused
+        assert_eq!(inventory.add_item(3, ItemType::Chest { tier: 3, seed: 1 }).unwrap(), 2);
+        assert_eq!(inventory.get_item(1).unwrap().level, 2);
+        assert_eq!(inventory.get_item(2).unwrap().level, 3);
+    }
+
+    #[test]
+    fn test_inventory_full() {
+        let mut inventory = create_inventory_for_testing();
+
+        for _ in 0..INVENTORY_CAPACITY {
+            inventory.add_item(1, ItemType::Chest { tier: 1, seed: 1 }).unwrap();
+        }
+
+        assert!(inventory.add_item(1, ItemType::Chest { tier: 1, seed: 1 }).is_err());
+
+        inventory.remove_item(0).unwrap();
+
+        assert!(inventory.add_item(1, ItemType::Chest { tier: 1, seed: 1 }).is_ok());
+    }
+
+    #[test]
+    fn test_inventory_item_size_is_its_largest_encoding() {
+        let item_types = [
+            ItemType::Zombie,
+            ItemType::Chest { tier: 4, seed: u32::MAX },
+            ItemType::Equipment {
+                feature: ItemFeature::Fire,
+                rarity: ItemRarity::Legendary,
+                equipment_type: EquipmentType::Staff,
+                value: u16::MAX,
+            },
+            ItemType::SpellBook {
+                spell: SpellType::Item,
+                cost_feature: ItemFeature::Water,
+                rarity: ItemRarity::Legendary,
+                cost: u16::MAX,
+                value: u16::MAX,
+            },
+        ];
+
+        let sizes: Vec<usize> = item_types
+            .iter()
+            .map(|item_type| {
+                InventoryItem {
+                    id: u32::MAX,
+                    level: 30,
+                    item_type: *item_type,
+                    equipped_owner: Some(Pubkey::new_unique()),
+                }
+                .try_to_vec()
+                .unwrap()
+                .len()
+            })
+            .collect();
+
+        assert_eq!(sizes.iter().max(), Some(&InventoryItem::SIZE));
+    }
+
+    #[test]
+    fn test_inventory_space_fits_its_items() {
+        let mut inventory = create_inventory_for_testing();
+
+        for _ in 0..3 {
+            inventory.add_item(1, ItemType::Chest { tier: 1, seed: 1 }).unwrap();
+        }
+
+        //Discriminator, then the Borsh encoding of the inventory
+        assert!(8 + inventory.try_to_vec().unwrap().len() <= Inventory::space(3));
+    }
+
+    #[test]
+    fn test_get_inventory_item_key() {
+        let inventory = Pubkey::new_unique();
+
+        assert_eq!(get_inventory_item_key(&inventory, 0), get_inventory_item_key(&inventory, 0));
+        assert_ne!(get_inventory_item_key(&inventory, 0), get_inventory_item_key(&inventory, 1));
+        assert_ne!(
+            get_inventory_item_key(&inventory, 0),
+            get_inventory_item_key(&Pubkey::new_unique(), 0)
+        );
+    }
+}
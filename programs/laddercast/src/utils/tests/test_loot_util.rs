@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use std::convert::TryInto;
+
+    use anchor_lang::prelude::Pubkey;
+    use lazy_static::lazy_static;
+    use rand::random;
+
+    use crate::{ChestTier, GameRules, PlayerBonuses, TileType};
+    use crate::utils::{get_chest_item_rarity, get_chest_tier, get_chest_tier_rules, get_loot_range, is_chest_found, is_chest_item_spell_book, ItemRarity, ItemType, RandomGenerator, roll_chest_contents, roll_loot_resources};
+
+    lazy_static! {
+        static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
+    }
+
+    fn generate_slot_hashes(is_random: bool) -> Vec<u8> {
+        if is_random {
+            (0..512 * 40).map(|_| random()).collect()
+        } else {
+            vec![1; 512 * 40]
+        }
+    }
+
+    fn create_player_bonuses_for_testing(critical_chance: u16, magic_find_chance: u16) -> PlayerBonuses {
+        PlayerBonuses {
+            critical_chance,
+            magic_find_chance,
+            fire_chance: 5,
+            water_chance: 0,
+            earth_chance: 0,
+            craft_upgrade_chance: 0,
+        }
+    }
+
+    #[test]
+    fn test_get_loot_range() {
+        let player_bonuses = create_player_bonuses_for_testing(0, 0);
+
+        assert_eq!(get_loot_range(0, TileType::Water, &player_bonuses), (1, 10));
+        assert_eq!(get_loot_range(4, TileType::Water, &player_bonuses), (1, 50));
+        //Only the bonus of the looted resource is added
+        assert_eq!(get_loot_range(0, TileType::Fire, &player_bonuses), (1, 15));
+    }
+
+    #[test]
+    fn test_roll_loot_resources_within_range() {
+        let player_bonuses = create_player_bonuses_for_testing(0, 0);
+
+        for _i in 0..20 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+            let looted = roll_loot_resources(2, TileType::Earth, &player_bonuses, &mut rand);
+
+            assert!(looted >= 1 && looted <= 30);
+        }
+    }
+
+    #[test]
+    fn test_roll_loot_resources_always_critical() {
+        //Critical roll is at most 10 000
+        let player_bonuses = create_player_bonuses_for_testing(10_000, 0);
+
+        for _i in 0..20 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+            let looted = roll_loot_resources(0, TileType::Earth, &player_bonuses, &mut rand);
+
+            assert!(looted >= 2 && looted <= 20 && looted % 2 == 0);
+        }
+    }
+
+    #[test]
+    fn test_is_chest_found() {
+        let mut rules = GameRules::default();
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+        rules.default_magic_find_in_percent = 0;
+        assert!(!is_chest_found(&rules, &create_player_bonuses_for_testing(0, 0), &mut rand));
+
+        rules.default_magic_find_in_percent = 10_000;
+        assert!(is_chest_found(&rules, &create_player_bonuses_for_testing(0, 0), &mut rand));
+    }
+
+    #[test]
+    fn test_get_chest_tier() {
+        assert_eq!(get_chest_tier(0), 1);
+        assert_eq!(get_chest_tier(5), 2);
+        assert_eq!(get_chest_tier(14), 3);
+        assert_eq!(get_chest_tier(29), 4);
+    }
+
+    fn count_chest_item_rarities(chest_tier: &ChestTier, rolls: u32) -> [u32; 4] {
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+        let mut counts = [0; 4];
+
+        for _i in 0..rolls {
+            counts[get_chest_item_rarity(chest_tier, &mut rand) as usize] += 1;
+        }
+
+        counts
+    }
+
+    #[test]
+    fn test_get_chest_tier_rules() {
+        let rules = GameRules::default();
+
+        assert_eq!(get_chest_tier_rules(&rules, 1).min_item_level, 1);
+        assert_eq!(get_chest_tier_rules(&rules, 2).min_item_level, 6);
+        assert_eq!(get_chest_tier_rules(&rules, 3).min_item_level, 11);
+        assert_eq!(get_chest_tier_rules(&rules, 4).min_item_level, 16);
+        assert_eq!(*get_chest_tier_rules(&rules, 0), rules.chest_tiers[0]);
+        assert_eq!(*get_chest_tier_rules(&rules, 5), rules.chest_tiers[0]);
+    }
+
+    #[test]
+    fn test_get_chest_item_rarity_follows_weights() {
+        let rules = GameRules::default();
+        let counts = count_chest_item_rarities(get_chest_tier_rules(&rules, 1), 10_000);
+
+        //80 / 15 / 4 / 1, within 2% of the rolls
+        assert!((7_800..=8_200).contains(&counts[ItemRarity::Common as usize]));
+        assert!((1_300..=1_700).contains(&counts[ItemRarity::Rare as usize]));
+        assert!((200..=600).contains(&counts[ItemRarity::Epic as usize]));
+        assert!(counts[ItemRarity::Legendary as usize] <= 300);
+        assert_eq!(counts.iter().sum::<u32>(), 10_000);
+    }
+
+    #[test]
+    fn test_get_chest_item_rarity_better_on_higher_tier() {
+        let rules = GameRules::default();
+        let tier_1 = count_chest_item_rarities(get_chest_tier_rules(&rules, 1), 10_000);
+        let tier_4 = count_chest_item_rarities(get_chest_tier_rules(&rules, 4), 10_000);
+
+        assert!(tier_4[ItemRarity::Common as usize] < tier_1[ItemRarity::Common as usize]);
+        assert!(tier_4[ItemRarity::Epic as usize] > tier_1[ItemRarity::Epic as usize]);
+        assert!(tier_4[ItemRarity::Legendary as usize] > tier_1[ItemRarity::Legendary as usize]);
+    }
+
+    #[test]
+    fn test_get_chest_item_rarity_skips_zero_weights() {
+        let chest_tier = ChestTier {
+            rarity_weights: [0, 0, 0, 1],
+            ..GameRules::default().chest_tiers[0]
+        };
+
+        assert_eq!(count_chest_item_rarities(&chest_tier, 100)[ItemRarity::Legendary as usize], 100);
+    }
+
+    #[test]
+    fn test_is_chest_item_spell_book() {
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+        let mut chest_tier = GameRules::default().chest_tiers[0];
+
+        let spell_books = (0..10_000)
+            .filter(|_| is_chest_item_spell_book(&chest_tier, &mut rand))
+            .count();
+
+        //25%, within 2% of the rolls
+        assert!((2_300..=2_700).contains(&spell_books));
+
+        chest_tier.spell_book_chance = 0;
+        assert!(!is_chest_item_spell_book(&chest_tier, &mut rand));
+
+        chest_tier.spell_book_chance = 100;
+        assert!(is_chest_item_spell_book(&chest_tier, &mut rand));
+    }
+
+    #[test]
+    fn test_roll_chest_contents() {
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+        let chest_tier = ChestTier {
+            rarity_weights: [0, 1, 0, 0],
+            ..GameRules::default().chest_tiers[1]
+        };
+
+        for _i in 0..20 {
+            let contents = roll_chest_contents(&chest_tier, 8, &mut rand);
+
+            assert_eq!(contents.len(), chest_tier.item_count as usize);
+
+            for (level, item_type) in contents {
+                assert!((chest_tier.min_item_level..=8).contains(&level));
+
+                match item_type {
+                    ItemType::Equipment { rarity, .. } | ItemType::SpellBook { rarity, .. } => {
+                        assert_eq!(rarity, ItemRarity::Rare);
+                    }
+                    _ => panic!("Chest gave something else than an equipment or a spell book"),
+                }
+            }
+        }
+
+        //Chest level below the min item level of its tier gives items of the chest level
+        let contents = roll_chest_contents(&chest_tier, 1, &mut rand);
+        assert!(contents.iter().all(|(level, _)| *level == 1));
+    }
+
+    #[test]
+    fn test_default_chest_tiers_fit_open_chest_accounts() {
+        //open_chest has 3 item accounts, more is only for games opting in
+        for chest_tier in GameRules::default().chest_tiers.iter() {
+            assert_eq!(chest_tier.item_count, 3);
+        }
+    }
+
+    #[test]
+    fn test_validate_chest_tiers() {
+        let mut rules = GameRules::default();
+        rules.chest_tiers[3].item_count = 0;
+        assert!(rules.validate().is_err());
+
+        let mut rules = GameRules::default();
+        rules.chest_tiers[0].rarity_weights = [0; 4];
+        assert!(rules.validate().is_err());
+
+        let mut rules = GameRules::default();
+        rules.chest_tiers[1].spell_book_chance = 101;
+        assert!(rules.validate().is_err());
+
+        let mut rules = GameRules::default();
+        rules.chest_tiers[2].min_item_level = 0;
+        assert!(rules.validate().is_err());
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorSerialize, Discriminator};
+    use anchor_lang::prelude::{AccountInfo, Pubkey};
+
+    use crate::{GameTurnInfo, Tile, TileType};
+    use crate::account::{Game, TurnData};
+    use crate::utils::{
+        LEGACY_MAP_COLUMNS, LEGACY_MAP_LEVELS, LegacyGame, LegacyTurnData, read_legacy_game,
+        read_legacy_turn_data, write_migrated_account,
+    };
+
+    //Game::SIZE before per-game map dimensions
+    const LEGACY_GAME_SIZE: usize = 8 + 32 + (90 + 1) * Tile::SIZE + 8 + 1 + 32 + 32 + 32 + 32 + 32 + 32 + 300;
+
+    fn create_legacy_game_for_testing() -> LegacyGame {
+        let mut map = [[None; 3]; 30];
+        map[0] = [
+            Some(Tile { tile_type: TileType::Fire, life: 4, is_first_time_spawning: false }),
+            Some(Tile { tile_type: TileType::Water, life: 5, is_first_time_spawning: false }),
+            Some(Tile { tile_type: TileType::Crafting, life: 6, is_first_time_spawning: true }),
+        ];
+        map[29][2] = Some(Tile { tile_type: TileType::Legendary, life: 3, is_first_time_spawning: false });
+
+        LegacyGame {
+            authority: Pubkey::new_unique(),
+            map,
+            turn_info: GameTurnInfo {
+                turn: 42,
+                turn_delay: 1200,
+                last_crank_seconds: 3,
+                last_tile_spawn: 40,
+                tile_spawn_delay: 20,
+            },
+            last_turn_added: 42,
+            signer_bump: 255,
+            resource_1_mint_account: Pubkey::new_unique(),
+            resource_2_mint_account: Pubkey::new_unique(),
+            resource_3_mint_account: Pubkey::new_unique(),
+            lada_mint_account: Pubkey::new_unique(),
+            lada_token_account: Pubkey::new_unique(),
+        }
+    }
+
+    /// Account data as the baseline program wrote it: discriminator, fields, then zeroed padding
+    fn serialize_legacy_account<T: AnchorSerialize>(discriminator: [u8; 8], account: &T, size: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.extend(account.try_to_vec().unwrap());
+        assert!(data.len() <= size);
+        data.resize(size, 0);
+        data
+    }
+
+    #[test]
+    fn test_migrate_legacy_game() {
+        let legacy_game = create_legacy_game_for_testing();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let mut data = serialize_legacy_account(Game::discriminator(), &legacy_game, LEGACY_GAME_SIZE);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        assert_eq!(read_legacy_game(&info, &crate::ID).unwrap(), legacy_game);
+        assert!(read_legacy_game(&info, &Pubkey::new_unique()).is_err());
+
+        //The new layout is bigger, it has to be written in a grown account
+        let new_size = Game::size(LEGACY_MAP_LEVELS, LEGACY_MAP_COLUMNS);
+        assert!(new_size > LEGACY_GAME_SIZE);
+
+        let mut new_data = vec![0; new_size];
+        let mut writer: &mut [u8] = &mut new_data;
+        legacy_game.clone().into_game().try_serialize(&mut writer).unwrap();
+
+        let game = Game::try_deserialize(&mut &new_data[..]).unwrap();
+        assert_eq!(game.authority, legacy_game.authority);
+        assert_eq!((game.levels, game.columns), (30, 3));
+        assert_eq!(game.map.len(), 30);
+        assert!(game.map.iter().all(|level| level.len() == 3));
+        assert_eq!(game.map[0], legacy_game.map[0].to_vec());
+        assert_eq!(game.map[29][2], legacy_game.map[29][2]);
+        assert_eq!(game.turn_info, legacy_game.turn_info);
+        assert_eq!(game.last_turn_added, 42);
+        assert_eq!(game.resource_3_mint_account, legacy_game.resource_3_mint_account);
+        assert_eq!(game.lada_token_account, legacy_game.lada_token_account);
+    }
+
+    #[test]
+    fn test_migrate_game_signer_rewrites_baseline_game() {
+        let legacy_game = create_legacy_game_for_testing();
+        let new_size = Game::size(LEGACY_MAP_LEVELS, LEGACY_MAP_COLUMNS);
+
+        //Baseline account, already grown to the new size like migrate_game_signer does before writing
+        let mut data = serialize_legacy_account(Game::discriminator(), &legacy_game, LEGACY_GAME_SIZE);
+        data.resize(new_size, 0);
+
+        let (key, payer_key, system_key) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::default());
+        let (mut lamports, mut payer_lamports, mut system_lamports) = (1, 1, 1);
+        let (mut payer_data, mut system_data): (Vec<u8>, Vec<u8>) = (vec![], vec![]);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let payer = AccountInfo::new(&payer_key, true, true, &mut payer_lamports, &mut payer_data, &system_key, false, 0);
+        let system_program = AccountInfo::new(&system_key, false, false, &mut system_lamports, &mut system_data, &system_key, true, 0);
+
+        let new_signer_bump = 253;
+        let new_lada_token_account = Pubkey::new_unique();
+
+        let mut game = read_legacy_game(&info, &crate::ID).unwrap().into_game();
+        game.signer_bump = new_signer_bump;
+        game.lada_token_account = new_lada_token_account;
+
+        write_migrated_account(&info, &game, new_size, &payer, &system_program).unwrap();
+
+        let migrated_game = Game::try_deserialize(&mut &info.try_borrow_data().unwrap()[..]).unwrap();
+        assert_eq!(migrated_game.authority, legacy_game.authority);
+        assert_eq!((migrated_game.levels, migrated_game.columns), (30, 3));
+        assert_eq!(migrated_game.map[0], legacy_game.map[0].to_vec());
+        assert_eq!(migrated_game.turn_info, legacy_game.turn_info);
+        assert_eq!(migrated_game.signer_bump, new_signer_bump);
+        assert_eq!(migrated_game.lada_token_account, new_lada_token_account);
+        assert_eq!(migrated_game.resource_1_mint_account, legacy_game.resource_1_mint_account);
+
+        //A migrated game isn't in the legacy layout anymore, it can't be migrated twice
+        assert!(read_legacy_game(&info, &crate::ID).is_err());
+    }
+
+    #[test]
+    fn test_migrate_legacy_turn_data() {
+        let mut map = [[None; 3]; 30];
+        map[1] = [Some(TileType::Earth), None, Some(TileType::Fire)];
+
+        let legacy_turn_data = LegacyTurnData {
+            bump: 254,
+            resource_1_burned: 10,
+            resource_2_burned: 20,
+            resource_3_burned: 30,
+            map,
+        };
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let mut data = serialize_legacy_account(TurnData::discriminator(), &legacy_turn_data, LegacyTurnData::SIZE);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        let read = read_legacy_turn_data(&info, &crate::ID).unwrap();
+        assert_eq!(read, legacy_turn_data);
+
+        let payer = Pubkey::new_unique();
+        let mut new_data = vec![0; TurnData::size(LEGACY_MAP_LEVELS, LEGACY_MAP_COLUMNS)];
+        let mut writer: &mut [u8] = &mut new_data;
+        read.into_turn_data(payer).try_serialize(&mut writer).unwrap();
+
+        let turn_data = TurnData::try_deserialize(&mut &new_data[..]).unwrap();
+        assert_eq!(turn_data.bump, 254);
+        assert_eq!(turn_data.payer, payer);
+        assert_eq!(
+            [turn_data.resource_1_burned, turn_data.resource_2_burned, turn_data.resource_3_burned],
+            [10, 20, 30]
+        );
+        assert_eq!(turn_data.map.len(), 30);
+        assert_eq!(turn_data.map[1], vec![Some(TileType::Earth), None, Some(TileType::Fire)]);
+    }
+
+    #[test]
+    fn test_current_turn_data_is_not_legacy() {
+        let turn_data = TurnData {
+            map: vec![vec![None; 3]; 30],
+            ..Default::default()
+        };
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let mut data = vec![0; TurnData::size(LEGACY_MAP_LEVELS, LEGACY_MAP_COLUMNS)];
+        let mut writer: &mut [u8] = &mut data;
+        turn_data.try_serialize(&mut writer).unwrap();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        assert!(read_legacy_turn_data(&info, &crate::ID).is_err());
+    }
+}
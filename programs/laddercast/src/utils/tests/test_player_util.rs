@@ -0,0 +1,303 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::AccountSerialize;
+    use anchor_lang::prelude::{AccountInfo, ProgramError, Pubkey};
+
+    use crate::{ItemFeature, ItemType, PlayerBonuses};
+    use crate::account::{Inventory, InventoryItem, Item};
+    use crate::error::ErrorCode;
+    use crate::utils::{
+        add_set_bonuses, create_caster_modifiers_for_testing, create_equipment_for_testing,
+        EquipmentType, get_inventory_item_key, get_player_bonuses, ItemRarity, ItemRecord,
+        load_equipped_item, load_equipped_items, SET_BONUS_THREE_PIECES, SET_BONUS_TWO_PIECES,
+    };
+
+    fn create_player_bonuses_for_testing() -> PlayerBonuses {
+        PlayerBonuses {
+            critical_chance: 200,
+            magic_find_chance: 0,
+            fire_chance: 0,
+            water_chance: 0,
+            earth_chance: 0,
+            craft_upgrade_chance: 0,
+        }
+    }
+
+    fn serialize_account<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn create_account_info<'a>(key: &'a Pubkey, lamports: &'a mut u64, data: &'a mut [u8]) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, false, lamports, data, &crate::ID, false, 0)
+    }
+
+    fn create_equipment_record_for_testing(key: Pubkey, equipment_type: EquipmentType, equipped_owner: Option<Pubkey>) -> ItemRecord {
+        let item = create_equipment_for_testing(equipment_type);
+
+        ItemRecord {
+            key,
+            id: None,
+            level: item.level,
+            item_type: item.item_type,
+            equipped_owner,
+        }
+    }
+
+    fn assert_error(result: Result<impl std::fmt::Debug, ProgramError>, error_code: ErrorCode) {
+        let expected: ProgramError = error_code.into();
+        assert_eq!(result.unwrap_err(), expected);
+    }
+
+    #[test]
+    fn test_load_equipped_item_account() {
+        let item = create_equipment_for_testing(EquipmentType::Head);
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let mut data = serialize_account(&item);
+        let info = create_account_info(&key, &mut lamports, &mut data);
+
+        let record = load_equipped_item(&info, key, &item.game, &item.owner).unwrap();
+        assert_eq!(record.key, key);
+        assert_eq!(record.id, None);
+        assert_eq!(record.item_type, item.item_type);
+
+        assert_error(
+            load_equipped_item(&info, key, &Pubkey::new_unique(), &item.owner),
+            ErrorCode::EquippedItemWrongGame,
+        );
+        assert_error(
+            load_equipped_item(&info, key, &item.game, &Pubkey::new_unique()),
+            ErrorCode::EquippedItemWrongOwner,
+        );
+    }
+
+    #[test]
+    fn test_load_equipped_items_swapped_accounts() {
+        let head = create_equipment_for_testing(EquipmentType::Head);
+        let mut robe = create_equipment_for_testing(EquipmentType::Robe);
+        robe.game = head.game;
+        robe.owner = head.owner;
+
+        let mut modifiers = create_caster_modifiers_for_testing(false);
+        modifiers.staff = None;
+
+        let head_key = modifiers.head.unwrap();
+        let robe_key = modifiers.robe.unwrap();
+        let (mut head_lamports, mut robe_lamports) = (1, 1);
+        let mut head_data = serialize_account(&head);
+        let mut robe_data = serialize_account(&robe);
+        let head_info = create_account_info(&head_key, &mut head_lamports, &mut head_data);
+        let robe_info = create_account_info(&robe_key, &mut robe_lamports, &mut robe_data);
+
+        //Staff account isn't read since nothing is equipped in that slot
+        let equipped_items =
+            load_equipped_items(&head_info, &robe_info, &head_info, &modifiers, &head.game, &head.owner).unwrap();
+        assert_eq!(equipped_items.len(), 2);
+
+        assert_error(
+            load_equipped_items(&robe_info, &head_info, &head_info, &modifiers, &head.game, &head.owner),
+            ErrorCode::EquippedItemAccountMismatch,
+        );
+    }
+
+    #[test]
+    fn test_load_equipped_item_from_inventory() {
+        let inventory_key = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        let item = create_equipment_for_testing(EquipmentType::Staff);
+        let inventory = Inventory {
+            bump: 0,
+            player,
+            next_id: 1,
+            items: vec![InventoryItem {
+                id: 0,
+                level: item.level,
+                item_type: item.item_type,
+                equipped_owner: None,
+            }],
+        };
+        let mut lamports = 1;
+        let mut data = serialize_account(&inventory);
+        let info = create_account_info(&inventory_key, &mut lamports, &mut data);
+
+        let key = get_inventory_item_key(&inventory_key, 0);
+        let record = load_equipped_item(&info, key, &item.game, &player).unwrap();
+        assert_eq!(record.id, Some(0));
+        assert_eq!(record.key, key);
+
+        assert_error(
+            load_equipped_item(&info, key, &item.game, &Pubkey::new_unique()),
+            ErrorCode::EquippedItemWrongOwner,
+        );
+        assert_error(
+            load_equipped_item(&info, get_inventory_item_key(&inventory_key, 1), &item.game, &player),
+            ErrorCode::EquippedItemAccountMismatch,
+        );
+    }
+
+    #[test]
+    fn test_get_player_bonuses() {
+        let caster = Pubkey::new_unique();
+        let modifiers = create_caster_modifiers_for_testing(false);
+        let items = [
+            create_equipment_record_for_testing(modifiers.head.unwrap(), EquipmentType::Head, Some(caster)),
+            create_equipment_record_for_testing(modifiers.robe.unwrap(), EquipmentType::Robe, Some(caster)),
+            create_equipment_record_for_testing(modifiers.staff.unwrap(), EquipmentType::Staff, Some(caster)),
+        ];
+
+        //Three common power pieces of value 1
+        let player_bonuses = get_player_bonuses(&modifiers, &items, &caster, 200).unwrap();
+        assert_eq!(player_bonuses.critical_chance, 200 + 3 + SET_BONUS_THREE_PIECES);
+        assert_eq!(player_bonuses.magic_find_chance, 0);
+
+        //Every equipped item has to be provided
+        assert_error(
+            get_player_bonuses(&modifiers, &items[..2], &caster, 200),
+            ErrorCode::EquippedItemAccountMismatch,
+        );
+    }
+
+    #[test]
+    fn test_get_player_bonuses_not_equipped_by_caster() {
+        let caster = Pubkey::new_unique();
+        let mut modifiers = create_caster_modifiers_for_testing(false);
+        modifiers.robe = None;
+        modifiers.staff = None;
+
+        let unequipped = [create_equipment_record_for_testing(modifiers.head.unwrap(), EquipmentType::Head, None)];
+        assert_error(
+            get_player_bonuses(&modifiers, &unequipped, &caster, 200),
+            ErrorCode::ItemNotEquippedByCaster,
+        );
+
+        let other_caster = [create_equipment_record_for_testing(
+            modifiers.head.unwrap(),
+            EquipmentType::Head,
+            Some(Pubkey::new_unique()),
+        )];
+        assert_error(
+            get_player_bonuses(&modifiers, &other_caster, &caster, 200),
+            ErrorCode::ItemNotEquippedByCaster,
+        );
+    }
+
+    #[test]
+    fn test_get_player_bonuses_wrong_slot() {
+        let caster = Pubkey::new_unique();
+        let mut modifiers = create_caster_modifiers_for_testing(false);
+        modifiers.robe = None;
+        modifiers.staff = None;
+
+        //Robe in the head slot
+        let swapped = [create_equipment_record_for_testing(modifiers.head.unwrap(), EquipmentType::Robe, Some(caster))];
+        assert_error(
+            get_player_bonuses(&modifiers, &swapped, &caster, 200),
+            ErrorCode::EquippedItemWrongSlot,
+        );
+
+        let mut chest = swapped;
+        chest[0].item_type = ItemType::Chest { tier: 1, seed: 1 };
+        assert_error(
+            get_player_bonuses(&modifiers, &chest, &caster, 200),
+            ErrorCode::EquippedItemWrongSlot,
+        );
+    }
+
+    #[test]
+    fn test_no_set_bonus_without_matching_pieces() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        add_set_bonuses(&mut player_bonuses, &[]);
+        add_set_bonuses(
+            &mut player_bonuses,
+            &[
+                (ItemFeature::Fire, ItemRarity::Common),
+                (ItemFeature::Water, ItemRarity::Rare),
+                (ItemFeature::Earth, ItemRarity::Epic),
+            ],
+        );
+
+        assert_eq!(player_bonuses.critical_chance, 200);
+        assert_eq!(player_bonuses.magic_find_chance, 0);
+        assert_eq!(player_bonuses.craft_upgrade_chance, 0);
+    }
+
+    #[test]
+    fn test_feature_set_bonus() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        add_set_bonuses(
+            &mut player_bonuses,
+            &[
+                (ItemFeature::Fire, ItemRarity::Common),
+                (ItemFeature::Fire, ItemRarity::Rare),
+                (ItemFeature::Water, ItemRarity::Epic),
+            ],
+        );
+
+        assert_eq!(player_bonuses.critical_chance, 200 + SET_BONUS_TWO_PIECES);
+
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        add_set_bonuses(
+            &mut player_bonuses,
+            &[
+                (ItemFeature::Magic, ItemRarity::Common),
+                (ItemFeature::Magic, ItemRarity::Rare),
+                (ItemFeature::Magic, ItemRarity::Epic),
+            ],
+        );
+
+        assert_eq!(player_bonuses.critical_chance, 200 + SET_BONUS_THREE_PIECES);
+        assert_eq!(player_bonuses.magic_find_chance, 0);
+        assert_eq!(player_bonuses.craft_upgrade_chance, 0);
+    }
+
+    #[test]
+    fn test_rarity_set_bonus() {
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        //Common pieces never make a set
+        add_set_bonuses(
+            &mut player_bonuses,
+            &[
+                (ItemFeature::Fire, ItemRarity::Common),
+                (ItemFeature::Water, ItemRarity::Common),
+                (ItemFeature::Earth, ItemRarity::Common),
+            ],
+        );
+
+        assert_eq!(player_bonuses.magic_find_chance, 0);
+        assert_eq!(player_bonuses.craft_upgrade_chance, 0);
+
+        add_set_bonuses(
+            &mut player_bonuses,
+            &[
+                (ItemFeature::Fire, ItemRarity::Rare),
+                (ItemFeature::Water, ItemRarity::Rare),
+                (ItemFeature::Earth, ItemRarity::Common),
+            ],
+        );
+
+        assert_eq!(player_bonuses.critical_chance, 200);
+        assert_eq!(player_bonuses.magic_find_chance, SET_BONUS_TWO_PIECES);
+        assert_eq!(player_bonuses.craft_upgrade_chance, SET_BONUS_TWO_PIECES);
+
+        let mut player_bonuses = create_player_bonuses_for_testing();
+
+        add_set_bonuses(
+            &mut player_bonuses,
+            &[
+                (ItemFeature::Earth, ItemRarity::Legendary),
+                (ItemFeature::Earth, ItemRarity::Legendary),
+                (ItemFeature::Earth, ItemRarity::Legendary),
+            ],
+        );
+
+        assert_eq!(player_bonuses.critical_chance, 200 + SET_BONUS_THREE_PIECES);
+        assert_eq!(player_bonuses.magic_find_chance, SET_BONUS_THREE_PIECES);
+        assert_eq!(player_bonuses.craft_upgrade_chance, SET_BONUS_THREE_PIECES);
+    }
+}
@@ -6,8 +6,10 @@ mod test_internal_functions {
     use lazy_static::lazy_static;
     use rand::random;
 
+    use crate::account::TurnData;
+    use crate::error::ErrorCode;
     use crate::Pubkey;
-    use crate::utils::RandomGenerator;
+    use crate::utils::{ACTION_CRAFT_INDEX, ACTION_LOOT_INDEX, ChestEntropy, CommitRevealEntropy, get_commitment, get_slot_hash, get_turn_entropy, RandomGenerator, SET_BONUS_TWO_PIECES, SlotHash, TurnCommit};
 
     lazy_static! {
         static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
@@ -68,4 +70,276 @@ mod test_internal_functions {
 
         assert_eq!(total_number, 20);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_random_weighted_index_skips_zero_weights() {
+        for _i in 0..20 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+            assert_eq!(rand.random_weighted_index(&[0, 5, 0]), 1);
+        }
+    }
+
+    #[test]
+    fn test_random_weighted_index_within_weights() {
+        let mut hash_map: HashMap<usize, u8> = HashMap::new();
+
+        for _i in 0..20 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+            let index = rand.random_weighted_index(&[1, 0, 1]);
+
+            hash_map.entry(index).and_modify(|e| *e += 1).or_insert(1);
+        }
+
+        //Index with no weight is never picked
+        assert!(hash_map.get(&1).is_none());
+        assert_eq!(hash_map.values().map(|value| *value as u16).sum::<u16>(), 20);
+    }
+
+    #[test]
+    fn test_commit_reveal_depends_on_secret_and_turn_entropy() {
+        let pubkey = Pubkey::new_unique();
+
+        let mut first = RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [1; 32],
+            turn_entropy: [2; 32],
+            pubkey,
+            action: 0,
+        });
+        let mut same = RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [1; 32],
+            turn_entropy: [2; 32],
+            pubkey,
+            action: 0,
+        });
+        let mut other_secret = RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [3; 32],
+            turn_entropy: [2; 32],
+            pubkey,
+            action: 0,
+        });
+        let mut other_turn = RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [1; 32],
+            turn_entropy: [3; 32],
+            pubkey,
+            action: 0,
+        });
+
+        let value = first.random::<u64, 8>();
+
+        assert_eq!(value, same.random::<u64, 8>());
+        assert_ne!(value, other_secret.random::<u64, 8>());
+        assert_ne!(value, other_turn.random::<u64, 8>());
+    }
+
+    #[test]
+    fn test_commit_reveal_actions_dont_share_numbers() {
+        let pubkey = Pubkey::new_unique();
+
+        let mut loot = RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [1; 32],
+            turn_entropy: [2; 32],
+            pubkey,
+            action: ACTION_LOOT_INDEX,
+        });
+        let mut craft = RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [1; 32],
+            turn_entropy: [2; 32],
+            pubkey,
+            action: ACTION_CRAFT_INDEX,
+        });
+
+        assert_ne!(loot.random::<u64, 8>(), craft.random::<u64, 8>());
+    }
+
+    #[test]
+    fn test_turn_entropy_differs_per_turn() {
+        let entropy = [7; 32];
+
+        //Turns added by the same crank share the slot hash but not their entropy
+        assert_ne!(get_turn_entropy(&entropy, 10), get_turn_entropy(&entropy, 11));
+        assert_eq!(get_turn_entropy(&entropy, 10), get_turn_entropy(&entropy, 10));
+        assert_ne!(get_turn_entropy(&entropy, 10), entropy);
+    }
+
+    fn create_slot_hashes_for_testing(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+
+        data
+    }
+
+    #[test]
+    fn test_get_slot_hash() {
+        //Most recent slot first, slot 98 was skipped
+        let slot_hashes = create_slot_hashes_for_testing(&[100, 99, 97]);
+
+        assert_eq!(get_slot_hash(&slot_hashes, 99), SlotHash::Hashed([99; 32]));
+        assert_eq!(get_slot_hash(&slot_hashes, 97), SlotHash::Hashed([97; 32]));
+        assert_eq!(get_slot_hash(&slot_hashes, 101), SlotHash::NotHashed);
+        assert_eq!(get_slot_hash(&create_slot_hashes_for_testing(&[]), 1), SlotHash::NotHashed);
+
+        //Never will be in it, whoever records it late can't pick another hash
+        assert_eq!(get_slot_hash(&slot_hashes, 98), SlotHash::Expired);
+        assert_eq!(get_slot_hash(&slot_hashes, 50), SlotHash::Expired);
+    }
+
+    #[test]
+    fn test_record_turn_entropy_only_from_crank_slot_hash() {
+        let slot_hashes = create_slot_hashes_for_testing(&[100, 99, 97]);
+
+        let mut not_hashed = TurnData { entropy_slot: 101, ..Default::default() };
+        assert_eq!(not_hashed.record_entropy(&slot_hashes, 5), Err(ErrorCode::TurnEntropyNotReady.into()));
+        assert_eq!(not_hashed.entropy, [0; 32]);
+
+        //Too late, there is no value to fall back on that whoever records it could pick
+        let mut expired = TurnData { entropy_slot: 50, ..Default::default() };
+        assert_eq!(expired.record_entropy(&slot_hashes, 5), Err(ErrorCode::TurnEntropyExpired.into()));
+        assert_eq!(expired.get_entropy(), Err(ErrorCode::TurnEntropyNotRecorded.into()));
+
+        let mut hashed = TurnData { entropy_slot: 99, ..Default::default() };
+        assert_eq!(hashed.record_entropy(&slot_hashes, 5), Ok(()));
+        assert_eq!(hashed.get_entropy(), Ok(get_turn_entropy(&[99; 32], 5)));
+
+        //Once recorded it stays, even after the slot ages out
+        assert_eq!(hashed.record_entropy(&create_slot_hashes_for_testing(&[700]), 5), Ok(()));
+        assert_eq!(hashed.get_entropy(), Ok(get_turn_entropy(&[99; 32], 5)));
+    }
+
+    #[test]
+    fn test_chest_entropy_only_depends_on_sealed_seed() {
+        let mut first = RandomGenerator::from_entropy(&ChestEntropy { seed: 5, pubkey: Pubkey::new_unique() });
+        let mut other_key = RandomGenerator::from_entropy(&ChestEntropy { seed: 5, pubkey: Pubkey::new_unique() });
+        let mut other_seed = RandomGenerator::from_entropy(&ChestEntropy { seed: 6, pubkey: Pubkey::new_unique() });
+
+        let value = first.random::<u64, 8>();
+
+        //Picking the chest key doesn't change its items
+        assert_eq!(value, other_key.random::<u64, 8>());
+        assert_ne!(value, other_seed.random::<u64, 8>());
+
+        //Chests looted before the seed existed roll on their key
+        let mut legacy = RandomGenerator::from_entropy(&ChestEntropy { seed: 0, pubkey: Pubkey::new_unique() });
+        let mut other_legacy = RandomGenerator::from_entropy(&ChestEntropy { seed: 0, pubkey: Pubkey::new_unique() });
+
+        assert_ne!(legacy.random::<u64, 8>(), other_legacy.random::<u64, 8>());
+    }
+
+    #[test]
+    fn test_turn_commit_commitment() {
+        let secret = [7; 32];
+        let mut turn_commit = TurnCommit::default();
+
+        //Nothing committed yet, no secret can be revealed
+        assert!(turn_commit.verify_secret(&secret).is_err());
+
+        assert!(turn_commit.commit([0; 32]).is_err());
+        assert!(turn_commit.commit(get_commitment(&secret)).is_ok());
+
+        //Every action of the turn uses the same commitment
+        assert!(turn_commit.commit(get_commitment(&secret)).is_ok());
+        assert!(turn_commit.commit(get_commitment(&[8; 32])).is_err());
+
+        assert!(turn_commit.verify_secret(&secret).is_ok());
+        assert!(turn_commit.verify_secret(&[8; 32]).is_err());
+    }
+
+    fn create_fixed_generator_for_testing() -> RandomGenerator {
+        //Fixed seed so the statistical tests can't randomly fail
+        RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [1; 32],
+            turn_entropy: [2; 32],
+            pubkey: Pubkey::default(),
+            action: 0,
+        })
+    }
+
+    #[test]
+    fn test_randomness_never_runs_out() {
+        let mut rand = create_fixed_generator_for_testing();
+
+        let first_block: Vec<u64> = (0..4).map(|_| rand.random::<u64, 8>()).collect();
+        let second_block: Vec<u64> = (0..4).map(|_| rand.random::<u64, 8>()).collect();
+
+        //Next block is a new hash, not the same bytes again
+        assert_ne!(first_block, second_block);
+
+        for _i in 0..10_000 {
+            rand.random::<u128, 16>();
+            rand.random_within_range::<u8, 1>(0, 255);
+        }
+    }
+
+    #[test]
+    fn test_randomness_with_range_is_uniform() {
+        let mut rand = create_fixed_generator_for_testing();
+        let draws = 50_000;
+        let mut counts = [0u32; 10];
+
+        for _i in 0..draws {
+            counts[rand.random_within_range::<u8, 1>(0, 9) as usize] += 1;
+        }
+
+        let expected = draws as f64 / counts.len() as f64;
+        let chi_square: f64 = counts
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        //Critical value for 9 degrees of freedom at p = 0.001
+        assert!(chi_square < 27.88, "chi square too high: {}", chi_square);
+    }
+
+    #[test]
+    fn test_randomness_with_range_has_no_modulo_bias() {
+        let mut rand = create_fixed_generator_for_testing();
+        let mut lowest_results = 0u32;
+        let mut middle_results = 0u32;
+
+        //With a modulo, 0..=55 would come up twice as often as 56..=111 since 256 isn't a multiple of 200
+        for _i in 0..60_000 {
+            let value = rand.random_within_range::<u8, 1>(0, 199);
+
+            if value < 56 {
+                lowest_results += 1;
+            } else if value < 112 {
+                middle_results += 1;
+            }
+        }
+
+        let ratio = lowest_results as f64 / middle_results as f64;
+
+        assert!(ratio > 0.9 && ratio < 1.1, "ratio too far from 1: {}", ratio);
+    }
+
+    #[test]
+    fn test_randomness_on_u16_with_range_stays_within_range() {
+        let mut rand = create_fixed_generator_for_testing();
+
+        for _i in 0..10_000 {
+            let value = rand.random_within_range::<u16, 2>(10_000, 10_999);
+
+            assert!(value >= 10_000 && value <= 10_999);
+        }
+    }
+
+    #[test]
+    fn test_is_rolled_within() {
+        let mut rand = create_fixed_generator_for_testing();
+
+        assert!((0..1_000).all(|_| !rand.is_rolled_within(0)));
+        assert!((0..1_000).all(|_| rand.is_rolled_within(10_000)));
+
+        //Smallest set bonus is 1%, within 0.2% of the rolls
+        let rolled = (0..100_000)
+            .filter(|_| rand.is_rolled_within(SET_BONUS_TWO_PIECES))
+            .count();
+
+        assert!((800..=1_200).contains(&rolled), "rolled {} times", rolled);
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod test_internal_functions {
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::account::TurnData;
+    use crate::GameRules;
+    use crate::utils::{
+        CommitRevealEntropy, create_caster_for_testing, get_turn_commit_to_redeem, ItemRarity, ItemType,
+        RandomGenerator, roll_spell, settle_turn_reward, SpellEffect, SpellSnapshot, SpellType, TurnCommit,
+    };
+
+    fn create_generator_for_testing(secret: u8) -> RandomGenerator {
+        RandomGenerator::from_entropy(&CommitRevealEntropy {
+            secret: [secret; 32],
+            turn_entropy: [2; 32],
+            pubkey: Pubkey::default(),
+            action: 0,
+        })
+    }
+
+    #[test]
+    fn test_get_turn_commit_to_redeem() {
+        let turn_commit = TurnCommit {
+            turn: 3,
+            ..Default::default()
+        };
+
+        assert!(get_turn_commit_to_redeem(None, 4).is_err());
+        //Turn isn't over yet
+        assert!(get_turn_commit_to_redeem(Some(turn_commit), 3).is_err());
+        assert_eq!(get_turn_commit_to_redeem(Some(turn_commit), 4).unwrap(), turn_commit);
+    }
+
+    #[test]
+    fn test_roll_spell_craft_does_nothing_by_itself() {
+        for secret in 0..50 {
+            let spell_snapshot = SpellSnapshot {
+                spell: SpellType::Craft,
+                rarity: ItemRarity::Legendary,
+                value: 0,
+                level: 1,
+            };
+
+            assert_eq!(roll_spell(&spell_snapshot, &mut create_generator_for_testing(secret)), SpellEffect::Nothing);
+        }
+    }
+
+    #[test]
+    fn test_roll_spell_item_keeps_level_and_rarity() {
+        let spell_snapshot = SpellSnapshot {
+            spell: SpellType::Item,
+            rarity: ItemRarity::Epic,
+            value: 0,
+            level: 7,
+        };
+
+        let mut items_given = 0;
+
+        for secret in 0..50 {
+            match roll_spell(&spell_snapshot, &mut create_generator_for_testing(secret)) {
+                SpellEffect::Nothing => {}
+                SpellEffect::Item(level, ItemType::Equipment { rarity, .. }) => {
+                    assert_eq!(level, 7);
+                    assert_eq!(rarity, ItemRarity::Epic);
+                    items_given += 1;
+                }
+                spell_effect => panic!("unexpected spell effect {:?}", spell_effect),
+            }
+        }
+
+        assert!(items_given > 0);
+    }
+
+    #[test]
+    fn test_settle_turn_reward() {
+        let rules = GameRules::default();
+        let mut caster = create_caster_for_testing();
+        let mut turn_data = TurnData {
+            resource_1_burned: 30,
+            resource_2_burned: 10,
+            ..Default::default()
+        };
+
+        caster.turn_commit = Some(TurnCommit {
+            turn: 1,
+            resources_burned: [10, 10, 0],
+            ..Default::default()
+        });
+
+        //Half the turn's burn, half the turn's LADA
+        assert_eq!(settle_turn_reward(&mut caster, &mut turn_data, &rules), rules.lada_distribution_per_turn / 2);
+        assert_eq!(caster.experience, 20);
+        assert_eq!(caster.turn_commit, None);
+        assert_eq!(turn_data.rewards_redeemed, 1);
+    }
+}
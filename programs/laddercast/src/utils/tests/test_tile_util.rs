@@ -6,8 +6,9 @@ mod test_internal_functions {
     use lazy_static::lazy_static;
     use rand::random;
 
-    use crate::{Tile, TileType};
-    use crate::utils::{create_tile_for_testing, cycle_tile, get_current_tile, get_highest_level_and_column, RandomGenerator};
+    use crate::{GameRules, Tile, TileBand, TileType};
+    use crate::account::TileHistory;
+    use crate::utils::{create_tile_for_testing, cycle_tile, get_current_tile, get_tile_band, get_highest_level_and_column, RandomGenerator, TILE_HISTORY_CAPACITY, TileChange, TileChangeKind};
 
     lazy_static! {
         static ref SLOT_HASHES: [u8; 512 * 40] = generate_slot_hashes(true).try_into().unwrap();
@@ -25,7 +26,7 @@ mod test_internal_functions {
     fn test_cycle_tile_tile_is_none() {
         let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
 
-        let generated_tile = cycle_tile(None, 1, &mut rand);
+        let generated_tile = cycle_tile(None, 1, 29, &GameRules::default(), &mut rand);
 
         assert!(generated_tile.life >= 3 && generated_tile.life <= 6);
         assert!(matches!(
@@ -40,7 +41,7 @@ mod test_internal_functions {
 
         let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
 
-        let generated_tile = cycle_tile(Some(current_tile), 1, &mut rand);
+        let generated_tile = cycle_tile(Some(current_tile), 1, 29, &GameRules::default(), &mut rand);
 
         assert!(generated_tile.life >= 3 && generated_tile.life <= 6);
         assert!(matches!(
@@ -55,7 +56,7 @@ mod test_internal_functions {
 
         let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
 
-        let generated_tile = cycle_tile(Some(current_tile), 1, &mut rand);
+        let generated_tile = cycle_tile(Some(current_tile), 1, 29, &GameRules::default(), &mut rand);
 
         assert_eq!(generated_tile.life, 1);
         assert_eq!(generated_tile.tile_type, TileType::Crafting);
@@ -67,7 +68,7 @@ mod test_internal_functions {
 
         let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
 
-        let generated_tile = cycle_tile(Some(current_tile), 1, &mut rand);
+        let generated_tile = cycle_tile(Some(current_tile), 1, 29, &GameRules::default(), &mut rand);
 
         assert_eq!(generated_tile.life, 1);
         assert_eq!(generated_tile.tile_type, TileType::Legendary);
@@ -80,15 +81,100 @@ mod test_internal_functions {
         let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
 
         let generated_tile =
-            cycle_tile(Some(current_tile), 29, &mut rand);
+            cycle_tile(Some(current_tile), 29, 29, &GameRules::default(), &mut rand);
 
         assert_eq!(generated_tile.life, 1);
         assert_eq!(generated_tile.tile_type, TileType::Legendary);
     }
 
+    #[test]
+    fn test_cycle_tile_is_resource_is_top_level_of_smaller_map() {
+        let current_tile = create_tile_for_testing(TileType::Fire, 1, false);
+
+        let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+        let top_level_tile =
+            cycle_tile(Some(current_tile), 9, 9, &GameRules::default(), &mut rand);
+        let below_top_level_tile =
+            cycle_tile(Some(current_tile), 8, 9, &GameRules::default(), &mut rand);
+
+        assert_eq!(top_level_tile.tile_type, TileType::Legendary);
+        assert_eq!(below_top_level_tile.tile_type, TileType::Crafting);
+    }
+
+    fn create_rules_with_scarce_fire_on_top() -> GameRules {
+        let mut rules = GameRules::default();
+
+        rules.tile_bands = [
+            TileBand { max_level: 9, weights: [0, 0, 1], min_life: 3, max_life: 3 },
+            TileBand { max_level: 19, weights: [1, 1, 1], min_life: 4, max_life: 6 },
+            TileBand { max_level: 29, weights: [1, 1, 0], min_life: 7, max_life: 8 },
+        ];
+
+        rules
+    }
+
+    #[test]
+    fn test_get_tile_band() {
+        let rules = create_rules_with_scarce_fire_on_top();
+
+        assert_eq!(get_tile_band(0, &rules).max_level, 9);
+        assert_eq!(get_tile_band(9, &rules).max_level, 9);
+        assert_eq!(get_tile_band(10, &rules).max_level, 19);
+        assert_eq!(get_tile_band(29, &rules).max_level, 29);
+        //Above the last band falls back on it
+        assert_eq!(get_tile_band(40, &rules).max_level, 29);
+    }
+
+    #[test]
+    fn test_cycle_tile_uses_band_of_level() {
+        let rules = create_rules_with_scarce_fire_on_top();
+
+        for _i in 0..20 {
+            let mut rand = RandomGenerator::new(SLOT_HASHES.as_slice(), Pubkey::new_unique());
+
+            let bottom_tile = cycle_tile(None, 0, 29, &rules, &mut rand);
+
+            assert_eq!(bottom_tile.tile_type, TileType::Fire);
+            assert_eq!(bottom_tile.life, 3);
+
+            let top_tile = cycle_tile(None, 25, 29, &rules, &mut rand);
+
+            assert_ne!(top_tile.tile_type, TileType::Fire);
+            assert!(top_tile.life >= 7 && top_tile.life <= 8);
+        }
+    }
+
+    #[test]
+    fn test_game_rules_validate_tile_bands() {
+        assert!(GameRules::default().validate().is_ok());
+        assert!(create_rules_with_scarce_fire_on_top().validate().is_ok());
+
+        let mut no_weights = GameRules::default();
+        no_weights.tile_bands[1].weights = [0, 0, 0];
+        assert!(no_weights.validate().is_err());
+
+        let mut no_life = GameRules::default();
+        no_life.tile_bands[0].min_life = 0;
+        assert!(no_life.validate().is_err());
+
+        let mut inverted_life = GameRules::default();
+        inverted_life.tile_bands[0].min_life = 5;
+        inverted_life.tile_bands[0].max_life = 4;
+        assert!(inverted_life.validate().is_err());
+
+        let mut unordered = GameRules::default();
+        unordered.tile_bands[1].max_level = 9;
+        assert!(unordered.validate().is_err());
+
+        let mut not_covering_map = GameRules::default();
+        not_covering_map.tile_bands[2].max_level = 20;
+        assert!(not_covering_map.validate().is_err());
+    }
+
     #[test]
     fn test_get_highest_level_and_column() {
-        let mut map: [[Option<Tile>; 3]; 30] = [[None; 3]; 30];
+        let mut map: Vec<Vec<Option<Tile>>> = vec![vec![None; 3]; 30];
 
         map[0][0] = Some(create_tile_for_testing(TileType::Fire, 1, false));
         map[1][0] = Some(create_tile_for_testing(TileType::Fire, 1, false));
@@ -104,7 +190,7 @@ mod test_internal_functions {
 
     #[test]
     fn test_get_current_tile() {
-        let mut map: [[Option<Tile>; 3]; 30] = [[None; 3]; 30];
+        let mut map: Vec<Vec<Option<Tile>>> = vec![vec![None; 3]; 30];
 
         map[0][0] = Some(create_tile_for_testing(TileType::Fire, 1, false));
         map[1][0] = Some(create_tile_for_testing(TileType::Fire, 1, false));
@@ -118,4 +204,78 @@ mod test_internal_functions {
         assert_eq!(current_tile.unwrap().is_first_time_spawning, map[2][1].unwrap().is_first_time_spawning);
         assert_eq!(current_tile.unwrap().life, map[2][1].unwrap().life);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_highest_level_and_column_custom_dimensions() {
+        let mut map: Vec<Vec<Option<Tile>>> = vec![vec![None; 5]; 10];
+
+        for column in 0..5 {
+            map[0][column] = Some(create_tile_for_testing(TileType::Fire, 1, false));
+        }
+        map[1][0] = Some(create_tile_for_testing(TileType::Fire, 1, false));
+
+        let (highest_level, highest_column) = get_highest_level_and_column(&map);
+
+        assert_eq!(highest_level, 1);
+        assert_eq!(highest_column, 0);
+    }
+
+    #[test]
+    fn test_get_current_tile_out_of_bounds() {
+        let mut map: Vec<Vec<Option<Tile>>> = vec![vec![None; 5]; 10];
+
+        map[0][4] = Some(create_tile_for_testing(TileType::Fire, 1, false));
+
+        assert!(get_current_tile(&map, 0, 4).is_some());
+        assert!(get_current_tile(&map, 0, 5).is_none());
+        assert!(get_current_tile(&map, 10, 0).is_none());
+    }
+
+    fn create_tile_change_for_testing(turn: u32) -> TileChange {
+        TileChange {
+            turn,
+            level: 0,
+            column: 0,
+            kind: TileChangeKind::Spawned,
+            tile_type: TileType::Fire,
+            life: 3,
+        }
+    }
+
+    #[test]
+    fn test_tile_history_record_not_full() {
+        let mut tile_history = TileHistory {
+            bump: 0,
+            game: Pubkey::new_unique(),
+            head: 0,
+            changes: vec![],
+        };
+
+        tile_history.record(create_tile_change_for_testing(1));
+        tile_history.record(create_tile_change_for_testing(2));
+
+        assert_eq!(tile_history.changes.len(), 2);
+        assert_eq!(tile_history.head, 2);
+        assert_eq!(tile_history.changes[1].turn, 2);
+    }
+
+    #[test]
+    fn test_tile_history_record_overwrites_oldest_when_full() {
+        let mut tile_history = TileHistory {
+            bump: 0,
+            game: Pubkey::new_unique(),
+            head: 0,
+            changes: vec![],
+        };
+
+        for turn in 0..(TILE_HISTORY_CAPACITY as u32 + 2) {
+            tile_history.record(create_tile_change_for_testing(turn));
+        }
+
+        assert_eq!(tile_history.changes.len(), TILE_HISTORY_CAPACITY);
+        assert_eq!(tile_history.head, 2);
+        assert_eq!(tile_history.changes[0].turn, TILE_HISTORY_CAPACITY as u32);
+        assert_eq!(tile_history.changes[1].turn, TILE_HISTORY_CAPACITY as u32 + 1);
+        assert_eq!(tile_history.changes[2].turn, 2);
+    }
+}
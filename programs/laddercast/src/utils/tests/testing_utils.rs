@@ -48,7 +48,9 @@ pub fn create_tile_for_testing(
 pub fn create_game_for_testing() -> Game {
     Game {
         authority: Pubkey::new_unique(),
-        map: [[None; 3]; 30],
+        levels: 30,
+        columns: 3,
+        map: vec![vec![None; 3]; 30],
         turn_info: GameTurnInfo {
             turn: 1,
             turn_delay: 2,
@@ -103,7 +105,8 @@ pub fn create_chest_for_testing() -> Item {
         owner: Pubkey::new_unique(),
         level: 3,
         item_type: ItemType::Chest {
-            tier: 2
+            tier: 2,
+            seed: 1,
         },
         equipped_owner: None,
     }
@@ -1,34 +1,43 @@
-use crate::{Tile, TileType};
-use crate::utils::{MAX_LEVEL_0_BASED, MINIMUM_TILE_LIFE, RandomGenerator};
+use strum::IntoEnumIterator;
 
-pub fn cycle_tile(tile: Option<Tile>, level: u8, rand: &mut RandomGenerator) -> Tile {
-    match tile {
-        None => {
-            let random_life = rand.random_within_range::<u8, 1>(0, 3);
+use crate::{GameRules, Tile, TileBand, TileType};
+use crate::utils::RandomGenerator;
 
-            Tile {
-                tile_type: rand.random_enum_within_range::<TileType>(0, 2),
-                life: MINIMUM_TILE_LIFE + random_life,
-                is_first_time_spawning: true,
-            }
-        }
+/// Band covering the level, levels above the last band use the last one
+pub fn get_tile_band(level: u8, rules: &GameRules) -> &TileBand {
+    rules
+        .tile_bands
+        .iter()
+        .find(|band| level <= band.max_level)
+        .unwrap_or(&rules.tile_bands[rules.tile_bands.len() - 1])
+}
+
+fn spawn_resource_tile(level: u8, rules: &GameRules, is_first_time_spawning: bool, rand: &mut RandomGenerator) -> Tile {
+    let band = get_tile_band(level, rules);
+
+    //Weights are in the same order as the resources in TileType
+    let resource_index = rand.random_weighted_index(&band.weights);
+
+    Tile {
+        tile_type: TileType::iter().nth(resource_index).unwrap(),
+        life: rand.random_within_range::<u8, 1>(band.min_life, band.max_life),
+        is_first_time_spawning,
+    }
+}
+
+/// top_level is the last level (0 based) of the map the tile is on
+pub fn cycle_tile(tile: Option<Tile>, level: u8, top_level: u8, rules: &GameRules, rand: &mut RandomGenerator) -> Tile {
+    match tile {
+        None => spawn_resource_tile(level, rules, true, rand),
         Some(tile) => {
             //if resource tile, then return crafting / if crafting tile then return resource
             match tile.tile_type {
-                TileType::Crafting | TileType::Legendary => {
-                    let random_life = rand.random_within_range::<u8, 1>(0, 3);
-
-                    Tile {
-                        tile_type: rand.random_enum_within_range::<TileType>(0, 2),
-                        life: MINIMUM_TILE_LIFE + random_life,
-                        is_first_time_spawning: false,
-                    }
-                }
+                TileType::Crafting | TileType::Legendary => spawn_resource_tile(level, rules, false, rand),
                 _ => {
                     let mut _feature: TileType;
 
-                    //Crafting tile level 30 are always legendary, if not they are normal crafting
-                    if level == MAX_LEVEL_0_BASED || tile.is_first_time_spawning {
+                    //Crafting tiles on the top level are always legendary, if not they are normal crafting
+                    if level == top_level || tile.is_first_time_spawning {
                         _feature = TileType::Legendary;
                     } else {
                         _feature = TileType::Crafting;
@@ -45,7 +54,7 @@ pub fn cycle_tile(tile: Option<Tile>, level: u8, rand: &mut RandomGenerator) ->
     }
 }
 
-pub fn get_highest_level_and_column(map: &[[Option<Tile>; 3]; 30]) -> (u8, u8) {
+pub fn get_highest_level_and_column(map: &[Vec<Option<Tile>>]) -> (u8, u8) {
     let mut highest_level: usize = 0;
     let mut highest_column: usize = 0;
 
@@ -62,7 +71,7 @@ pub fn get_highest_level_and_column(map: &[[Option<Tile>; 3]; 30]) -> (u8, u8) {
     (highest_level as u8, highest_column as u8)
 }
 
-pub fn get_current_tile(map: &[[Option<Tile>; 3]; 30], dest_level: u8, dest_column: u8) -> Option<&Tile> {
+pub fn get_current_tile(map: &[Vec<Option<Tile>>], dest_level: u8, dest_column: u8) -> Option<&Tile> {
     match map.get(dest_level as usize) {
         Some(lvl) => match lvl.get(dest_column as usize) {
             Some(tile) => tile.as_ref(),
@@ -72,7 +81,7 @@ pub fn get_current_tile(map: &[[Option<Tile>; 3]; 30], dest_level: u8, dest_colu
     }
 }
 
-pub fn get_current_tile_feature(map: &[[Option<TileType>; 3]; 30], dest_level: u8, dest_column: u8) -> Option<&TileType> {
+pub fn get_current_tile_feature(map: &[Vec<Option<TileType>>], dest_level: u8, dest_column: u8) -> Option<&TileType> {
     match map.get(dest_level as usize) {
         Some(lvl) => match lvl.get(dest_column as usize) {
             Some(tile_type) => tile_type.as_ref(),
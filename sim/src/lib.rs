@@ -0,0 +1,518 @@
+//! Headless simulation of a game, using the same rules as the program so balancing
+//! the economy doesn't need a deployment. Every action resolves at the crank of its turn.
+//!
+//! Casters loot, craft, climb, equip the best equipment they craft or find in chests and cast
+//! the spell books they find. Inventory, loadouts, salvaging, reforging and NFTs aren't modeled,
+//! an item that isn't equipped or cast is dropped.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::keccak::hashv;
+
+use laddercast::rules::{
+    advance_turn, Caster, CommittedActions, CraftingSnapshot, cycle_tile, EARTH_INDEX, EntropySource,
+    EquipmentType, FIRE_INDEX, Game, GameRules, GameTurnInfo, get_crafting_cost, get_current_tile,
+    get_lada_reward, get_move_cost, get_player_bonuses, give_exp_to_caster_resources_burned,
+    give_exp_to_caster_spell, ItemFeature, ItemRarity, ItemRecord, ItemType, MAX_MAP_LEVELS, Modifiers,
+    RandomGenerator, roll_crafted_item, roll_loot, roll_sealed_chest_contents, roll_spell, SpellEffect,
+    SpellSnapshot, TileType, WATER_INDEX,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SimConfig {
+    pub casters: u32,
+    pub cranks: u32,
+    /// Same seed, same simulation
+    pub seed: u64,
+    pub levels: u8,
+    pub columns: u8,
+    pub tile_spawn_delay: u32,
+    pub rules: GameRules,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            casters: 100,
+            cranks: 720,
+            seed: 0,
+            levels: MAX_MAP_LEVELS,
+            columns: 3,
+            tile_spawn_delay: 20,
+            rules: GameRules::default(),
+        }
+    }
+}
+
+/// What happened during a turn, resources are indexed with FIRE_INDEX, WATER_INDEX and EARTH_INDEX
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TurnReport {
+    pub turn: u32,
+    pub resources_looted: [u64; 3],
+    /// Given by successful resource spells
+    pub resources_from_spells: [u64; 3],
+    pub resources_burned: [u64; 3],
+    /// LADA (number * 10 ^ 9) given to the casters
+    pub lada_distributed: u64,
+    pub chests_found: u32,
+    pub items_crafted: u32,
+    /// Equipment crafted or found that replaced what a caster had equipped
+    pub items_equipped: u32,
+    pub spells_cast: u32,
+    pub spells_successful: u32,
+    pub average_experience: f64,
+    pub average_level: f64,
+    /// Highest tile level (0 based) a caster is on
+    pub highest_tile_level: u8,
+}
+
+/// Seed of the crank (stream 0) or of a caster (stream index + 1) for a turn
+struct SeededEntropy {
+    seed: u64,
+    turn: u32,
+    stream: u64,
+}
+
+impl EntropySource for SeededEntropy {
+    fn seed(&self) -> [u8; 32] {
+        hashv(&[
+            &self.seed.to_le_bytes(),
+            &self.turn.to_le_bytes(),
+            &self.stream.to_le_bytes(),
+        ])
+            .to_bytes()
+    }
+}
+
+pub struct SimCaster {
+    pub key: Pubkey,
+    pub caster: Caster,
+    pub resources: [u64; 3],
+    pub lada: u64,
+    /// Items in the head, robe and staff slots of the caster modifiers
+    pub equipped_items: Vec<ItemRecord>,
+    /// Spell books found, level and type, one is cast per turn
+    pub spell_books: Vec<(u8, ItemType)>,
+}
+
+pub struct Simulator {
+    pub config: SimConfig,
+    pub game: Game,
+    pub casters: Vec<SimCaster>,
+}
+
+fn get_resource_index(tile_type: TileType) -> Option<usize> {
+    match tile_type {
+        TileType::Fire => Some(FIRE_INDEX),
+        TileType::Water => Some(WATER_INDEX),
+        TileType::Earth => Some(EARTH_INDEX),
+        _ => None,
+    }
+}
+
+fn get_feature_resource_index(item_feature: ItemFeature) -> Option<usize> {
+    match item_feature {
+        ItemFeature::Fire => Some(FIRE_INDEX),
+        ItemFeature::Water => Some(WATER_INDEX),
+        ItemFeature::Earth => Some(EARTH_INDEX),
+        ItemFeature::Power | ItemFeature::Magic => None,
+    }
+}
+
+/// Key of a caster or an item, derived so the same seed gives the same keys
+fn get_item_key(seed: u64, turn: u32, caster_index: usize, item_index: usize) -> Pubkey {
+    Pubkey::new_from_array(
+        hashv(&[
+            &seed.to_le_bytes(),
+            &turn.to_le_bytes(),
+            &(caster_index as u64).to_le_bytes(),
+            &(item_index as u64).to_le_bytes(),
+        ])
+            .to_bytes(),
+    )
+}
+
+impl Simulator {
+    pub fn new(config: SimConfig) -> Self {
+        let mut game = Game {
+            authority: Pubkey::default(),
+            levels: config.levels,
+            columns: config.columns,
+            map: vec![vec![None; config.columns as usize]; config.levels as usize],
+            turn_info: GameTurnInfo {
+                turn: 1,
+                turn_delay: 0,
+                last_crank_seconds: 0,
+                last_tile_spawn: 0,
+                tile_spawn_delay: config.tile_spawn_delay,
+            },
+            last_turn_added: 1,
+            signer_bump: 0,
+            resource_1_mint_account: Pubkey::default(),
+            resource_2_mint_account: Pubkey::default(),
+            resource_3_mint_account: Pubkey::default(),
+            lada_mint_account: Pubkey::default(),
+            lada_token_account: Pubkey::default(),
+        };
+
+        let mut rand = RandomGenerator::from_entropy(&SeededEntropy {
+            seed: config.seed,
+            turn: 0,
+            stream: 0,
+        });
+
+        //First level starts full, same as init_game
+        for column in 0..config.columns as usize {
+            game.map[0][column] = Some(cycle_tile(None, 0, config.levels - 1, &config.rules, &mut rand));
+        }
+
+        let casters = (0..config.casters)
+            .map(|index| SimCaster {
+                key: get_item_key(config.seed, 0, index as usize, usize::MAX),
+                caster: Caster {
+                    version: 1,
+                    level: 1,
+                    experience: 0,
+                    owner: Pubkey::default(),
+                    modifiers: Modifiers {
+                        tile_level: 0,
+                        tile_column: rand.random_within_range::<u8, 1>(0, config.columns - 1),
+                        head: None,
+                        robe: None,
+                        staff: None,
+                        spell_book: None,
+                    },
+                    turn_commit: None,
+                },
+                resources: [0; 3],
+                lada: 0,
+                equipped_items: vec![],
+                spell_books: vec![],
+            })
+            .collect();
+
+        Self {
+            config,
+            game,
+            casters,
+        }
+    }
+
+    /// Runs every crank of the config
+    pub fn run(&mut self) -> Vec<TurnReport> {
+        (0..self.config.cranks).map(|_| self.step()).collect()
+    }
+
+    /// Plays a turn for every caster, hands out the rewards and pulls the crank
+    pub fn step(&mut self) -> TurnReport {
+        let rules = self.config.rules;
+        let turn = self.game.turn_info.turn;
+
+        let mut report = TurnReport {
+            turn,
+            ..Default::default()
+        };
+
+        let mut resources_burned_per_caster = vec![[0u64; 3]; self.casters.len()];
+
+        //What the turn data of the turn keeps, the redeems roll from it
+        let map = self.game.get_map_as_tile_features_only();
+
+        for (index, sim_caster) in self.casters.iter_mut().enumerate() {
+            let mut rand = RandomGenerator::from_entropy(&SeededEntropy {
+                seed: self.config.seed,
+                turn,
+                stream: index as u64 + 1,
+            });
+
+            SimTurn {
+                game: &self.game,
+                map: &map,
+                rules: &rules,
+                rand: &mut rand,
+                report: &mut report,
+                resources_burned: &mut resources_burned_per_caster[index],
+                seed: self.config.seed,
+                caster_index: index,
+                items_created: 0,
+            }
+                .play(sim_caster);
+        }
+
+        let total_resources_burned: u64 = resources_burned_per_caster.iter().flatten().sum();
+
+        for (sim_caster, resources_burned) in self.casters.iter_mut().zip(resources_burned_per_caster.iter()) {
+            give_exp_to_caster_resources_burned(
+                &mut sim_caster.caster,
+                Some(resources_burned[FIRE_INDEX]),
+                Some(resources_burned[EARTH_INDEX]),
+                Some(resources_burned[WATER_INDEX]),
+            );
+
+            let lada = get_lada_reward(
+                total_resources_burned,
+                resources_burned.iter().sum(),
+                rules.lada_distribution_per_turn,
+            );
+
+            sim_caster.lada += lada;
+            report.lada_distributed += lada;
+
+            for index in 0..3 {
+                report.resources_burned[index] += resources_burned[index];
+            }
+        }
+
+        let mut rand = RandomGenerator::from_entropy(&SeededEntropy {
+            seed: self.config.seed,
+            turn,
+            stream: 0,
+        });
+
+        advance_turn(&mut self.game, &rules, &mut rand);
+
+        if !self.casters.is_empty() {
+            let number_of_casters = self.casters.len() as f64;
+
+            report.average_experience =
+                self.casters.iter().map(|sim_caster| sim_caster.caster.experience as f64).sum::<f64>() / number_of_casters;
+            report.average_level =
+                self.casters.iter().map(|sim_caster| sim_caster.caster.level as f64).sum::<f64>() / number_of_casters;
+            report.highest_tile_level = self
+                .casters
+                .iter()
+                .map(|sim_caster| sim_caster.caster.modifiers.tile_level)
+                .max()
+                .unwrap();
+        }
+
+        report
+    }
+}
+
+/// Turn of a caster, resolved with the same rolls as the redeems. Items (and chests) it gets have a key
+/// derived from the caster and the number of items created before
+struct SimTurn<'a> {
+    game: &'a Game,
+    map: &'a [Vec<Option<TileType>>],
+    rules: &'a GameRules,
+    rand: &'a mut RandomGenerator,
+    report: &'a mut TurnReport,
+    resources_burned: &'a mut [u64; 3],
+    seed: u64,
+    caster_index: usize,
+    items_created: usize,
+}
+
+impl SimTurn<'_> {
+    /// Casts a spell book, loots a resource tile or crafts on a crafting tile, then climbs the ladder whenever possible
+    fn play(&mut self, sim_caster: &mut SimCaster) {
+        let rules = self.rules;
+        let (tile_level, tile_column) = (sim_caster.caster.modifiers.tile_level, sim_caster.caster.modifiers.tile_column);
+
+        let tile = match get_current_tile(&self.game.map, tile_level, tile_column) {
+            Some(tile) => *tile,
+            None => return,
+        };
+
+        let player_bonuses = get_player_bonuses(
+            &sim_caster.caster.modifiers,
+            &sim_caster.equipped_items,
+            &sim_caster.key,
+            rules.default_critical_chance_in_percent,
+        )
+            .expect("equipped items match the caster modifiers");
+
+        let spell = self.cast_spell(sim_caster);
+
+        match get_resource_index(tile.tile_type) {
+            Some(_) => {
+                let loot = roll_loot(self.map, &sim_caster.caster.modifiers, rules, &player_bonuses, self.rand)
+                    .expect("resource tiles can be looted");
+
+                sim_caster.resources[loot.resource_index] += loot.amount;
+                self.report.resources_looted[loot.resource_index] += loot.amount;
+
+                if let Some((chest_level, ItemType::Chest { tier, seed })) = loot.chest {
+                    self.report.chests_found += 1;
+
+                    //Opened right away
+                    let chest_key = self.next_item_key();
+
+                    for (item_level, item_type) in roll_sealed_chest_contents(rules, chest_key, chest_level, tier, seed) {
+                        match item_type {
+                            ItemType::SpellBook { .. } => sim_caster.spell_books.push((item_level, item_type)),
+                            _ => self.equip_if_better(sim_caster, item_level, item_type),
+                        }
+                    }
+                }
+            }
+            None => {
+                let per_resource_burn = get_crafting_cost(tile_level, rules);
+
+                if sim_caster.resources.iter().all(|amount| *amount >= per_resource_burn) {
+                    for index in 0..3 {
+                        sim_caster.resources[index] -= per_resource_burn;
+                        self.resources_burned[index] += per_resource_burn;
+                    }
+
+                    let actions = CommittedActions {
+                        spell,
+                        crafting: Some(CraftingSnapshot {
+                            min_level: tile_level + 1,
+                            min_rarity: ItemRarity::Common,
+                            max_rarity: if tile.tile_type == TileType::Legendary {
+                                ItemRarity::Legendary
+                            } else {
+                                ItemRarity::Epic
+                            },
+                        }),
+                        ..Default::default()
+                    };
+
+                    //Same as redeeming the craft, a craft spell cast this turn can add a level
+                    let (item_level, item_type) =
+                        roll_crafted_item(&actions, player_bonuses.craft_upgrade_chance, self.rand);
+
+                    self.report.items_crafted += 1;
+                    self.equip_if_better(sim_caster, item_level, item_type);
+                }
+            }
+        }
+
+        self.climb(sim_caster);
+    }
+
+    /// Casts the oldest spell book the caster can pay for, returns what was cast (a craft spell only acts on the craft)
+    fn cast_spell(&mut self, sim_caster: &mut SimCaster) -> Option<SpellSnapshot> {
+        let position = sim_caster.spell_books.iter().position(|(_, item_type)| match *item_type {
+            ItemType::SpellBook { cost_feature, cost, .. } => get_feature_resource_index(cost_feature)
+                .map_or(false, |index| sim_caster.resources[index] >= cost as u64),
+            _ => false,
+        })?;
+
+        let (item_level, spell_book) = sim_caster.spell_books.remove(position);
+
+        let (spell, cost_feature, rarity, cost, value) = match spell_book {
+            ItemType::SpellBook { spell, cost_feature, rarity, cost, value } => (spell, cost_feature, rarity, cost, value),
+            _ => return None,
+        };
+
+        //Cost is burned, it counts for experience and the LADA share
+        let cost_index = get_feature_resource_index(cost_feature)?;
+        sim_caster.resources[cost_index] -= cost as u64;
+        self.resources_burned[cost_index] += cost as u64;
+
+        self.report.spells_cast += 1;
+
+        let spell_snapshot = SpellSnapshot {
+            spell,
+            rarity,
+            value,
+            level: item_level,
+        };
+
+        match roll_spell(&spell_snapshot, self.rand) {
+            SpellEffect::Nothing => {}
+            SpellEffect::Resource { resource_index, amount } => {
+                sim_caster.resources[resource_index] += amount;
+                self.report.resources_from_spells[resource_index] += amount;
+                self.report.spells_successful += 1;
+            }
+            SpellEffect::Experience(amount) => {
+                give_exp_to_caster_spell(&mut sim_caster.caster, amount);
+                self.report.spells_successful += 1;
+            }
+            SpellEffect::Item(item_level, item_type) => {
+                self.equip_if_better(sim_caster, item_level, item_type);
+                self.report.spells_successful += 1;
+            }
+        }
+
+        Some(spell_snapshot)
+    }
+
+    /// Equipment replaces the one in its slot if it has a higher level, or the same level and a higher rarity
+    fn equip_if_better(&mut self, sim_caster: &mut SimCaster, item_level: u8, item_type: ItemType) {
+        let (equipment_type, rarity) = match item_type {
+            ItemType::Equipment { equipment_type, rarity, .. } => (equipment_type, rarity),
+            _ => return,
+        };
+
+        let modifiers = &mut sim_caster.caster.modifiers;
+        let slot = match equipment_type {
+            EquipmentType::Head => &mut modifiers.head,
+            EquipmentType::Robe => &mut modifiers.robe,
+            EquipmentType::Staff => &mut modifiers.staff,
+        };
+
+        let equipped = slot.and_then(|key| sim_caster.equipped_items.iter().position(|item| item.key == key));
+
+        if let Some(index) = equipped {
+            let item = &sim_caster.equipped_items[index];
+            let item_rarity = match item.item_type {
+                ItemType::Equipment { rarity, .. } => rarity,
+                _ => ItemRarity::Common,
+            };
+
+            if (item_level, rarity) <= (item.level, item_rarity) {
+                return;
+            }
+
+            sim_caster.equipped_items.swap_remove(index);
+        }
+
+        let key = self.next_item_key();
+
+        *slot = Some(key);
+        sim_caster.equipped_items.push(ItemRecord {
+            key,
+            id: None,
+            level: item_level,
+            item_type,
+            equipped_owner: Some(sim_caster.key),
+        });
+
+        self.report.items_equipped += 1;
+    }
+
+    fn next_item_key(&mut self) -> Pubkey {
+        let key = get_item_key(self.seed, self.report.turn, self.caster_index, self.items_created);
+        self.items_created += 1;
+
+        key
+    }
+
+    fn climb(&mut self, sim_caster: &mut SimCaster) {
+        let SimCaster { caster, resources, .. } = sim_caster;
+        let (tile_level, tile_column) = (caster.modifiers.tile_level, caster.modifiers.tile_column);
+
+        //Level is 1 based but map is 0 based
+        let dest_level = tile_level + 1;
+
+        //Too low for the next level, burns what it holds for experience (manual resource burn)
+        if dest_level > caster.level - 1 {
+            for index in 0..3 {
+                self.resources_burned[index] += resources[index];
+                resources[index] = 0;
+            }
+
+            return;
+        }
+
+        if let Some(dest_tile) = get_current_tile(&self.game.map, dest_level, tile_column) {
+            if let Some(index) = get_resource_index(dest_tile.tile_type) {
+                let resource_burned = get_move_cost(dest_level, self.rules);
+
+                if resources[index] >= resource_burned {
+                    resources[index] -= resource_burned;
+                    self.resources_burned[index] += resource_burned;
+
+                    caster.modifiers.tile_level = dest_level;
+                }
+            }
+        }
+    }
+}
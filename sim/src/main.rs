@@ -0,0 +1,45 @@
+//! Prints the curves of a simulated game as CSV
+//! Usage: laddercast-sim [casters] [cranks] [seed]
+
+use std::env;
+
+use laddercast_sim::{SimConfig, Simulator};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let default_config = SimConfig::default();
+
+    let config = SimConfig {
+        casters: args.get(0).map_or(default_config.casters, |arg| arg.parse().expect("casters must be a number")),
+        cranks: args.get(1).map_or(default_config.cranks, |arg| arg.parse().expect("cranks must be a number")),
+        seed: args.get(2).map_or(default_config.seed, |arg| arg.parse().expect("seed must be a number")),
+        ..default_config
+    };
+
+    println!("turn,fire_looted,water_looted,earth_looted,fire_from_spells,water_from_spells,earth_from_spells,fire_burned,water_burned,earth_burned,lada_distributed,chests_found,items_crafted,items_equipped,spells_cast,spells_successful,average_experience,average_level,highest_tile_level");
+
+    for report in Simulator::new(config).run() {
+        println!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{:.2},{:.2},{}",
+            report.turn,
+            report.resources_looted[0],
+            report.resources_looted[1],
+            report.resources_looted[2],
+            report.resources_from_spells[0],
+            report.resources_from_spells[1],
+            report.resources_from_spells[2],
+            report.resources_burned[0],
+            report.resources_burned[1],
+            report.resources_burned[2],
+            report.lada_distributed,
+            report.chests_found,
+            report.items_crafted,
+            report.items_equipped,
+            report.spells_cast,
+            report.spells_successful,
+            report.average_experience,
+            report.average_level,
+            report.highest_tile_level,
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{SimConfig, Simulator};
+
+fn create_config_for_testing(seed: u64) -> SimConfig {
+    SimConfig {
+        casters: 10,
+        cranks: 200,
+        seed,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_same_seed_same_simulation() {
+    let first = Simulator::new(create_config_for_testing(1)).run();
+    let second = Simulator::new(create_config_for_testing(1)).run();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_different_seed_different_simulation() {
+    let first = Simulator::new(create_config_for_testing(1)).run();
+    let second = Simulator::new(create_config_for_testing(2)).run();
+
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_casters_progress() {
+    let mut simulator = Simulator::new(create_config_for_testing(1));
+    let reports = simulator.run();
+
+    assert_eq!(reports.len(), 200);
+    assert_eq!(simulator.game.turn_info.turn, 201);
+
+    //Resource spells give resources too
+    let total_looted: u64 = reports
+        .iter()
+        .map(|report| report.resources_looted.iter().chain(report.resources_from_spells.iter()).sum::<u64>())
+        .sum();
+    let total_burned: u64 = reports.iter().map(|report| report.resources_burned.iter().sum::<u64>()).sum();
+
+    assert!(total_looted > 0);
+    assert!(total_burned <= total_looted);
+    assert!(reports.last().unwrap().average_experience > 0.0);
+
+    //The whole turn distribution goes to the casters as soon as one of them burns
+    for report in reports.iter() {
+        if report.resources_burned.iter().sum::<u64>() > 0 {
+            assert!(report.lada_distributed > 0);
+        }
+    }
+}
+
+#[test]
+fn test_casters_equip_items_and_cast_spells() {
+    let mut simulator = Simulator::new(SimConfig {
+        casters: 20,
+        cranks: 400,
+        ..create_config_for_testing(1)
+    });
+    let reports = simulator.run();
+
+    assert!(reports.iter().map(|report| report.items_equipped).sum::<u32>() > 0);
+    assert!(reports.iter().map(|report| report.spells_cast).sum::<u32>() > 0);
+
+    //Equipped items go in the slot of their equipment type and count in the bonuses
+    for sim_caster in simulator.casters.iter() {
+        let modifiers = &sim_caster.caster.modifiers;
+        let slots = [modifiers.head, modifiers.robe, modifiers.staff];
+
+        assert_eq!(slots.iter().flatten().count(), sim_caster.equipped_items.len());
+
+        for item in sim_caster.equipped_items.iter() {
+            assert!(slots.contains(&Some(item.key)));
+            assert_eq!(item.equipped_owner, Some(sim_caster.key));
+        }
+    }
+}
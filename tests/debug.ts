@@ -0,0 +1,24 @@
+import { Idl } from "@project-serum/anchor";
+import fs from "fs";
+
+//Debug instructions only exist when the program is built with the "debug" feature,
+//they aren't in the program IDL. `yarn test-debug` generates theirs from the account
+//structs in test_helper.rs (anchor idl parse), so the tests never describe them by hand
+const DEBUG_IDL_PATH = "target/idl/laddercast_debug.json";
+
+function loadDebugIdl(): Idl {
+  if (!fs.existsSync(DEBUG_IDL_PATH)) {
+    throw new Error(
+      `${DEBUG_IDL_PATH} is missing, run the tests with yarn test-debug`
+    );
+  }
+
+  return JSON.parse(fs.readFileSync(DEBUG_IDL_PATH, "utf8"));
+}
+
+export const DEBUG_INSTRUCTIONS: Idl["instructions"] =
+  loadDebugIdl().instructions;
+
+export function withDebugInstructions(idl: Idl): Idl {
+  return { ...idl, instructions: [...idl.instructions, ...DEBUG_INSTRUCTIONS] };
+}
@@ -13,7 +13,9 @@ import {
   SYSVAR_SLOT_HASHES_PUBKEY,
 } from "@solana/web3.js";
 import assert from "assert";
-import { GameTurnInfo } from "./interface";
+import keccak256 from "keccak256";
+import { DEBUG_INSTRUCTIONS, withDebugInstructions } from "./debug";
+import { GameRules, GameTurnInfo } from "./interface";
 
 describe("laddercast", () => {
   //Connection
@@ -22,10 +24,20 @@ describe("laddercast", () => {
   //Programs
   const program = anchor.workspace.Laddercast as Program<Laddercast>;
 
+  //Same program with the debug instructions, only there when built with the "debug" feature
+  const debugProgram = new anchor.Program(
+    withDebugInstructions(program.idl),
+    program.programId,
+    program.provider
+  );
+
   //Game related
   const gameAuthority = anchor.web3.Keypair.generate();
 
+  let programAdmin: anchor.web3.PublicKey;
+
   let gameAccount: anchor.web3.Keypair;
+  let gameConfig: anchor.web3.PublicKey;
   let gameLADATokenAccount: anchor.web3.PublicKey;
 
   //Player
@@ -50,8 +62,15 @@ describe("laddercast", () => {
   //Caster
   let caster: anchor.web3.Keypair;
 
+  //Secret of the caster for the turn, only its hash is sent when committing
+  const casterSecret = Array.from(
+    anchor.web3.Keypair.generate().publicKey.toBytes()
+  );
+  const casterCommitment = Array.from(keccak256(Buffer.from(casterSecret)));
+
   //Items
   let spellBook: anchor.web3.Keypair;
+  let reforgedItem: anchor.web3.Keypair;
 
   //Constants
   const DECIMALS = 1_000_000_000;
@@ -64,6 +83,40 @@ describe("laddercast", () => {
     tileSpawnDelay: 20,
   } as GameTurnInfo;
 
+  const gameRules = {
+    moveCostMultiplier: 10,
+    craftingCostMultiplier: 5,
+    tileBands: [
+      { maxLevel: 9, weights: [1, 1, 1], minLife: 3, maxLife: 6 },
+      { maxLevel: 19, weights: [1, 1, 1], minLife: 3, maxLife: 6 },
+      { maxLevel: 29, weights: [1, 1, 1], minLife: 3, maxLife: 6 },
+    ],
+    defaultCriticalChanceInPercent: 200,
+    defaultMagicFindInPercent: 1000,
+    ladaDistributionPerTurn: new anchor.BN(1_984_126_984_130),
+    costInLadaForCaster: 1_000,
+    crankReward: { none: {} },
+    turnDataRetention: 720,
+    chestTiers: [
+      { minItemLevel: 1, itemCount: 3, spellBookChance: 25, rarityWeights: [80, 15, 4, 1] },
+      { minItemLevel: 6, itemCount: 3, spellBookChance: 25, rarityWeights: [70, 20, 8, 2] },
+      { minItemLevel: 11, itemCount: 3, spellBookChance: 25, rarityWeights: [60, 25, 12, 3] },
+      { minItemLevel: 16, itemCount: 3, spellBookChance: 25, rarityWeights: [50, 30, 15, 5] },
+    ],
+    teleportCostMultiplier: 20,
+  } as GameRules;
+
+  const mapLevels = 30;
+  const mapColumns = 3;
+
+  //Rejects with the program error of that message, not for any other reason (missing account...)
+  async function assertRejectsWith(promise: Promise<any>, msg: string) {
+    await assert.rejects(promise, (err: any) => {
+      assert.equal(err.msg, msg);
+      return true;
+    });
+  }
+
   async function getTokenAccountBalance(pubkey: anchor.web3.PublicKey) {
     return Number.parseInt(
       (await connection.getTokenAccountBalance(pubkey as PublicKey)).value
@@ -86,6 +139,94 @@ describe("laddercast", () => {
     )[0];
   }
 
+  it("Doesn't expose debug instructions in the program interface", async () => {
+    const instructionNames = program.idl.instructions.map((ix) => ix.name);
+
+    for (const debugInstruction of DEBUG_INSTRUCTIONS) {
+      assert.ok(!instructionNames.includes(debugInstruction.name));
+    }
+  });
+
+  it("Initializes program admin", async () => {
+    const provider = anchor.Provider.local();
+
+    anchor.setProvider(provider);
+
+    [programAdmin] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("program_admin")],
+      program.programId
+    );
+
+    //Program data holds the upgrade authority, which is the first admin
+    const [programData] = await anchor.web3.PublicKey.findProgramAddress(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    await program.rpc.initProgramAdmin({
+      accounts: {
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        programData: programData,
+        programAdmin: programAdmin,
+      },
+    });
+
+    assert.equal(
+      (await program.account.programAdmin.fetch(programAdmin)).admin.toString(),
+      provider.wallet.publicKey.toString()
+    );
+
+    //Only the admin can propose a new admin
+    await assertRejectsWith(
+      program.rpc.proposeAdmin(someGuy.publicKey, {
+        accounts: {
+          authority: someGuy.publicKey,
+          programAdmin: programAdmin,
+        },
+        signers: [someGuy],
+      }),
+      "Only super admin can create new game."
+    );
+
+    await program.rpc.proposeAdmin(gameAuthority.publicKey, {
+      accounts: {
+        authority: provider.wallet.publicKey,
+        programAdmin: programAdmin,
+      },
+    });
+
+    //Only the proposed admin can accept
+    await assertRejectsWith(
+      program.rpc.acceptAdmin({
+        accounts: {
+          authority: someGuy.publicKey,
+          programAdmin: programAdmin,
+        },
+        signers: [someGuy],
+      }),
+      "Signer is not the pending program admin."
+    );
+
+    await program.rpc.acceptAdmin({
+      accounts: {
+        authority: gameAuthority.publicKey,
+        programAdmin: programAdmin,
+      },
+      signers: [gameAuthority],
+    });
+
+    const fetchedProgramAdmin = await program.account.programAdmin.fetch(
+      programAdmin
+    );
+
+    assert.equal(
+      fetchedProgramAdmin.admin.toString(),
+      gameAuthority.publicKey.toString()
+    );
+    assert.equal(fetchedProgramAdmin.pendingAdmin, null);
+  });
+
   it("Initializes game", async () => {
     const con = anchor.Provider.local();
 
@@ -120,7 +261,7 @@ describe("laddercast", () => {
     );
 
     const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("game_signer")],
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
       program.programId
     );
 
@@ -168,15 +309,53 @@ describe("laddercast", () => {
       program.programId
     );
 
-    await program.rpc.initGame(gameTurnInfo, {
+    [gameConfig] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_config"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    //Only the program admin can create a game
+    await assertRejectsWith(
+      program.rpc.initGame(gameTurnInfo, gameRules, mapLevels, mapColumns, {
+        accounts: {
+          authority: someGuy.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: "SysvarRent111111111111111111111111111111111",
+          programAdmin: programAdmin,
+          gameAccount: gameAccount.publicKey,
+          gameSigner: gameSigner,
+          gameTurnData: gameTurnData,
+          gameConfig: gameConfig,
+          slots: "SysvarS1otHashes111111111111111111111111111",
+          resource1Mint: mintResource1.publicKey,
+          resource2Mint: mintResource2.publicKey,
+          resource3Mint: mintResource3.publicKey,
+          ladaMint: ladaMint.publicKey,
+          ladaTokenAccount: gameLADATokenAccount,
+        },
+        signers: [
+          someGuy,
+          gameAccount,
+          mintResource1,
+          mintResource2,
+          mintResource3,
+        ],
+      }),
+      "Only super admin can create new game."
+    );
+
+    await program.rpc.initGame(gameTurnInfo, gameRules, mapLevels, mapColumns, {
       accounts: {
         authority: gameAuthority.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         rent: "SysvarRent111111111111111111111111111111111",
+        programAdmin: programAdmin,
         gameAccount: gameAccount.publicKey,
         gameSigner: gameSigner,
         gameTurnData: gameTurnData,
+        gameConfig: gameConfig,
         slots: "SysvarS1otHashes111111111111111111111111111",
         resource1Mint: mintResource1.publicKey,
         resource2Mint: mintResource2.publicKey,
@@ -196,6 +375,11 @@ describe("laddercast", () => {
     //Assertions
     let createdGame = await program.account.game.fetch(gameAccount.publicKey);
 
+    assert.equal(createdGame.levels, mapLevels);
+    assert.equal(createdGame.columns, mapColumns);
+    assert.equal(createdGame.map.length, mapLevels);
+    assert.equal(createdGame.map[0].length, mapColumns);
+
     assert.equal(createdGame.map[0][0] !== null, true);
     assert.equal(createdGame.map[0][1] !== null, true);
     assert.equal(createdGame.map[0][2] !== null, true);
@@ -208,6 +392,157 @@ describe("laddercast", () => {
 
     assert.deepEqual(createdGame.turnInfo, gameTurnInfo);
     assert.equal(createdGame.lastTurnAdded, 1);
+
+    const createdGameConfig = await program.account.gameConfig.fetch(
+      gameConfig
+    );
+
+    assert.deepEqual(createdGameConfig.game, gameAccount.publicKey);
+    assert.equal(createdGameConfig.rules.moveCostMultiplier, 10);
+    assert.equal(createdGameConfig.rules.teleportCostMultiplier, 20);
+    assert.equal(createdGameConfig.rules.costInLadaForCaster, 1_000);
+    assert.equal(createdGameConfig.rules.tileBands.length, 3);
+    assert.equal(createdGameConfig.rules.tileBands[2].maxLevel, 29);
+  });
+
+  it("updates game config", async () => {
+    await program.rpc.updateGameConfig(
+      { ...gameRules, craftingCostMultiplier: 6 },
+      {
+        accounts: {
+          authority: gameAuthority.publicKey,
+          programAdmin: programAdmin,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+        },
+        signers: [gameAuthority],
+      }
+    );
+
+    assert.equal(
+      (await program.account.gameConfig.fetch(gameConfig)).rules
+        .craftingCostMultiplier,
+      6
+    );
+
+    //A band where no resource can spawn is rejected
+    await assert.rejects(
+      program.rpc.updateGameConfig(
+        {
+          ...gameRules,
+          tileBands: [
+            gameRules.tileBands[0],
+            { ...gameRules.tileBands[1], weights: [0, 0, 0] },
+            gameRules.tileBands[2],
+          ],
+        },
+        {
+          accounts: {
+            authority: gameAuthority.publicKey,
+            programAdmin: programAdmin,
+            game: gameAccount.publicKey,
+            gameConfig: gameConfig,
+          },
+          signers: [gameAuthority],
+        }
+      )
+    );
+
+    //A chest tier giving no item is rejected
+    await assert.rejects(
+      program.rpc.updateGameConfig(
+        {
+          ...gameRules,
+          chestTiers: [
+            gameRules.chestTiers[0],
+            gameRules.chestTiers[1],
+            gameRules.chestTiers[2],
+            { ...gameRules.chestTiers[3], itemCount: 0 },
+          ],
+        },
+        {
+          accounts: {
+            authority: gameAuthority.publicKey,
+            programAdmin: programAdmin,
+            game: gameAccount.publicKey,
+            gameConfig: gameConfig,
+          },
+          signers: [gameAuthority],
+        }
+      )
+    );
+
+    //Only the program admin can change the rules
+    await assertRejectsWith(
+      program.rpc.updateGameConfig(gameRules, {
+        accounts: {
+          authority: someGuy.publicKey,
+          programAdmin: programAdmin,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+        },
+        signers: [someGuy],
+      }),
+      "Only super admin can create new game."
+    );
+
+    await program.rpc.updateGameConfig(gameRules, {
+      accounts: {
+        authority: gameAuthority.publicKey,
+        programAdmin: programAdmin,
+        game: gameAccount.publicKey,
+        gameConfig: gameConfig,
+      },
+      signers: [gameAuthority],
+    });
+  });
+
+  it("uses a game signer unique to the game", async () => {
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+    const [legacyGameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer")],
+      program.programId
+    );
+
+    const resource1Mint = new Token(
+      connection,
+      mintResource1.publicKey,
+      TOKEN_PROGRAM_ID,
+      gameAuthority
+    );
+
+    assert.equal(
+      (await resource1Mint.getMintInfo()).mintAuthority.toString(),
+      gameSigner.toString()
+    );
+
+    //Nothing to migrate for a game created with its own signer
+    const newLADATokenAccount = anchor.web3.Keypair.generate();
+
+    await assert.rejects(
+      program.rpc.migrateGameSigner({
+        accounts: {
+          authority: gameAuthority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+          legacyGameSigner: legacyGameSigner,
+          gameSigner: gameSigner,
+          resource1MintAccount: mintResource1.publicKey,
+          resource2MintAccount: mintResource2.publicKey,
+          resource3MintAccount: mintResource3.publicKey,
+          ladaMint: ladaMint.publicKey,
+          ladaTokenAccount: gameLADATokenAccount,
+          newLadaTokenAccount: newLADATokenAccount.publicKey,
+        },
+        signers: [gameAuthority, newLADATokenAccount],
+      })
+    );
   });
 
   it("Initializes player", async () => {
@@ -252,6 +587,7 @@ describe("laddercast", () => {
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
         instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -305,10 +641,10 @@ describe("laddercast", () => {
       program.programId
     );
 
-    const chest = { chest: { tier: 1 } };
+    const chest = { chest: { tier: 1, seed: 1 } };
     const chestItem = anchor.web3.Keypair.generate();
 
-    await program.rpc.giveItem(chest, new anchor.BN(2), {
+    await debugProgram.rpc.giveItem(chest, new anchor.BN(2), {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         game: gameAccount.publicKey,
@@ -326,12 +662,12 @@ describe("laddercast", () => {
       anchor.web3.Keypair.generate(),
     ];
 
-    await program.rpc.openChest({
+    await program.rpc.openChest(null, {
       accounts: {
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         authority: someGuy.publicKey,
         player: playerAccount,
-        slots: "SysvarS1otHashes111111111111111111111111111",
         instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
         chest: chestItem.publicKey,
         item1: newItems[0].publicKey,
@@ -376,7 +712,7 @@ describe("laddercast", () => {
 
     const equipmentItem = anchor.web3.Keypair.generate();
 
-    await program.rpc.giveItem(equipment, new anchor.BN(1), {
+    await debugProgram.rpc.giveItem(equipment, new anchor.BN(1), {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         game: gameAccount.publicKey,
@@ -388,7 +724,7 @@ describe("laddercast", () => {
       signers: [someGuy, equipmentItem],
     });
 
-    await program.rpc.equipItem({
+    await program.rpc.equipItem(null, {
       accounts: {
         game: gameAccount.publicKey,
         authority: someGuy.publicKey,
@@ -404,7 +740,7 @@ describe("laddercast", () => {
     );
     assert.deepEqual(equippedItem.equippedOwner, caster.publicKey);
 
-    await program.rpc.unequipItem({
+    await program.rpc.unequipItem(null, {
       accounts: {
         game: gameAccount.publicKey,
         authority: someGuy.publicKey,
@@ -429,7 +765,7 @@ describe("laddercast", () => {
     };
     const spellbookItem = anchor.web3.Keypair.generate();
 
-    await program.rpc.giveItem(spellbook, new anchor.BN(1), {
+    await debugProgram.rpc.giveItem(spellbook, new anchor.BN(1), {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         game: gameAccount.publicKey,
@@ -441,7 +777,7 @@ describe("laddercast", () => {
       signers: [someGuy, spellbookItem],
     });
 
-    await program.rpc.equipItem({
+    await program.rpc.equipItem(null, {
       accounts: {
         game: gameAccount.publicKey,
         authority: someGuy.publicKey,
@@ -457,7 +793,7 @@ describe("laddercast", () => {
     );
     assert.deepEqual(equippedSpellBook.equippedOwner, caster.publicKey);
 
-    await program.rpc.unequipItem({
+    await program.rpc.unequipItem(null, {
       accounts: {
         game: gameAccount.publicKey,
         authority: someGuy.publicKey,
@@ -474,188 +810,491 @@ describe("laddercast", () => {
     assert.deepEqual(equippedSpellBook.equippedOwner, null);
   });
 
-  it("commit spell", async () => {
+  it("inventory", async () => {
     const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
       [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
       program.programId
     );
-    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
-      [
-        Buffer.from("turn_data"),
-        gameAccount.publicKey.toBuffer(),
-        Buffer.from(anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn))),
-      ],
-      program.programId
-    );
-
-    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("game_signer")],
+    const [inventoryAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("inventory"), playerAccount.toBuffer()],
       program.programId
     );
 
-    await program.rpc.giveResources(new anchor.BN(500), {
+    await program.rpc.initInventory({
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
-        gameSigner: gameSigner,
         game: gameAccount.publicKey,
         player: playerAccount,
-        resource1MintAccount: mintResource1.publicKey,
-        resource2MintAccount: mintResource2.publicKey,
-        resource3MintAccount: mintResource3.publicKey,
-        resource1TokenAccount: ATAResource1,
-        resource2TokenAccount: ATAResource2,
-        resource3TokenAccount: ATAResource3,
+        inventory: inventoryAccount,
       },
       signers: [someGuy],
     });
 
-    const spellbook = {
-      spellBook: {
-        spell: { fire: {} },
-        costFeature: { fire: {} },
+    const equipment = {
+      equipment: {
+        feature: { magic: {} },
         rarity: { common: {} },
-        cost: 3,
+        equipmentType: { robe: {} },
         value: 1,
       },
     };
-    spellBook = anchor.web3.Keypair.generate();
 
-    await program.rpc.giveItem(spellbook, new anchor.BN(1), {
+    const equipmentItem = anchor.web3.Keypair.generate();
+
+    await debugProgram.rpc.giveItem(equipment, new anchor.BN(1), {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         game: gameAccount.publicKey,
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: spellBook.publicKey,
+        item: equipmentItem.publicKey,
       },
-      signers: [someGuy, spellBook],
+      signers: [someGuy, equipmentItem],
     });
 
-    await program.rpc.equipItem({
+    await program.rpc.migrateItemToInventory({
       accounts: {
-        game: gameAccount.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
         authority: someGuy.publicKey,
+        game: gameAccount.publicKey,
         player: playerAccount,
-        caster: caster.publicKey,
-        item: spellBook.publicKey,
+        inventory: inventoryAccount,
+        item: equipmentItem.publicKey,
       },
       signers: [someGuy],
     });
 
-    const turnResource1Burned = (
-      await program.account.turnData.fetch(gameTurnData)
-    ).resource1Burned;
+    //Item account is closed, the item lives in the inventory
+    assert.equal(
+      await program.account.item.fetchNullable(equipmentItem.publicKey),
+      null
+    );
 
-    const someGuyFireResource = await getTokenAccountBalance(ATAResource1);
+    let inventory = await program.account.inventory.fetch(inventoryAccount);
+    assert.equal(inventory.items.length, 1);
+    assert.equal(inventory.items[0].id, 0);
+    assert.deepEqual(inventory.items[0].itemType, equipment);
 
-    await program.rpc.casterCommitSpell({
+    await program.rpc.equipItem(0, {
       accounts: {
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        rent: SYSVAR_RENT_PUBKEY,
-        authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        authority: someGuy.publicKey,
         player: playerAccount,
         caster: caster.publicKey,
-        slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
-        resource1MintAccount: mintResource1.publicKey,
-        resource2MintAccount: mintResource2.publicKey,
-        resource3MintAccount: mintResource3.publicKey,
-        resource1TokenAccount: ATAResource1,
-        resource2TokenAccount: ATAResource2,
-        resource3TokenAccount: ATAResource3,
-        spellbook: spellBook.publicKey,
-        gameTurnData: gameTurnData,
+        item: inventoryAccount,
       },
       signers: [someGuy],
     });
 
-    //Assert game turn data resource burned higher
-    assert.equal(
-      (
-        await program.account.turnData.fetch(gameTurnData)
-      ).resource1Burned.toNumber(),
-      turnResource1Burned.add(new anchor.BN(3)).toNumber()
-    );
-
-    //Assert player has burned some resources
-    assert.equal(
-      await getTokenAccountBalance(ATAResource1),
-      someGuyFireResource - 3
-    );
-
-    //Assert turn commit created (deep equal) (actions & order actions)
-    const fetchedCaster = await program.account.caster.fetch(caster.publicKey);
-
-    assert.equal(fetchedCaster.turnCommit.resourcesBurned[0].toNumber(), 3);
-    assert.equal(fetchedCaster.turnCommit.resourcesBurned[1].toNumber(), 0);
-    assert.equal(fetchedCaster.turnCommit.resourcesBurned[2].toNumber(), 0);
-
-    delete fetchedCaster.turnCommit.resourcesBurned;
+    inventory = await program.account.inventory.fetch(inventoryAccount);
+    assert.deepEqual(inventory.items[0].equippedOwner, caster.publicKey);
 
-    assert.deepEqual(fetchedCaster.turnCommit, {
-      turn: 1,
-      actions: {
-        loot: false,
-        spell: {
-          isExtraLevelBonus: false,
-        },
-        mv: null,
-        crafting: null,
-        actionOrder: [0, 1, 0, 0],
+    await program.rpc.unequipItem(0, {
+      accounts: {
+        game: gameAccount.publicKey,
+        authority: someGuy.publicKey,
+        player: playerAccount,
+        caster: caster.publicKey,
+        item: inventoryAccount,
       },
+      signers: [someGuy],
     });
+
+    inventory = await program.account.inventory.fetch(inventoryAccount);
+    assert.equal(inventory.items[0].equippedOwner, null);
   });
 
-  it("commit craft", async () => {
+  it("can set a loadout", async () => {
     const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
       [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
       program.programId
     );
-    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
-      [
-        Buffer.from("turn_data"),
-        gameAccount.publicKey.toBuffer(),
-        Buffer.from(anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn))),
-      ],
+    const [inventoryAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("inventory"), playerAccount.toBuffer()],
       program.programId
     );
 
-    const equipment = {
-      equipment: {
-        feature: { power: {} },
-        rarity: { common: {} },
-        equipmentType: { head: {} },
-        value: 1,
-      },
-    };
-    const item1 = anchor.web3.Keypair.generate();
+    const headItem = anchor.web3.Keypair.generate();
+    const empty = anchor.web3.Keypair.generate();
 
-    await program.rpc.giveItem(equipment, new anchor.BN(1), {
-      accounts: {
-        systemProgram: anchor.web3.SystemProgram.programId,
-        game: gameAccount.publicKey,
-        authority: someGuy.publicKey,
-        player: playerAccount,
-        slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        item: item1.publicKey,
+    await debugProgram.rpc.giveItem(
+      {
+        equipment: {
+          feature: { fire: {} },
+          rarity: { common: {} },
+          equipmentType: { head: {} },
+          value: 1,
+        },
       },
-      signers: [someGuy, item1],
-    });
-
-    const item2 = anchor.web3.Keypair.generate();
+      new anchor.BN(1),
+      {
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          game: gameAccount.publicKey,
+          authority: someGuy.publicKey,
+          player: playerAccount,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          item: headItem.publicKey,
+        },
+        signers: [someGuy, headItem],
+      }
+    );
 
-    await program.rpc.giveItem(equipment, new anchor.BN(1), {
-      accounts: {
-        systemProgram: anchor.web3.SystemProgram.programId,
-        game: gameAccount.publicKey,
+    //Robe from the inventory can't go in the head slot
+    await assert.rejects(
+      program.rpc.setLoadout(
+        { head: { itemId: 0 }, robe: null, staff: null, spellBook: null },
+        {
+          accounts: {
+            game: gameAccount.publicKey,
+            authority: someGuy.publicKey,
+            player: playerAccount,
+            caster: caster.publicKey,
+            head: inventoryAccount,
+            robe: empty.publicKey,
+            staff: empty.publicKey,
+            spellBook: empty.publicKey,
+          },
+          signers: [someGuy],
+        }
+      )
+    );
+
+    await program.rpc.setLoadout(
+      {
+        head: { itemId: null },
+        robe: { itemId: 0 },
+        staff: null,
+        spellBook: null,
+      },
+      {
+        accounts: {
+          game: gameAccount.publicKey,
+          authority: someGuy.publicKey,
+          player: playerAccount,
+          caster: caster.publicKey,
+          head: headItem.publicKey,
+          robe: inventoryAccount,
+          staff: empty.publicKey,
+          spellBook: empty.publicKey,
+        },
+        signers: [someGuy],
+      }
+    );
+
+    let fetchedCaster = await program.account.caster.fetch(caster.publicKey);
+    assert.deepEqual(fetchedCaster.modifiers.head, headItem.publicKey);
+    assert.notEqual(fetchedCaster.modifiers.robe, null);
+    assert.deepEqual(
+      (await program.account.item.fetch(headItem.publicKey)).equippedOwner,
+      caster.publicKey
+    );
+    assert.deepEqual(
+      (await program.account.inventory.fetch(inventoryAccount)).items[0]
+        .equippedOwner,
+      caster.publicKey
+    );
+
+    //Emptying every slot takes the equipped items off
+    await program.rpc.setLoadout(
+      { head: null, robe: null, staff: null, spellBook: null },
+      {
+        accounts: {
+          game: gameAccount.publicKey,
+          authority: someGuy.publicKey,
+          player: playerAccount,
+          caster: caster.publicKey,
+          head: empty.publicKey,
+          robe: empty.publicKey,
+          staff: empty.publicKey,
+          spellBook: empty.publicKey,
+        },
+        remainingAccounts: [
+          { pubkey: headItem.publicKey, isWritable: true, isSigner: false },
+          { pubkey: inventoryAccount, isWritable: true, isSigner: false },
+        ],
+        signers: [someGuy],
+      }
+    );
+
+    fetchedCaster = await program.account.caster.fetch(caster.publicKey);
+    assert.equal(fetchedCaster.modifiers.head, null);
+    assert.equal(fetchedCaster.modifiers.robe, null);
+    assert.equal(
+      (await program.account.item.fetch(headItem.publicKey)).equippedOwner,
+      null
+    );
+    assert.equal(
+      (await program.account.inventory.fetch(inventoryAccount)).items[0]
+        .equippedOwner,
+      null
+    );
+  });
+
+  it("can open chests in a batch", async () => {
+    const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+      program.programId
+    );
+    const [inventoryAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("inventory"), playerAccount.toBuffer()],
+      program.programId
+    );
+
+    const chestItems = [
+      anchor.web3.Keypair.generate(),
+      anchor.web3.Keypair.generate(),
+    ];
+
+    for (const chestItem of chestItems) {
+      await debugProgram.rpc.giveItem({ chest: { tier: 1, seed: 1 } }, new anchor.BN(2), {
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          game: gameAccount.publicKey,
+          authority: someGuy.publicKey,
+          player: playerAccount,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          item: chestItem.publicKey,
+        },
+        signers: [someGuy, chestItem],
+      });
+    }
+
+    const itemsBefore = (await program.account.inventory.fetch(inventoryAccount))
+      .items.length;
+
+    //Chest then the 3 accounts receiving its items, here the inventory
+    const remainingAccounts = chestItems.flatMap((chestItem) => [
+      { pubkey: chestItem.publicKey, isWritable: true, isSigner: false },
+      { pubkey: inventoryAccount, isWritable: true, isSigner: false },
+      { pubkey: inventoryAccount, isWritable: true, isSigner: false },
+      { pubkey: inventoryAccount, isWritable: true, isSigner: false },
+    ]);
+
+    await program.rpc.openChests([null, null], {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        game: gameAccount.publicKey,
+        gameConfig: gameConfig,
+        authority: someGuy.publicKey,
+        player: playerAccount,
+        instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+      },
+      remainingAccounts,
+      signers: [someGuy],
+    });
+
+    for (const chestItem of chestItems) {
+      assert.equal(
+        await program.account.item.fetchNullable(chestItem.publicKey),
+        null
+      );
+    }
+
+    const inventory = await program.account.inventory.fetch(inventoryAccount);
+    assert.equal(inventory.items.length, itemsBefore + 6);
+
+    for (const item of inventory.items.slice(itemsBefore)) {
+      assert.equal(item.level <= 2 && item.level >= 1, true);
+      assert.equal(item.equippedOwner, null);
+    }
+  });
+
+  it("commit spell", async () => {
+    const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+      program.programId
+    );
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn))),
+      ],
+      program.programId
+    );
+
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await debugProgram.rpc.giveResources(new anchor.BN(500), {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        authority: someGuy.publicKey,
+        gameSigner: gameSigner,
+        game: gameAccount.publicKey,
+        player: playerAccount,
+        resource1MintAccount: mintResource1.publicKey,
+        resource2MintAccount: mintResource2.publicKey,
+        resource3MintAccount: mintResource3.publicKey,
+        resource1TokenAccount: ATAResource1,
+        resource2TokenAccount: ATAResource2,
+        resource3TokenAccount: ATAResource3,
+      },
+      signers: [someGuy],
+    });
+
+    const spellbook = {
+      spellBook: {
+        spell: { fire: {} },
+        costFeature: { fire: {} },
+        rarity: { common: {} },
+        cost: 3,
+        value: 1,
+      },
+    };
+    spellBook = anchor.web3.Keypair.generate();
+
+    await debugProgram.rpc.giveItem(spellbook, new anchor.BN(1), {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        game: gameAccount.publicKey,
+        authority: someGuy.publicKey,
+        player: playerAccount,
+        slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        item: spellBook.publicKey,
+      },
+      signers: [someGuy, spellBook],
+    });
+
+    await program.rpc.equipItem(null, {
+      accounts: {
+        game: gameAccount.publicKey,
+        authority: someGuy.publicKey,
+        player: playerAccount,
+        caster: caster.publicKey,
+        item: spellBook.publicKey,
+      },
+      signers: [someGuy],
+    });
+
+    const turnResource1Burned = (
+      await program.account.turnData.fetch(gameTurnData)
+    ).resource1Burned;
+
+    const someGuyFireResource = await getTokenAccountBalance(ATAResource1);
+
+    await program.rpc.casterCommitSpell(casterCommitment, null, {
+      accounts: {
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        authority: someGuy.publicKey,
+        game: gameAccount.publicKey,
+        gameConfig: gameConfig,
+        player: playerAccount,
+        caster: caster.publicKey,
+        instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+        resource1MintAccount: mintResource1.publicKey,
+        resource2MintAccount: mintResource2.publicKey,
+        resource3MintAccount: mintResource3.publicKey,
+        resource1TokenAccount: ATAResource1,
+        resource2TokenAccount: ATAResource2,
+        resource3TokenAccount: ATAResource3,
+        spellbook: spellBook.publicKey,
+        gameTurnData: gameTurnData,
+      },
+      signers: [someGuy],
+    });
+
+    //Assert game turn data resource burned higher
+    assert.equal(
+      (
+        await program.account.turnData.fetch(gameTurnData)
+      ).resource1Burned.toNumber(),
+      turnResource1Burned.add(new anchor.BN(3)).toNumber()
+    );
+
+    //Assert player has burned some resources
+    assert.equal(
+      await getTokenAccountBalance(ATAResource1),
+      someGuyFireResource - 3
+    );
+
+    //Assert spell book burned by the commit (get zombified)
+    assert.equal(
+      await program.account.item.fetchNullable(spellBook.publicKey),
+      null
+    );
+
+    //Assert turn commit created (deep equal) (actions & order actions)
+    const fetchedCaster = await program.account.caster.fetch(caster.publicKey);
+
+    assert.equal(fetchedCaster.turnCommit.resourcesBurned[0].toNumber(), 3);
+    assert.equal(fetchedCaster.turnCommit.resourcesBurned[1].toNumber(), 0);
+    assert.equal(fetchedCaster.turnCommit.resourcesBurned[2].toNumber(), 0);
+
+    delete fetchedCaster.turnCommit.resourcesBurned;
+
+    assert.deepEqual(fetchedCaster.turnCommit, {
+      turn: 1,
+      commitment: casterCommitment,
+      actions: {
+        loot: false,
+        spell: {
+          spell: { fire: {} },
+          rarity: { common: {} },
+          value: 1,
+          level: 1,
+        },
+        mv: null,
+        crafting: null,
+        actionOrder: [0, 1, 0, 0],
+        reforge: null,
+      },
+    });
+  });
+
+  it("commit craft", async () => {
+    const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+      program.programId
+    );
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn))),
+      ],
+      program.programId
+    );
+
+    const equipment = {
+      equipment: {
+        feature: { power: {} },
+        rarity: { common: {} },
+        equipmentType: { head: {} },
+        value: 1,
+      },
+    };
+    const item1 = anchor.web3.Keypair.generate();
+
+    await debugProgram.rpc.giveItem(equipment, new anchor.BN(1), {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        game: gameAccount.publicKey,
+        authority: someGuy.publicKey,
+        player: playerAccount,
+        slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        item: item1.publicKey,
+      },
+      signers: [someGuy, item1],
+    });
+
+    const item2 = anchor.web3.Keypair.generate();
+
+    await debugProgram.rpc.giveItem(equipment, new anchor.BN(1), {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        game: gameAccount.publicKey,
         authority: someGuy.publicKey,
         player: playerAccount,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
@@ -666,7 +1305,7 @@ describe("laddercast", () => {
 
     const item3 = anchor.web3.Keypair.generate();
 
-    await program.rpc.giveItem(equipment, new anchor.BN(1), {
+    await debugProgram.rpc.giveItem(equipment, new anchor.BN(1), {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         game: gameAccount.publicKey,
@@ -682,7 +1321,7 @@ describe("laddercast", () => {
       .modifiers;
 
     //Sets the tile as a crafting tile
-    await program.rpc.changeTile(
+    await debugProgram.rpc.changeTile(
       { crafting: {} },
       currentModifiers.tileLevel,
       currentModifiers.tileColumn,
@@ -702,7 +1341,7 @@ describe("laddercast", () => {
     ).turnCommit.resourcesBurned;
     let preSomeGuyResources = await getTokenAccountBalances();
 
-    await program.rpc.casterCommitCraft({
+    await program.rpc.casterCommitCraft(casterCommitment, [null, null, null], {
       accounts: {
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -710,6 +1349,7 @@ describe("laddercast", () => {
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
         resource1MintAccount: mintResource1.publicKey,
@@ -806,10 +1446,14 @@ describe("laddercast", () => {
 
     assert.deepEqual(fetchedCaster.turnCommit, {
       turn: 1,
+      commitment: casterCommitment,
       actions: {
         loot: false,
         spell: {
-          isExtraLevelBonus: false,
+          spell: { fire: {} },
+          rarity: { common: {} },
+          value: 1,
+          level: 1,
         },
         mv: null,
         crafting: {
@@ -818,11 +1462,12 @@ describe("laddercast", () => {
           maxRarity: { epic: {} },
         },
         actionOrder: [0, 1, 0, 2],
+        reforge: null,
       },
     });
   });
 
-  it("commit move", async () => {
+  it("can reforge an item", async () => {
     const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
       [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
       program.programId
@@ -836,14 +1481,155 @@ describe("laddercast", () => {
       program.programId
     );
 
-    let preGameTurnDataResourcesBurned = await program.account.turnData.fetch(
-      gameTurnData
-    );
-    let preTurnCommitResourceBurned = (
-      await program.account.caster.fetch(caster.publicKey)
-    ).turnCommit.resourcesBurned;
-    let preSomeGuyResources = await getTokenAccountBalances();
-
+    const equipment = {
+      equipment: {
+        feature: { power: {} },
+        rarity: { common: {} },
+        equipmentType: { head: {} },
+        value: 1,
+      },
+    };
+    reforgedItem = anchor.web3.Keypair.generate();
+
+    await debugProgram.rpc.giveItem(equipment, new anchor.BN(1), {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        game: gameAccount.publicKey,
+        authority: someGuy.publicKey,
+        player: playerAccount,
+        slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        item: reforgedItem.publicKey,
+      },
+      signers: [someGuy, reforgedItem],
+    });
+
+    const preGameTurnData = await program.account.turnData.fetch(gameTurnData);
+    const preSomeGuyResources = await getTokenAccountBalances();
+
+    //Caster is still on the crafting tile of the craft
+    await program.rpc.reforgeItem(casterCommitment, { value: {} }, null, {
+      accounts: {
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        authority: someGuy.publicKey,
+        game: gameAccount.publicKey,
+        gameConfig: gameConfig,
+        player: playerAccount,
+        caster: caster.publicKey,
+        instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+        resource1MintAccount: mintResource1.publicKey,
+        resource2MintAccount: mintResource2.publicKey,
+        resource3MintAccount: mintResource3.publicKey,
+        resource1TokenAccount: ATAResource1,
+        resource2TokenAccount: ATAResource2,
+        resource3TokenAccount: ATAResource3,
+        gameTurnData: gameTurnData,
+        item: reforgedItem.publicKey,
+      },
+      signers: [someGuy],
+    });
+
+    //Level 1 common costs the crafting cost multiplier of each resource
+    const postSomeGuyResources = await getTokenAccountBalances();
+    for (let i = 0; i < 3; i++) {
+      assert.equal(postSomeGuyResources[i], preSomeGuyResources[i] - 5);
+    }
+
+    const postGameTurnData = await program.account.turnData.fetch(gameTurnData);
+    assert.equal(
+      postGameTurnData.resource1Burned.toNumber(),
+      preGameTurnData.resource1Burned.toNumber() + 5
+    );
+
+    //Rerolled once the turn is over, from the secret and the turn's entropy,
+    //locked to the caster until then
+    const lockedItem = await program.account.item.fetch(reforgedItem.publicKey);
+    assert.deepEqual(lockedItem.itemType, equipment);
+    assert.equal(
+      lockedItem.equippedOwner.toString(),
+      caster.publicKey.toString()
+    );
+
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await assertRejectsWith(
+      program.rpc.salvageItem(null, {
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          authority: someGuy.publicKey,
+          game: gameAccount.publicKey,
+          player: playerAccount,
+          gameSigner: gameSigner,
+          resource1MintAccount: mintResource1.publicKey,
+          resource2MintAccount: mintResource2.publicKey,
+          resource3MintAccount: mintResource3.publicKey,
+          resource1TokenAccount: ATAResource1,
+          resource2TokenAccount: ATAResource2,
+          resource3TokenAccount: ATAResource3,
+          gameTurnData: gameTurnData,
+          item: reforgedItem.publicKey,
+        },
+        signers: [someGuy],
+      }),
+      "Item already in use by a caster, equipped or being reforged."
+    );
+  });
+
+  it("commit move", async () => {
+    const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+      program.programId
+    );
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn))),
+      ],
+      program.programId
+    );
+
+    let preGameTurnDataResourcesBurned = await program.account.turnData.fetch(
+      gameTurnData
+    );
+    let preTurnCommitResourceBurned = (
+      await program.account.caster.fetch(caster.publicKey)
+    ).turnCommit.resourcesBurned;
+    let preSomeGuyResources = await getTokenAccountBalances();
+
+    //Cannot move outside of the map
+    await assert.rejects(
+      program.rpc.casterCommitMove(0, mapColumns, {
+        accounts: {
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          authority: someGuy.publicKey,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+          player: playerAccount,
+          caster: caster.publicKey,
+          resource1MintAccount: mintResource1.publicKey,
+          resource2MintAccount: mintResource2.publicKey,
+          resource3MintAccount: mintResource3.publicKey,
+          resource1TokenAccount: ATAResource1,
+          resource2TokenAccount: ATAResource2,
+          resource3TokenAccount: ATAResource3,
+          gameTurnData: gameTurnData,
+        },
+        signers: [someGuy],
+      })
+    );
+
     await program.rpc.casterCommitMove(0, 1, {
       accounts: {
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -852,6 +1638,7 @@ describe("laddercast", () => {
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
         resource1MintAccount: mintResource1.publicKey,
@@ -865,6 +1652,31 @@ describe("laddercast", () => {
       signers: [someGuy],
     });
 
+    //Teleporting is the same action as moving
+    await assert.rejects(
+      program.rpc.casterCommitTeleport(1, 0, {
+        accounts: {
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          authority: someGuy.publicKey,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+          player: playerAccount,
+          caster: caster.publicKey,
+          resource1MintAccount: mintResource1.publicKey,
+          resource2MintAccount: mintResource2.publicKey,
+          resource3MintAccount: mintResource3.publicKey,
+          resource1TokenAccount: ATAResource1,
+          resource2TokenAccount: ATAResource2,
+          resource3TokenAccount: ATAResource3,
+          gameTurnData: gameTurnData,
+        },
+        signers: [someGuy],
+      })
+    );
+
     await new Promise((f) => setTimeout(f, 10000)); //Give time to sol to update the accounts
 
     let postGameTurnDataResourcesBurned = await program.account.turnData.fetch(
@@ -917,10 +1729,14 @@ describe("laddercast", () => {
 
     assert.deepEqual(fetchedCaster.turnCommit, {
       turn: 1,
+      commitment: casterCommitment,
       actions: {
         loot: false,
         spell: {
-          isExtraLevelBonus: false,
+          spell: { fire: {} },
+          rarity: { common: {} },
+          value: 1,
+          level: 1,
         },
         mv: [0, 1],
         crafting: {
@@ -929,6 +1745,11 @@ describe("laddercast", () => {
           maxRarity: { epic: {} },
         },
         actionOrder: [0, 1, 3, 2],
+        reforge: {
+          item: reforgedItem.publicKey,
+          itemId: null,
+          reforgeType: { value: {} },
+        },
       },
     });
   });
@@ -948,18 +1769,19 @@ describe("laddercast", () => {
     );
 
     //Sets the tile as a crafting tile
-    await program.rpc.changeTile({ fire: {} }, 0, 1, {
+    await debugProgram.rpc.changeTile({ fire: {} }, 0, 1, {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         game: gameAccount.publicKey,
       },
     });
 
-    await program.rpc.casterCommitLoot({
+    await program.rpc.casterCommitLoot(casterCommitment, {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
       },
@@ -973,10 +1795,14 @@ describe("laddercast", () => {
 
     assert.deepEqual(fetchedCaster.turnCommit, {
       turn: 1,
+      commitment: casterCommitment,
       actions: {
         loot: true,
         spell: {
-          isExtraLevelBonus: false,
+          spell: { fire: {} },
+          rarity: { common: {} },
+          value: 1,
+          level: 1,
         },
         mv: [0, 1],
         crafting: {
@@ -985,8 +1811,94 @@ describe("laddercast", () => {
           maxRarity: { epic: {} },
         },
         actionOrder: [4, 1, 3, 2],
+        reforge: {
+          item: reforgedItem.publicKey,
+          itemId: null,
+          reforgeType: { value: {} },
+        },
+      },
+    });
+  });
+
+  it("can salvage an item", async () => {
+    const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+      program.programId
+    );
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn))),
+      ],
+      program.programId
+    );
+
+    const equipment = {
+      equipment: {
+        feature: { earth: {} },
+        rarity: { common: {} },
+        equipmentType: { staff: {} },
+        value: 1,
+      },
+    };
+    const equipmentItem = anchor.web3.Keypair.generate();
+
+    await debugProgram.rpc.giveItem(equipment, new anchor.BN(2), {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        game: gameAccount.publicKey,
+        authority: someGuy.publicKey,
+        player: playerAccount,
+        slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        item: equipmentItem.publicKey,
+      },
+      signers: [someGuy, equipmentItem],
+    });
+
+    const preSalvaged = (await program.account.turnData.fetch(gameTurnData))
+      .resourcesSalvaged;
+    const preSomeGuyResources = await getTokenAccountBalances();
+
+    await program.rpc.salvageItem(null, {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        authority: someGuy.publicKey,
+        game: gameAccount.publicKey,
+        player: playerAccount,
+        gameSigner: gameSigner,
+        resource1MintAccount: mintResource1.publicKey,
+        resource2MintAccount: mintResource2.publicKey,
+        resource3MintAccount: mintResource3.publicKey,
+        resource1TokenAccount: ATAResource1,
+        resource2TokenAccount: ATAResource2,
+        resource3TokenAccount: ATAResource3,
+        gameTurnData: gameTurnData,
+        item: equipmentItem.publicKey,
       },
+      signers: [someGuy],
     });
+
+    //Level 2 common earth item gives 2 * 3 earth
+    const postSomeGuyResources = await getTokenAccountBalances();
+    assert.equal(postSomeGuyResources[2], preSomeGuyResources[2] + 6);
+    assert.equal(postSomeGuyResources[0], preSomeGuyResources[0]);
+
+    const postSalvaged = (await program.account.turnData.fetch(gameTurnData))
+      .resourcesSalvaged;
+    assert.equal(postSalvaged[2].toNumber(), preSalvaged[2].toNumber() + 6);
+
+    assert.equal(
+      await program.account.item.fetchNullable(equipmentItem.publicKey),
+      null
+    );
   });
 
   it("can manual_resource_burn", async () => {
@@ -1020,6 +1932,7 @@ describe("laddercast", () => {
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
         resource1MintAccount: mintResource1.publicKey,
@@ -1083,26 +1996,186 @@ describe("laddercast", () => {
       program.programId
     );
 
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const [tileHistory] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("tile_history"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    //Cranker gets 5 fire resources for advancing the turn
+    await program.rpc.updateGameConfig(
+      {
+        ...gameRules,
+        crankReward: {
+          resource: { tileType: { fire: {} }, amount: new anchor.BN(5) },
+        },
+      },
+      {
+        accounts: {
+          authority: gameAuthority.publicKey,
+          programAdmin: programAdmin,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+        },
+        signers: [gameAuthority],
+      }
+    );
+
     let oldFetchedGame = await program.account.game.fetch(
       gameAccount.publicKey
     );
+    const preCrankFireBalance = await getTokenAccountBalance(ATAResource1);
 
     await program.rpc.crank({
       accounts: {
         authority: someGuy.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
         gameAccount: gameAccount.publicKey,
+        gameSigner: gameSigner,
+        gameConfig: gameConfig,
         slots: SYSVAR_SLOT_HASHES_PUBKEY,
         instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
         gameTurnData: gameTurnData,
         currentGameTurnData: currentGameTurnData,
+        tileHistory: tileHistory,
       },
+      //Reward source and destination, only passed when the game pays a reward
+      remainingAccounts: [
+        { pubkey: mintResource1.publicKey, isWritable: true, isSigner: false },
+        { pubkey: ATAResource1, isWritable: true, isSigner: false },
+      ],
       signers: [someGuy],
     });
 
+    //Entropy of the turn that just ended is the hash of the slot the crank landed in,
+    //it can only be recorded once that slot is hashed
+    const recordTurnEntropy = () =>
+      program.rpc.recordTurnEntropy(gameTurnInfo.turn, {
+        accounts: {
+          game: gameAccount.publicKey,
+          turnData: currentGameTurnData,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        },
+      });
+
+    await assertRejectsWith(
+      program.rpc.recordTurnEntropy(gameTurnInfo.turn + 1, {
+        accounts: {
+          game: gameAccount.publicKey,
+          turnData: gameTurnData,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        },
+      }),
+      "Turn entropy can't be recorded before the turn is over and its crank slot hashed."
+    );
+
+    for (let attempt = 0; ; attempt++) {
+      try {
+        await recordTurnEntropy();
+        break;
+      } catch (e) {
+        if (attempt >= 10) {
+          throw e;
+        }
+        await new Promise((f) => setTimeout(f, 500));
+      }
+    }
+
+    const turnEntropy = (
+      await program.account.turnData.fetch(currentGameTurnData)
+    ).entropy;
+    assert.notDeepEqual(turnEntropy, Array(32).fill(0));
+
+    //Recording it again doesn't change it
+    await recordTurnEntropy();
+    assert.deepEqual(
+      (await program.account.turnData.fetch(currentGameTurnData)).entropy,
+      turnEntropy
+    );
+
     let fetchedGame = await program.account.game.fetch(gameAccount.publicKey);
 
+    assert.equal(
+      await getTokenAccountBalance(ATAResource1),
+      preCrankFireBalance + 5
+    );
+
+    assert.equal(
+      (await program.account.tileHistory.fetch(tileHistory)).game.toString(),
+      gameAccount.publicKey.toString()
+    );
+
+    assert.equal(
+      (await program.account.turnData.fetch(gameTurnData)).payer.toString(),
+      someGuy.publicKey.toString()
+    );
+
+    //Previous turn can still be redeemed
+    await assert.rejects(
+      program.rpc.closeTurnData(gameTurnInfo.turn, {
+        accounts: {
+          authority: someGuy.publicKey,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+          turnData: currentGameTurnData,
+          payer: gameAuthority.publicKey,
+        },
+        signers: [someGuy],
+      })
+    );
+
+    //Turn that was just added isn't over, nothing to catch up on
+    const [nextGameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(
+          anchor.utils.bytes.utf8.encode(String(gameTurnInfo.turn + 2))
+        ),
+      ],
+      program.programId
+    );
+
+    await assert.rejects(
+      program.rpc.crankMany(5, {
+        accounts: {
+          authority: someGuy.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          gameAccount: gameAccount.publicKey,
+          gameSigner: gameSigner,
+          gameConfig: gameConfig,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+          currentGameTurnData: gameTurnData,
+          tileHistory: tileHistory,
+        },
+        remainingAccounts: [
+          { pubkey: mintResource1.publicKey, isWritable: true, isSigner: false },
+          { pubkey: ATAResource1, isWritable: true, isSigner: false },
+          { pubkey: nextGameTurnData, isWritable: true, isSigner: false },
+        ],
+        signers: [someGuy],
+      })
+    );
+
+    await program.rpc.updateGameConfig(gameRules, {
+      accounts: {
+        authority: gameAuthority.publicKey,
+        programAdmin: programAdmin,
+        game: gameAccount.publicKey,
+        gameConfig: gameConfig,
+      },
+      signers: [gameAuthority],
+    });
+
     assert.equal(fetchedGame.turnInfo.turn, 2);
     assert.equal(
       fetchedGame.turnInfo.lastCrankSeconds >
@@ -1119,41 +2192,62 @@ describe("laddercast", () => {
     );
 
     const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("game_signer")],
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(
+          anchor.utils.bytes.utf8.encode(
+            String(
+              (
+                await program.account.caster.fetch(caster.publicKey)
+              ).turnCommit.turn
+            )
+          )
+        ),
+      ],
       program.programId
     );
 
     const item = anchor.web3.Keypair.generate();
 
-    await program.rpc.casterRedeemSpell({
-      accounts: {
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        rent: SYSVAR_RENT_PUBKEY,
-        authority: someGuy.publicKey,
-        game: gameAccount.publicKey,
-        player: playerAccount,
-        caster: caster.publicKey,
-        gameSigner: gameSigner,
-        slots: SYSVAR_SLOT_HASHES_PUBKEY,
-        instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
-        resource1MintAccount: mintResource1.publicKey,
-        resource2MintAccount: mintResource2.publicKey,
-        resource3MintAccount: mintResource3.publicKey,
-        resource1TokenAccount: ATAResource1,
-        resource2TokenAccount: ATAResource2,
-        resource3TokenAccount: ATAResource3,
-        item: item.publicKey,
-      },
+    const redeemSpellAccounts = {
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+      authority: someGuy.publicKey,
+      game: gameAccount.publicKey,
+      player: playerAccount,
+      caster: caster.publicKey,
+      gameSigner: gameSigner,
+      instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+      slots: SYSVAR_SLOT_HASHES_PUBKEY,
+      gameTurnData: gameTurnData,
+      resource1MintAccount: mintResource1.publicKey,
+      resource2MintAccount: mintResource2.publicKey,
+      resource3MintAccount: mintResource3.publicKey,
+      resource1TokenAccount: ATAResource1,
+      resource2TokenAccount: ATAResource2,
+      resource3TokenAccount: ATAResource3,
+      item: item.publicKey,
+    };
+
+    //Secret has to match the commitment
+    await assert.rejects(
+      program.rpc.casterRedeemSpell(Array(32).fill(1), {
+        accounts: redeemSpellAccounts,
+        signers: [item, someGuy],
+      })
+    );
+
+    await program.rpc.casterRedeemSpell(casterSecret, {
+      accounts: redeemSpellAccounts,
       signers: [item, someGuy],
-      remainingAccounts: [
-        {
-          pubkey: spellBook.publicKey,
-          isSigner: false,
-          isWritable: true,
-        },
-      ],
     });
 
     //Check caster changes
@@ -1164,12 +2258,6 @@ describe("laddercast", () => {
       fetchedCaster.turnCommit.actions.actionOrder,
       [4, 0, 3, 2]
     );
-
-    //Assert spell (get zombified)
-    assert.equal(
-      await program.account.item.fetchNullable(spellBook.publicKey),
-      null
-    );
   });
 
   it("redeem craft", async () => {
@@ -1178,23 +2266,46 @@ describe("laddercast", () => {
       program.programId
     );
 
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(
+          anchor.utils.bytes.utf8.encode(
+            String(
+              (
+                await program.account.caster.fetch(caster.publicKey)
+              ).turnCommit.turn
+            )
+          )
+        ),
+      ],
+      program.programId
+    );
+
     const item = anchor.web3.Keypair.generate();
+    const empty = anchor.web3.Keypair.generate();
 
     const itemPreCreation = await program.account.item.fetchNullable(
       item.publicKey
     );
 
-    await program.rpc.casterRedeemCraft({
+    await program.rpc.casterRedeemCraft(casterSecret, {
       accounts: {
         systemProgram: anchor.web3.SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
-        slots: SYSVAR_SLOT_HASHES_PUBKEY,
         instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+        slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        gameTurnData: gameTurnData,
         item: item.publicKey,
+        staff: empty.publicKey,
+        head: empty.publicKey,
+        robe: empty.publicKey,
       },
       signers: [item, someGuy],
     });
@@ -1254,7 +2365,7 @@ describe("laddercast", () => {
     );
 
     const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("game_signer")],
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
       program.programId
     );
 
@@ -1281,7 +2392,7 @@ describe("laddercast", () => {
 
     const preSomeGuyResources = await getTokenAccountBalances();
 
-    await program.rpc.casterRedeemLoot({
+    await program.rpc.casterRedeemLoot(casterSecret, {
       accounts: {
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -1289,11 +2400,12 @@ describe("laddercast", () => {
         rent: SYSVAR_RENT_PUBKEY,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
         gameSigner: gameSigner,
-        slots: SYSVAR_SLOT_HASHES_PUBKEY,
         instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+        slots: SYSVAR_SLOT_HASHES_PUBKEY,
         resource1MintAccount: mintResource1.publicKey,
         resource2MintAccount: mintResource2.publicKey,
         resource3MintAccount: mintResource3.publicKey,
@@ -1322,6 +2434,111 @@ describe("laddercast", () => {
     const postSomeGuyResources = await getTokenAccountBalances();
 
     assert.equal(postSomeGuyResources[0] > preSomeGuyResources[0], true);
+
+    //Item account is only created if a chest was found
+    const lootedItem = await program.account.item.fetchNullable(item.publicKey);
+
+    if (lootedItem !== null) {
+      // @ts-ignore
+      assert.notEqual(lootedItem.itemType.chest, undefined);
+    }
+  });
+
+  it("redeem reforge", async () => {
+    const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const turn = (await program.account.caster.fetch(caster.publicKey))
+      .turnCommit.turn;
+
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(anchor.utils.bytes.utf8.encode(String(turn))),
+      ],
+      program.programId
+    );
+
+    //Rewards end the turn, the reforge can't be left behind
+    await assertRejectsWith(
+      program.rpc.casterRedeemReward({
+        accounts: {
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          authority: someGuy.publicKey,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+          player: playerAccount,
+          caster: caster.publicKey,
+          gameSigner: gameSigner,
+          instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+          ladaMintAccount: ladaMint.publicKey,
+          gameLadaTokenAccount: gameLADATokenAccount,
+          ladaTokenAccount: someGuyLADATokenAccount,
+          gameTurnData: gameTurnData,
+        },
+        signers: [someGuy],
+      }),
+      "Reforge has to be redeemed before the rewards."
+    );
+
+    const redeemReforgeAccounts = {
+      authority: someGuy.publicKey,
+      game: gameAccount.publicKey,
+      player: playerAccount,
+      caster: caster.publicKey,
+      instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+      slots: SYSVAR_SLOT_HASHES_PUBKEY,
+      gameTurnData: gameTurnData,
+      item: reforgedItem.publicKey,
+    };
+
+    //Secret has to match the commitment
+    await assertRejectsWith(
+      program.rpc.casterRedeemReforge(turn, Array(32).fill(1), {
+        accounts: redeemReforgeAccounts,
+        signers: [someGuy],
+      }),
+      "Secret doesn't match the committed hash."
+    );
+
+    await program.rpc.casterRedeemReforge(turn, casterSecret, {
+      accounts: redeemReforgeAccounts,
+      signers: [someGuy],
+    });
+
+    //Only value in the band of a level 1 common power item
+    const item = await program.account.item.fetch(reforgedItem.publicKey);
+    assert.deepEqual(item.itemType.equipment.feature, { power: {} });
+    assert.equal(item.itemType.equipment.value, 100);
+
+    //Released once rerolled
+    assert.equal(item.equippedOwner, null);
+
+    assert.equal(
+      // @ts-ignore
+      (await program.account.caster.fetch(caster.publicKey)).turnCommit.actions
+        .reforge,
+      null
+    );
+
+    //Nothing left to reforge
+    await assertRejectsWith(
+      program.rpc.casterRedeemReforge(turn, casterSecret, {
+        accounts: redeemReforgeAccounts,
+        signers: [someGuy],
+      }),
+      "No reforge to redeem."
+    );
   });
 
   it("redeem rewards", async () => {
@@ -1331,7 +2548,7 @@ describe("laddercast", () => {
     );
 
     const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("game_signer")],
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
       program.programId
     );
 
@@ -1370,6 +2587,7 @@ describe("laddercast", () => {
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         authority: someGuy.publicKey,
         game: gameAccount.publicKey,
+        gameConfig: gameConfig,
         player: playerAccount,
         caster: caster.publicKey,
         gameSigner: gameSigner,
@@ -1401,4 +2619,1037 @@ describe("laddercast", () => {
       gameLadaBalanceBefore - 1_984_126_984_130
     );
   });
+
+  it("can cancel a pending turn", async () => {
+    const [playerAccount] = await anchor.web3.PublicKey.findProgramAddress(
+      [gameAccount.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const currentTurn = (await program.account.game.fetch(gameAccount.publicKey))
+      .turnInfo.turn;
+
+    const [gameTurnData] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("turn_data"),
+        gameAccount.publicKey.toBuffer(),
+        Buffer.from(anchor.utils.bytes.utf8.encode(String(currentTurn))),
+      ],
+      program.programId
+    );
+
+    const preGameTurnData = await program.account.turnData.fetch(gameTurnData);
+
+    await program.rpc.manualResourceBurn({ water: {} }, new anchor.BN(10), {
+      accounts: {
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        authority: someGuy.publicKey,
+        game: gameAccount.publicKey,
+        gameConfig: gameConfig,
+        player: playerAccount,
+        caster: caster.publicKey,
+        resource1MintAccount: mintResource1.publicKey,
+        resource2MintAccount: mintResource2.publicKey,
+        resource3MintAccount: mintResource3.publicKey,
+        resource1TokenAccount: ATAResource1,
+        resource2TokenAccount: ATAResource2,
+        resource3TokenAccount: ATAResource3,
+        gameTurnData: gameTurnData,
+      },
+      signers: [someGuy],
+    });
+
+    assert.notEqual(
+      (await program.account.caster.fetch(caster.publicKey)).turnCommit,
+      null
+    );
+
+    const [gameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("game_signer"), gameAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const empty = anchor.web3.Keypair.generate();
+
+    //Turn isn't over yet, nothing to redeem in a single instruction either
+    await assert.rejects(
+      program.rpc.casterRedeemTurn(casterSecret, {
+        accounts: {
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          authority: someGuy.publicKey,
+          game: gameAccount.publicKey,
+          gameConfig: gameConfig,
+          player: playerAccount,
+          caster: caster.publicKey,
+          gameSigner: gameSigner,
+          instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          resource1MintAccount: mintResource1.publicKey,
+          resource2MintAccount: mintResource2.publicKey,
+          resource3MintAccount: mintResource3.publicKey,
+          resource1TokenAccount: ATAResource1,
+          resource2TokenAccount: ATAResource2,
+          resource3TokenAccount: ATAResource3,
+          gameLadaTokenAccount: gameLADATokenAccount,
+          ladaTokenAccount: someGuyLADATokenAccount,
+          gameTurnData: gameTurnData,
+          staff: empty.publicKey,
+          head: empty.publicKey,
+          robe: empty.publicKey,
+        },
+        signers: [someGuy],
+      })
+    );
+
+    await program.rpc.casterCancelTurn(currentTurn, {
+      accounts: {
+        authority: someGuy.publicKey,
+        game: gameAccount.publicKey,
+        player: playerAccount,
+        caster: caster.publicKey,
+        gameTurnData: gameTurnData,
+      },
+      signers: [someGuy],
+    });
+
+    //Commit is gone and its burn no longer counts toward the turn's LADA split
+    assert.equal(
+      (await program.account.caster.fetch(caster.publicKey)).turnCommit,
+      null
+    );
+    assert.equal(
+      (await program.account.turnData.fetch(gameTurnData)).resource2Burned.toNumber(),
+      preGameTurnData.resource2Burned.toNumber()
+    );
+
+    //Nothing left to cancel
+    await assertRejectsWith(
+      program.rpc.casterCancelTurn(currentTurn, {
+        accounts: {
+          authority: someGuy.publicKey,
+          game: gameAccount.publicKey,
+          player: playerAccount,
+          caster: caster.publicKey,
+          gameTurnData: gameTurnData,
+        },
+        signers: [someGuy],
+      }),
+      "No turn to redeem."
+    );
+  });
+
+  it("admin instructions reject a non admin signer", async () => {
+    await assertRejectsWith(
+      program.rpc.updateMerkleRoot("combined", 0, Array(32).fill(0), {
+        accounts: {
+          authority: someGuy.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          programAdmin: programAdmin,
+          gameAccount: gameAccount.publicKey,
+          merkleRootNft: (
+            await anchor.web3.PublicKey.findProgramAddress(
+              [
+                Buffer.from("merkle_roots"),
+                gameAccount.publicKey.toBuffer(),
+                Buffer.from("combined"),
+                Buffer.from("0"),
+              ],
+              program.programId
+            )
+          )[0],
+        },
+        signers: [someGuy],
+      }),
+      "Only super admin can create new game."
+    );
+
+    await assertRejectsWith(
+      program.rpc.fixRedeemSpell({
+        accounts: {
+          authority: someGuy.publicKey,
+          programAdmin: programAdmin,
+          caster: caster.publicKey,
+        },
+        signers: [someGuy],
+      }),
+      "Only super admin can create new game."
+    );
+
+    await assertRejectsWith(
+      program.rpc.burnLada({
+        accounts: {
+          authority: someGuy.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          programAdmin: programAdmin,
+          gameAccount: gameAccount.publicKey,
+          ladaMint: ladaMint.publicKey,
+          ladaTokenAccount: someGuyLADATokenAccount,
+        },
+        signers: [someGuy],
+      }),
+      "Only super admin can create new game."
+    );
+
+    await assertRejectsWith(
+      program.rpc.closeGame({
+        accounts: {
+          authority: someGuy.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          programAdmin: programAdmin,
+          gameAccount: gameAccount.publicKey,
+        },
+        signers: [someGuy],
+      }),
+      "Only super admin can create new game."
+    );
+
+    //Game is still there
+    assert.notEqual(
+      await connection.getAccountInfo(gameAccount.publicKey),
+      null
+    );
+  });
+
+  //Second game where a turn lasts a second, for what needs several turns to go by
+  describe("with one second turns", () => {
+    const quickGame = anchor.web3.Keypair.generate();
+
+    const quickMintResource1 = anchor.web3.Keypair.generate();
+    const quickMintResource2 = anchor.web3.Keypair.generate();
+    const quickMintResource3 = anchor.web3.Keypair.generate();
+
+    const quickTurnInfo = {
+      turn: 1,
+      turnDelay: 1,
+      lastCrankSeconds: new anchor.BN(0),
+      lastTileSpawn: 0,
+      tileSpawnDelay: 20,
+    } as GameTurnInfo;
+
+    let quickGameSigner: anchor.web3.PublicKey;
+    let quickGameConfig: anchor.web3.PublicKey;
+    let quickGameLADATokenAccount: anchor.web3.PublicKey;
+    let quickTileHistory: anchor.web3.PublicKey;
+    let quickPlayer: anchor.web3.PublicKey;
+
+    let quickATAResource1: anchor.web3.PublicKey;
+    let quickATAResource2: anchor.web3.PublicKey;
+    let quickATAResource3: anchor.web3.PublicKey;
+
+    function writable(pubkey: anchor.web3.PublicKey) {
+      return { pubkey, isWritable: true, isSigner: false };
+    }
+
+    async function getQuickTurnData(turn: number) {
+      return (
+        await anchor.web3.PublicKey.findProgramAddress(
+          [
+            Buffer.from("turn_data"),
+            quickGame.publicKey.toBuffer(),
+            Buffer.from(anchor.utils.bytes.utf8.encode(String(turn))),
+          ],
+          program.programId
+        )
+      )[0];
+    }
+
+    function updateQuickGameRules(rules: GameRules) {
+      return program.rpc.updateGameConfig(rules, {
+        accounts: {
+          authority: gameAuthority.publicKey,
+          programAdmin: programAdmin,
+          game: quickGame.publicKey,
+          gameConfig: quickGameConfig,
+        },
+        signers: [gameAuthority],
+      });
+    }
+
+    //Waits for the turn to be over then cranks it, resolves to the turn that ended
+    async function crankQuickGame(
+      remainingAccounts: anchor.web3.AccountMeta[] = []
+    ) {
+      await new Promise((f) => setTimeout(f, 1500));
+
+      const turn = (await program.account.game.fetch(quickGame.publicKey))
+        .turnInfo.turn;
+
+      await program.rpc.crank({
+        accounts: {
+          authority: someGuy.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          gameAccount: quickGame.publicKey,
+          gameSigner: quickGameSigner,
+          gameConfig: quickGameConfig,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+          gameTurnData: await getQuickTurnData(turn + 1),
+          currentGameTurnData: await getQuickTurnData(turn),
+          tileHistory: quickTileHistory,
+        },
+        remainingAccounts,
+        signers: [someGuy],
+      });
+
+      return turn;
+    }
+
+    async function initQuickCaster() {
+      const quickCaster = anchor.web3.Keypair.generate();
+
+      await ladaMint.mintTo(
+        someGuyLADATokenAccount,
+        mintAuthority.publicKey,
+        [mintAuthority],
+        1000 * DECIMALS
+      );
+
+      await program.rpc.initCaster({
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          authority: someGuy.publicKey,
+          game: quickGame.publicKey,
+          gameConfig: quickGameConfig,
+          player: quickPlayer,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+          ladaMint: ladaMint.publicKey,
+          caster: quickCaster,
+          ladaTokenAccount: someGuyLADATokenAccount,
+        },
+        signers: [someGuy, quickCaster],
+      });
+
+      return quickCaster;
+    }
+
+    //Accounts of the commits burning resources, for the current turn
+    async function getQuickCommitAccounts(caster: anchor.web3.PublicKey) {
+      const turn = (await program.account.game.fetch(quickGame.publicKey))
+        .turnInfo.turn;
+
+      return {
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        authority: someGuy.publicKey,
+        game: quickGame.publicKey,
+        gameConfig: quickGameConfig,
+        player: quickPlayer,
+        caster: caster,
+        resource1MintAccount: quickMintResource1.publicKey,
+        resource2MintAccount: quickMintResource2.publicKey,
+        resource3MintAccount: quickMintResource3.publicKey,
+        resource1TokenAccount: quickATAResource1,
+        resource2TokenAccount: quickATAResource2,
+        resource3TokenAccount: quickATAResource3,
+        gameTurnData: await getQuickTurnData(turn),
+      };
+    }
+
+    //Redeems the whole turn committed by the caster, once it's cranked
+    async function redeemQuickTurn(
+      caster: anchor.web3.PublicKey,
+      remainingAccounts: anchor.web3.AccountMeta[] = []
+    ) {
+      const { turn } = (await program.account.caster.fetch(caster)).turnCommit;
+      const empty = anchor.web3.Keypair.generate();
+
+      const redeemTx = program.transaction.casterRedeemTurn(casterSecret, {
+        accounts: {
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          authority: someGuy.publicKey,
+          game: quickGame.publicKey,
+          gameConfig: quickGameConfig,
+          player: quickPlayer,
+          caster: caster,
+          gameSigner: quickGameSigner,
+          instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+          slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          resource1MintAccount: quickMintResource1.publicKey,
+          resource2MintAccount: quickMintResource2.publicKey,
+          resource3MintAccount: quickMintResource3.publicKey,
+          resource1TokenAccount: quickATAResource1,
+          resource2TokenAccount: quickATAResource2,
+          resource3TokenAccount: quickATAResource3,
+          gameLadaTokenAccount: quickGameLADATokenAccount,
+          ladaTokenAccount: someGuyLADATokenAccount,
+          gameTurnData: await getQuickTurnData(turn),
+          staff: empty.publicKey,
+          head: empty.publicKey,
+          robe: empty.publicKey,
+        },
+        remainingAccounts,
+      });
+
+      return program.provider.send(redeemTx, [someGuy]);
+    }
+
+    it("Initializes a game with one second turns", async () => {
+      [quickGameSigner] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("game_signer"), quickGame.publicKey.toBuffer()],
+        program.programId
+      );
+      [quickGameConfig] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("game_config"), quickGame.publicKey.toBuffer()],
+        program.programId
+      );
+      [quickTileHistory] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("tile_history"), quickGame.publicKey.toBuffer()],
+        program.programId
+      );
+      [quickPlayer] = await anchor.web3.PublicKey.findProgramAddress(
+        [quickGame.publicKey.toBuffer(), someGuy.publicKey.toBuffer()],
+        program.programId
+      );
+
+      quickGameLADATokenAccount = await ladaMint.createAccount(quickGameSigner);
+
+      await ladaMint.mintTo(
+        quickGameLADATokenAccount,
+        mintAuthority.publicKey,
+        [mintAuthority],
+        3000 * DECIMALS
+      );
+
+      [quickATAResource1, quickATAResource2, quickATAResource3] =
+        await Promise.all(
+          [quickMintResource1, quickMintResource2, quickMintResource3].map(
+            (mint) =>
+              Token.getAssociatedTokenAddress(
+                ASSOCIATED_TOKEN_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+                mint.publicKey,
+                someGuy.publicKey
+              )
+          )
+        );
+
+      await program.rpc.initGame(
+        quickTurnInfo,
+        gameRules,
+        mapLevels,
+        mapColumns,
+        {
+          accounts: {
+            authority: gameAuthority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: SYSVAR_RENT_PUBKEY,
+            programAdmin: programAdmin,
+            gameAccount: quickGame.publicKey,
+            gameSigner: quickGameSigner,
+            gameTurnData: await getQuickTurnData(quickTurnInfo.turn),
+            gameConfig: quickGameConfig,
+            slots: SYSVAR_SLOT_HASHES_PUBKEY,
+            resource1Mint: quickMintResource1.publicKey,
+            resource2Mint: quickMintResource2.publicKey,
+            resource3Mint: quickMintResource3.publicKey,
+            ladaMint: ladaMint.publicKey,
+            ladaTokenAccount: quickGameLADATokenAccount,
+          },
+          signers: [
+            gameAuthority,
+            quickGame,
+            quickMintResource1,
+            quickMintResource2,
+            quickMintResource3,
+          ],
+        }
+      );
+
+      await program.rpc.initPlayer({
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          authority: someGuy.publicKey,
+          game: quickGame.publicKey,
+          playerAccount: quickPlayer,
+        },
+        signers: [someGuy],
+      });
+
+      //Creates the player's resource token accounts too
+      await debugProgram.rpc.giveResources(new anchor.BN(1000), {
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          authority: someGuy.publicKey,
+          gameSigner: quickGameSigner,
+          game: quickGame.publicKey,
+          player: quickPlayer,
+          resource1MintAccount: quickMintResource1.publicKey,
+          resource2MintAccount: quickMintResource2.publicKey,
+          resource3MintAccount: quickMintResource3.publicKey,
+          resource1TokenAccount: quickATAResource1,
+          resource2TokenAccount: quickATAResource2,
+          resource3TokenAccount: quickATAResource3,
+        },
+        signers: [someGuy],
+      });
+
+      assert.deepEqual(
+        (await program.account.game.fetch(quickGame.publicKey)).turnInfo,
+        quickTurnInfo
+      );
+    });
+
+    it("pays the crank reward", async () => {
+      const rewardEvents = [];
+      const listener = program.addEventListener(
+        "CrankRewarded",
+        (event) => {
+          rewardEvents.push(event);
+        }
+      );
+
+      //LADA from the game's LADA account
+      await updateQuickGameRules({
+        ...gameRules,
+        crankReward: { lada: { amount: new anchor.BN(2 * DECIMALS) } },
+      });
+
+      //Accounts of the reward are needed once the game pays one
+      await assertRejectsWith(
+        crankQuickGame(),
+        "Invalid crank reward accounts."
+      );
+
+      //Only paid to a token account of the cranker
+      await assertRejectsWith(
+        crankQuickGame([
+          writable(quickGameLADATokenAccount),
+          writable(gameLADATokenAccount),
+        ]),
+        "Invalid crank reward accounts."
+      );
+
+      const prePlayerLada = await getTokenAccountBalance(
+        someGuyLADATokenAccount
+      );
+      const preGameLada = await getTokenAccountBalance(
+        quickGameLADATokenAccount
+      );
+
+      const ladaRewardTurn = await crankQuickGame([
+        writable(quickGameLADATokenAccount),
+        writable(someGuyLADATokenAccount),
+      ]);
+
+      assert.equal(
+        await getTokenAccountBalance(someGuyLADATokenAccount),
+        prePlayerLada + 2 * DECIMALS
+      );
+      assert.equal(
+        await getTokenAccountBalance(quickGameLADATokenAccount),
+        preGameLada - 2 * DECIMALS
+      );
+
+      //Resource minted by the game signer
+      await updateQuickGameRules({
+        ...gameRules,
+        crankReward: {
+          resource: { tileType: { water: {} }, amount: new anchor.BN(7) },
+        },
+      });
+
+      const preWater = await getTokenAccountBalance(quickATAResource2);
+
+      const resourceRewardTurn = await crankQuickGame([
+        writable(quickMintResource2.publicKey),
+        writable(quickATAResource2),
+      ]);
+
+      assert.equal(
+        await getTokenAccountBalance(quickATAResource2),
+        preWater + 7
+      );
+
+      //Without a reward the crank takes no reward accounts, like before there were rewards
+      await updateQuickGameRules(gameRules);
+      await crankQuickGame();
+
+      for (let attempt = 0; rewardEvents.length < 2 && attempt < 20; attempt++) {
+        await new Promise((f) => setTimeout(f, 500));
+      }
+      await program.removeEventListener(listener);
+
+      //One event per paid crank, for the turn it added
+      assert.equal(rewardEvents.length, 2);
+      assert.equal(rewardEvents[0].turn, ladaRewardTurn + 1);
+      assert.equal(
+        rewardEvents[0].cranker.toString(),
+        someGuy.publicKey.toString()
+      );
+      assert.equal(
+        rewardEvents[0].reward.lada.amount.toNumber(),
+        2 * DECIMALS
+      );
+      assert.equal(rewardEvents[1].turn, resourceRewardTurn + 1);
+      assert.deepEqual(rewardEvents[1].reward.resource.tileType, {
+        water: {},
+      });
+      assert.equal(rewardEvents[1].reward.resource.amount.toNumber(), 7);
+    });
+
+    it("cranks several turns at once", async () => {
+      const preGame = await program.account.game.fetch(quickGame.publicKey);
+      const startTurn = preGame.turnInfo.turn;
+
+      const crankManyAccounts = {
+        authority: someGuy.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        gameAccount: quickGame.publicKey,
+        gameSigner: quickGameSigner,
+        gameConfig: quickGameConfig,
+        slots: SYSVAR_SLOT_HASHES_PUBKEY,
+        instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+        currentGameTurnData: await getQuickTurnData(startTurn),
+        tileHistory: quickTileHistory,
+      };
+
+      const addedTurnData = [];
+      for (let turn = startTurn + 1; turn <= startTurn + 3; turn++) {
+        addedTurnData.push(await getQuickTurnData(turn));
+      }
+
+      //Enough time for more turns than there are turn data accounts
+      await new Promise((f) => setTimeout(f, 4500));
+
+      //Like the crank, it has to be the last instruction
+      const notLastTx = program.transaction.crankMany(5, {
+        accounts: crankManyAccounts,
+        remainingAccounts: addedTurnData.map(writable),
+      });
+      notLastTx.add(
+        program.instruction.recordTurnEntropy(startTurn - 1, {
+          accounts: {
+            game: quickGame.publicKey,
+            turnData: await getQuickTurnData(startTurn - 1),
+            slots: SYSVAR_SLOT_HASHES_PUBKEY,
+          },
+        })
+      );
+      await assert.rejects(program.provider.send(notLastTx, [someGuy]));
+
+      await program.rpc.crankMany(5, {
+        accounts: crankManyAccounts,
+        remainingAccounts: addedTurnData.map(writable),
+        signers: [someGuy],
+      });
+
+      const postGame = await program.account.game.fetch(quickGame.publicKey);
+
+      //Limited by the turn data accounts passed
+      assert.equal(postGame.turnInfo.turn, startTurn + 3);
+      assert.equal(postGame.lastTurnAdded, startTurn + 3);
+
+      //Only the time of the turns added is counted
+      assert.equal(
+        postGame.turnInfo.lastCrankSeconds.toNumber(),
+        preGame.turnInfo.lastCrankSeconds.toNumber() +
+          3 * quickTurnInfo.turnDelay
+      );
+
+      const endedTurnData = await program.account.turnData.fetch(
+        crankManyAccounts.currentGameTurnData
+      );
+      assert.notEqual(endedTurnData.entropySlot.toNumber(), 0);
+
+      //Every turn in between gets its turn data, paid by the cranker and with
+      //its entropy coming from the same crank slot
+      for (const turnDataKey of addedTurnData) {
+        const turnData = await program.account.turnData.fetch(turnDataKey);
+
+        assert.equal(turnData.payer.toString(), someGuy.publicKey.toString());
+        assert.equal(
+          turnData.entropySlot.toNumber(),
+          endedTurnData.entropySlot.toNumber()
+        );
+        assert.deepEqual(turnData.entropy, Array(32).fill(0));
+        assert.equal(turnData.map.length, mapLevels);
+        assert.equal(turnData.map[0].length, mapColumns);
+      }
+
+      assert.equal(
+        await connection.getAccountInfo(await getQuickTurnData(startTurn + 4)),
+        null
+      );
+    });
+
+    it("closes turn data past the retention", async () => {
+      await updateQuickGameRules({ ...gameRules, turnDataRetention: 2 });
+
+      const currentTurn = (await program.account.game.fetch(quickGame.publicKey))
+        .turnInfo.turn;
+
+      const closeTurnData = (turn: number, turnData: anchor.web3.PublicKey) =>
+        program.rpc.closeTurnData(turn, {
+          accounts: {
+            authority: gameAuthority.publicKey,
+            game: quickGame.publicKey,
+            gameConfig: quickGameConfig,
+            turnData: turnData,
+            payer: someGuy.publicKey,
+          },
+          signers: [gameAuthority],
+        });
+
+      //Still within the retention
+      const retainedTurn = currentTurn - 2;
+      await assertRejectsWith(
+        closeTurnData(retainedTurn, await getQuickTurnData(retainedTurn)),
+        "Turn data can still be redeemed, cannot close it yet."
+      );
+
+      //Added by a crank of someGuy
+      const expiredTurn = currentTurn - 3;
+      const expiredTurnData = await getQuickTurnData(expiredTurn);
+
+      assert.equal(
+        (
+          await program.account.turnData.fetch(expiredTurnData)
+        ).payer.toString(),
+        someGuy.publicKey.toString()
+      );
+
+      const turnDataLamports = (await connection.getAccountInfo(expiredTurnData))
+        .lamports;
+      const prePayerLamports = await connection.getBalance(someGuy.publicKey);
+
+      await closeTurnData(expiredTurn, expiredTurnData);
+
+      //Rent goes back to whoever paid for it, not to whoever closed it
+      assert.equal(
+        await connection.getBalance(someGuy.publicKey),
+        prePayerLamports + turnDataLamports
+      );
+      assert.equal(await connection.getAccountInfo(expiredTurnData), null);
+
+      await updateQuickGameRules(gameRules);
+    });
+
+    it("redeems a whole turn in one instruction", async () => {
+      const quickCaster = await initQuickCaster();
+      const [quickInventory] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("inventory"), quickPlayer.toBuffer()],
+        program.programId
+      );
+
+      await program.rpc.initInventory({
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          authority: someGuy.publicKey,
+          game: quickGame.publicKey,
+          player: quickPlayer,
+          inventory: quickInventory,
+        },
+        signers: [someGuy],
+      });
+
+      const giveItem = async (itemType: any) => {
+        const item = anchor.web3.Keypair.generate();
+
+        await debugProgram.rpc.giveItem(itemType, new anchor.BN(1), {
+          accounts: {
+            systemProgram: anchor.web3.SystemProgram.programId,
+            game: quickGame.publicKey,
+            authority: someGuy.publicKey,
+            player: quickPlayer,
+            slots: SYSVAR_SLOT_HASHES_PUBKEY,
+            item: item.publicKey,
+          },
+          signers: [someGuy, item],
+        });
+
+        return item.publicKey;
+      };
+
+      //Legendary spell books work half of the time, the spell item is only
+      //there when it does
+      const spellBook = await giveItem({
+        spellBook: {
+          spell: { item: {} },
+          costFeature: { fire: {} },
+          rarity: { legendary: {} },
+          cost: 3,
+          value: 1,
+        },
+      });
+
+      await program.rpc.equipItem(null, {
+        accounts: {
+          game: quickGame.publicKey,
+          authority: someGuy.publicKey,
+          player: quickPlayer,
+          caster: quickCaster,
+          item: spellBook,
+        },
+        signers: [someGuy],
+      });
+
+      const equipment = {
+        equipment: {
+          feature: { power: {} },
+          rarity: { common: {} },
+          equipmentType: { head: {} },
+          value: 1,
+        },
+      };
+      const craftedItems = [
+        await giveItem(equipment),
+        await giveItem(equipment),
+        await giveItem(equipment),
+      ];
+
+      //Loots an earth tile then moves next to it to craft
+      const { tileLevel, tileColumn } = (
+        await program.account.caster.fetch(quickCaster)
+      ).modifiers;
+      const craftingColumn = tileColumn === 0 ? 1 : tileColumn - 1;
+
+      const changeTile = (tileType: any, column: number) =>
+        debugProgram.rpc.changeTile(tileType, tileLevel, column, {
+          accounts: {
+            systemProgram: anchor.web3.SystemProgram.programId,
+            game: quickGame.publicKey,
+          },
+        });
+
+      await changeTile({ earth: {} }, tileColumn);
+      await changeTile({ crafting: {} }, craftingColumn);
+
+      //Always finds a chest
+      await updateQuickGameRules({
+        ...gameRules,
+        defaultMagicFindInPercent: 10000,
+      });
+
+      const commitAccounts = await getQuickCommitAccounts(quickCaster);
+
+      await program.rpc.casterCommitLoot(casterCommitment, {
+        accounts: {
+          systemProgram: anchor.web3.SystemProgram.programId,
+          authority: someGuy.publicKey,
+          game: quickGame.publicKey,
+          gameConfig: quickGameConfig,
+          player: quickPlayer,
+          caster: quickCaster,
+        },
+        signers: [someGuy],
+      });
+
+      await program.rpc.casterCommitSpell(casterCommitment, null, {
+        accounts: {
+          ...commitAccounts,
+          instructionSysvarAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+          spellbook: spellBook,
+        },
+        signers: [someGuy],
+      });
+
+      await program.rpc.casterCommitMove(tileLevel, craftingColumn, {
+        accounts: commitAccounts,
+        signers: [someGuy],
+      });
+
+      await program.rpc.casterCommitCraft(
+        casterCommitment,
+        [null, null, null],
+        {
+          accounts: {
+            ...commitAccounts,
+            item1: craftedItems[0],
+            item2: craftedItems[1],
+            item3: craftedItems[2],
+          },
+          signers: [someGuy],
+        }
+      );
+
+      //Only this caster burned resources this turn, so it gets all its LADA
+      const { resourcesBurned } = (
+        await program.account.caster.fetch(quickCaster)
+      ).turnCommit;
+      assert.deepEqual(
+        resourcesBurned.map((burned) => burned.toNumber()),
+        [3 + 5, 5, 5]
+      );
+
+      const preResources = await Promise.all(
+        [quickATAResource1, quickATAResource2, quickATAResource3].map(
+          getTokenAccountBalance
+        )
+      );
+      const prePlayerLada = await getTokenAccountBalance(
+        someGuyLADATokenAccount
+      );
+      const preGameLada = await getTokenAccountBalance(
+        quickGameLADATokenAccount
+      );
+
+      await crankQuickGame();
+
+      //Entropy is recorded by the redeem, once the crank slot is hashed
+      await new Promise((f) => setTimeout(f, 1000));
+
+      //Every item produced goes to the inventory
+      await redeemQuickTurn(quickCaster, [writable(quickInventory)]);
+
+      const redeemedCaster = await program.account.caster.fetch(quickCaster);
+      assert.equal(redeemedCaster.turnCommit, null);
+      assert.equal(redeemedCaster.modifiers.tileLevel, tileLevel);
+      assert.equal(redeemedCaster.modifiers.tileColumn, craftingColumn);
+      assert.equal(redeemedCaster.modifiers.spellBook, null);
+
+      //Loot from the earth tile only, the spell gives an item
+      const postResources = await Promise.all(
+        [quickATAResource1, quickATAResource2, quickATAResource3].map(
+          getTokenAccountBalance
+        )
+      );
+      assert.equal(postResources[0], preResources[0]);
+      assert.equal(postResources[1], preResources[1]);
+      assert.equal(postResources[2] > preResources[2], true);
+
+      //Chest of the loot first, then the spell item if any, then the craft
+      const { items } = await program.account.inventory.fetch(quickInventory);
+      assert.equal(items.length === 2 || items.length === 3, true);
+
+      assert.equal(items[0].level, tileLevel + 1);
+      // @ts-ignore
+      assert.equal(items[0].itemType.chest.tier, 1);
+
+      for (const item of items.slice(1)) {
+        // @ts-ignore
+        assert.notEqual(item.itemType.equipment, undefined);
+        assert.equal(item.level >= 1, true);
+        assert.equal(item.equippedOwner, null);
+      }
+
+      for (const craftedItem of craftedItems) {
+        assert.equal(
+          await program.account.item.fetchNullable(craftedItem),
+          null
+        );
+      }
+
+      assert.equal(
+        await getTokenAccountBalance(someGuyLADATokenAccount),
+        prePlayerLada + gameRules.ladaDistributionPerTurn.toNumber()
+      );
+      assert.equal(
+        await getTokenAccountBalance(quickGameLADATokenAccount),
+        preGameLada - gameRules.ladaDistributionPerTurn.toNumber()
+      );
+
+      await updateQuickGameRules(gameRules);
+    });
+
+    it("teleports down the ladder and across columns", async () => {
+      const teleporter = await initQuickCaster();
+
+      //Every turn redeemed takes the LADA of the turn from the game
+      await ladaMint.mintTo(
+        quickGameLADATokenAccount,
+        mintAuthority.publicKey,
+        [mintAuthority],
+        6000 * DECIMALS
+      );
+
+      const changeTile = (tileType: any, level: number, column: number) =>
+        debugProgram.rpc.changeTile(tileType, level, column, {
+          accounts: {
+            systemProgram: anchor.web3.SystemProgram.programId,
+            game: quickGame.publicKey,
+          },
+        });
+
+      const commitTeleport = async (level: number, column: number) =>
+        program.rpc.casterCommitTeleport(level, column, {
+          accounts: await getQuickCommitAccounts(teleporter),
+          signers: [someGuy],
+        });
+
+      const { tileColumn } = (await program.account.caster.fetch(teleporter))
+        .modifiers;
+
+      //Burning enough to reach level 2, so the level above can be moved to
+      await program.rpc.manualResourceBurn({ earth: {} }, new anchor.BN(502), {
+        accounts: await getQuickCommitAccounts(teleporter),
+        signers: [someGuy],
+      });
+      await crankQuickGame();
+      await redeemQuickTurn(teleporter);
+
+      assert.equal((await program.account.caster.fetch(teleporter)).level, 2);
+
+      await changeTile({ water: {} }, 1, tileColumn);
+
+      //Climbing is only done by moves
+      await assertRejectsWith(commitTeleport(1, tileColumn), "Invalid Move.");
+
+      await program.rpc.casterCommitMove(1, tileColumn, {
+        accounts: await getQuickCommitAccounts(teleporter),
+        signers: [someGuy],
+      });
+      await crankQuickGame();
+      await redeemQuickTurn(teleporter);
+
+      //Down a level and across to the farthest column
+      const destColumn = tileColumn === 0 ? mapColumns - 1 : 0;
+      const distance = 1 + Math.abs(tileColumn - destColumn);
+
+      await changeTile({ fire: {} }, 0, destColumn);
+
+      //Teleporting has to go to another tile
+      await assertRejectsWith(commitTeleport(1, tileColumn), "Invalid Move.");
+
+      const preFire = await getTokenAccountBalance(quickATAResource1);
+
+      await commitTeleport(0, destColumn);
+
+      //Cost of moving to the destination plus the distance covered
+      const teleportCost =
+        gameRules.moveCostMultiplier * (0 + 1) +
+        distance * gameRules.teleportCostMultiplier;
+
+      assert.equal(
+        await getTokenAccountBalance(quickATAResource1),
+        preFire - teleportCost
+      );
+      assert.deepEqual(
+        (
+          await program.account.caster.fetch(teleporter)
+        ).turnCommit.resourcesBurned.map((burned) => burned.toNumber()),
+        [teleportCost, 0, 0]
+      );
+
+      await crankQuickGame();
+      await redeemQuickTurn(teleporter);
+
+      const teleportedCaster = await program.account.caster.fetch(teleporter);
+      assert.equal(teleportedCaster.turnCommit, null);
+      assert.equal(teleportedCaster.modifiers.tileLevel, 0);
+      assert.equal(teleportedCaster.modifiers.tileColumn, destColumn);
+    });
+  });
 });
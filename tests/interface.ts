@@ -12,3 +12,55 @@ export interface GameTurnInfo {
   // how many turns til next tile should spawn
   tileSpawnDelay: number; // 64
 }
+
+export interface GameRules {
+  //resources burned per level when moving
+  moveCostMultiplier: number; // 8
+  //resources burned of each type per level when crafting
+  craftingCostMultiplier: number; // 8
+  //resource tile distribution per level band, ordered by maxLevel
+  tileBands: TileBand[]; // 3
+  //default critical chance, 200 = 2%
+  defaultCriticalChanceInPercent: number; // 16
+  //default chance of finding a chest, 1000 = 10%
+  defaultMagicFindInPercent: number; // 16
+  //LADA given each turn (with 9 decimals)
+  ladaDistributionPerTurn: anchor.BN; // 64
+  //LADA burned to create a caster
+  costInLadaForCaster: number; // 16
+  //paid to whoever pulls the crank
+  crankReward: CrankReward;
+  //turns a turn data is kept after its turn before it can be closed
+  turnDataRetention: number; // 32
+  //what a chest gives when opened, tier 1 first
+  chestTiers: ChestTier[]; // 4
+  //resources burned per level skipped or column crossed when teleporting, on top of the move cost
+  teleportCostMultiplier: number; // 8
+}
+
+export interface TileBand {
+  //last level (0 based) of the band
+  maxLevel: number; // 8
+  //relative chance of Earth, Water and Fire
+  weights: number[]; // 3 * 16
+  minLife: number; // 8
+  maxLife: number; // 8
+}
+
+export interface ChestTier {
+  //lowest level of the items, the chest level is the highest
+  minItemLevel: number; // 8
+  //number of items given
+  itemCount: number; // 8
+  //chance (0-100) of an item being a spell book
+  spellBookChance: number; // 8
+  //relative chance of Common, Rare, Epic and Legendary
+  rarityWeights: number[]; // 4 * 16
+}
+
+//Only one of the keys is set
+export interface CrankReward {
+  none?: {};
+  lada?: { amount: anchor.BN };
+  resource?: { tileType: object; amount: anchor.BN };
+}